@@ -0,0 +1,109 @@
+// Trilha de acesso do dashboard: avistamentos exibidos, exportações e expurgos vão
+// para o log de auditoria encadeado (intelligence_db::audit_log). Se o evento não
+// puder ser gravado o comando falha — nada é exibido sem registro. Gravação e
+// supervisão são as de intelligence_db::operator, comuns ao catálogo.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::audit::ExportFormat;
+use intelligence_db::audit_log::{self, AuditEvent, AuditFilter, ChainReport};
+use intelligence_db::operator::{self, OperatorStore};
+use intelligence_db::{AppError, AppResult};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::settings::SettingsStore;
+
+// O polling repete a mesma lista a cada poucos segundos. A primeira exibição vira
+// evento na hora; as repetições seguintes (mesmo operador, mesma lista, dentro de
+// `SIGHTINGS_WINDOW`) só são contadas e saem num evento próprio, com `repeats` e a
+// janela `from`/`to`, assim que a lista ou o operador muda ou a janela fecha.
+const SIGHTINGS_WINDOW: Duration = Duration::from_secs(300);
+
+struct Displayed {
+    actor:   String,
+    detail:  serde_json::Value,
+    started: Instant,
+    from:    String,
+    to:      String,
+    repeats: u32,
+}
+
+#[derive(Default)]
+pub struct AccessLog {
+    last_sightings: Mutex<Option<Displayed>>,
+}
+
+impl AccessLog {
+    pub fn record_sightings(&self, store: &SettingsStore, actor: &str, detail: serde_json::Value) -> AppResult<()> {
+        let mut last = self.last_sightings.lock().unwrap();
+        if let Some(shown) = last.as_mut() {
+            if shown.actor == actor && shown.detail == detail && shown.started.elapsed() < SIGHTINGS_WINDOW {
+                shown.repeats += 1;
+                shown.to = audit_log::now();
+                return Ok(());
+            }
+            if shown.repeats > 0 {
+                let mut summary = shown.detail.clone();
+                summary["repeats"] = shown.repeats.into();
+                summary["window"] = serde_json::json!({ "from": shown.from, "to": shown.to });
+                store.record(&shown.actor, "get_recent_sightings", None, &summary)?;
+                shown.repeats = 0;
+            }
+        }
+        store.record(actor, "get_recent_sightings", None, &detail)?;
+        let at = audit_log::now();
+        *last = Some(Displayed {
+            actor: actor.to_string(),
+            detail,
+            started: Instant::now(),
+            from: at.clone(),
+            to: at,
+            repeats: 0,
+        });
+        Ok(())
+    }
+}
+
+// ─── Comandos (supervisão) ────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn verify_audit_log(store: State<'_, SettingsStore>, sessions: State<'_, Sessions>) -> AppResult<ChainReport> {
+    operator::verify_audit_log(&*store, &sessions)
+}
+
+#[tauri::command]
+pub fn get_audit_log(
+    filter:   Option<AuditFilter>,
+    store:    State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Vec<AuditEvent>> {
+    operator::get_audit_log(&*store, &sessions, &filter.unwrap_or_default())
+}
+
+// Eventos do filtro + estado da cadeia (JSON) ou só os eventos (CSV), onde o usuário escolher
+#[tauri::command]
+pub async fn export_audit_log(
+    filter:   Option<AuditFilter>,
+    format:   ExportFormat,
+    app:      AppHandle,
+    store:    State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Option<PathBuf>> {
+    let admin = store.require(&sessions, Role::Admin)?;
+    let filter = filter.unwrap_or_default();
+    let Some(picked) = app
+        .dialog()
+        .file()
+        .set_file_name(audit_log::export_file_name(format))
+        .add_filter(format.extension().to_uppercase(), &[format.extension()])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| AppError::invalid_input("path", e))?;
+    operator::export_audit_log(&*store, &admin, &filter, format, &path)?;
+    Ok(Some(path))
+}
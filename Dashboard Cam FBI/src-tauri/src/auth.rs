@@ -0,0 +1,6 @@
+// Login, tela de bloqueio e contas dos operadores: os comandos Tauri de
+// intelligence_db::operator, os mesmos do catálogo.
+
+use crate::settings::SettingsStore;
+
+intelligence_db::auth_commands!(SettingsStore);
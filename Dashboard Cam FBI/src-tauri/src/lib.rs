@@ -1,6 +1,21 @@
+mod audit_log;
+mod auth;
+mod retention;
+mod settings;
+
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::PathBuf;
-use sysinfo::{CpuExt, System, SystemExt, ComponentExt};
+use std::sync::Arc;
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::integrity::{self, IntegrityReport};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::review::{self, ReviewCounts, ReviewEvent, ReviewScope};
+use intelligence_db::{AppError, AppResult, Backend, BackendSlot, ReviewStatus, SchemaInfo, Sighting, SightingReview};
+use audit_log::AccessLog;
+use retention::RetentionState;
+use settings::{DashboardStore, SettingsStore};
+use tauri::{Manager, State};
+use sysinfo::{Components, System};
 
 #[derive(serde::Serialize)]
 struct SystemStats {
@@ -13,117 +28,158 @@ struct SystemStats {
     cores_usage: Vec<f32>,
 }
 
-#[tauri::command]
-fn get_live_id(search_term: String) -> Result<String, String> {
-    // Executa o yt-dlp para buscar o ID mais recente
-    // Usamos o comando que já validamos no Python
-    let search_query = format!("ytsearch1:{} live", search_term);
-    let output = Command::new("yt-dlp")
-        .args(&[
-            "--get-id",
-            "--no-warnings",
-            "--flat-playlist",
-            &search_query
-        ])
-        .output()
-        .map_err(|e| e.to_string())?;
-
-    if output.status.success() {
-        let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if id.is_empty() {
-            Err("Nenhum vídeo encontrado".to_string())
-        } else {
-            Ok(id)
-        }
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr);
-        Err(err.to_string())
-    }
-}
+const YT_DLP: &str = "yt-dlp";
 
 #[tauri::command]
-fn get_cameras() -> Result<String, String> {
-    // Usando caminho absoluto para garantir acesso em ambiente de desenvolvimento
-    let path = PathBuf::from("/home/douglasdsr/Documentos/Projects/FBI/Dashboard/database/omni_cams.json");
+fn get_cameras(store: State<'_, SettingsStore>, sessions: State<'_, Sessions>) -> AppResult<String> {
+    store.require(&sessions, Role::Viewer)?;
+    let path = store.require_paths()?.cams_file;
 
     if !path.exists() {
         println!("[OSS ERROR] Arquivo não encontrado em: {:?}", path);
         return Ok("[]".to_string());
     }
 
-    fs::read_to_string(path).map_err(|e| e.to_string())
+    fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))
 }
 
-#[derive(serde::Serialize)]
-struct Sighting {
-    id: String,
-    individual_id: String,
-    camera_id: Option<String>,
-    captured_at: String,
-    name: String,
-    threat_score: f64,
+// Backend (SQLite somente-leitura ou Postgres) aberto na primeira chamada e
+// descartado quando as configurações mudam
+fn db(store: &SettingsStore, slot: &BackendSlot) -> AppResult<Arc<dyn Backend>> {
+    if let Some(backend) = slot.get() {
+        return Ok(backend);
+    }
+    let cfg = store.backend_config()?;
+    slot.get_or_open_with(&cfg, |backend| {
+        let schema = backend.schema();
+        println!("[OSS] Usando banco de dados: {}", backend.describe());
+        println!("[OSS] Schema v{}/{} (ausente: {:?})", schema.version, schema.latest, schema.missing);
+    })
+    .map_err(AppError::from_open)
 }
 
 #[tauri::command]
-fn get_recent_sightings() -> Result<Vec<Sighting>, String> {
-    let path = "/home/douglasdsr/Documentos/Projects/FBI/Dashboard/intelligence/data/intelligence.db";
-    let conn = rusqlite::Connection::open(path).map_err(|e| e.to_string())?;
-
-    let mut stmt = conn.prepare(
-        "SELECT e.id, e.individual_id, e.camera_id, e.captured_at, i.name, COALESCE(t.score, 1.0) as threat_score
-         FROM evidence e
-         JOIN individuals i ON e.individual_id = i.id
-         LEFT JOIN threat_scores t ON e.individual_id = t.individual_id
-         ORDER BY e.captured_at DESC
-         LIMIT 50"
-    ).map_err(|e| e.to_string())?;
-
-    let sighting_iter = stmt.query_map([], |row| {
-        Ok(Sighting {
-            id: row.get(0)?,
-            individual_id: row.get(1)?,
-            camera_id: row.get(2)?,
-            captured_at: row.get(3)?,
-            name: row.get(4)?,
-            threat_score: row.get(5)?,
-        })
-    }).map_err(|e| e.to_string())?;
+fn get_schema_info(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<SchemaInfo> {
+    store.require(&sessions, Role::Viewer)?;
+    Ok(db(&store, &slot)?.schema().clone())
+}
 
-    let mut sightings = Vec::new();
-    for sighting in sighting_iter {
-        sightings.push(sighting.map_err(|e| e.to_string())?);
+// Padrão: só avistamentos revisados (confirmados/disputados); rejeitados nunca.
+// Pendentes (ainda sem revisão) só para revisores.
+#[tauri::command]
+fn get_recent_sightings(
+    scope:    Option<ReviewScope>,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    access:   State<'_, AccessLog>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Vec<Sighting>> {
+    let scope = scope.unwrap_or_default();
+    let required = if scope == ReviewScope::Reviewed { Role::Viewer } else { Role::Reviewer };
+    let account = store.require(&sessions, required)?;
+    let backend = db(&store, &slot)?;
+    let review_file = store.review_file()?;
+    let mut sightings = review::recent_sightings(backend.as_ref(), &review_file, scope, 50)?;
+    let ids: Vec<&str> = sightings.iter().map(|s| s.id.as_str()).collect();
+    let individuals: BTreeSet<&str> = sightings.iter().map(|s| s.individual_id.as_str()).collect();
+    access.record_sightings(&store, &account.username, serde_json::json!({ "scope": scope, "ids": ids, "individuals": individuals }))?;
+    // Sem o sidecar de integridade os avistamentos seguem como "unverified"
+    let integrity_file = store.app_data_file(integrity::INTEGRITY_FILE);
+    if let Err(e) = integrity::annotate(backend.as_ref(), &integrity_file, store.data_root().as_deref(), &mut sightings) {
+        println!("[OSS] Integridade das evidências indisponível: {}", e);
     }
-
     Ok(sightings)
 }
 
+// Re-hasheia os arquivos de evidência (todos, ou só `ids`) contra evidence.file_hash
+#[tauri::command]
+fn verify_evidence(
+    ids:      Option<Vec<String>>,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<IntegrityReport> {
+    store.require(&sessions, Role::Reviewer)?;
+    let backend = db(&store, &slot)?;
+    let integrity_file = store.app_data_file(integrity::INTEGRITY_FILE);
+    Ok(integrity::verify(backend.as_ref(), &integrity_file, store.data_root().as_deref(), ids.as_deref())?)
+}
+
+// O revisor registrado é a conta logada
+#[tauri::command]
+fn review_sighting(
+    evidence_id: String,
+    status:      ReviewStatus,
+    notes:       Option<String>,
+    store:       State<'_, SettingsStore>,
+    slot:        State<'_, BackendSlot>,
+    sessions:    State<'_, Sessions>,
+) -> AppResult<SightingReview> {
+    let reviewer = store.require(&sessions, Role::Reviewer)?;
+    let backend = db(&store, &slot)?;
+    let review_file = store.review_file()?;
+    Ok(review::set_review(backend.as_ref(), &review_file, &evidence_id, status, &reviewer.username, notes.as_deref())?)
+}
+
 #[tauri::command]
-fn get_system_stats() -> SystemStats {
+fn get_review_history(
+    evidence_id: String,
+    store:       State<'_, SettingsStore>,
+    sessions:    State<'_, Sessions>,
+) -> AppResult<Vec<ReviewEvent>> {
+    store.require(&sessions, Role::Viewer)?;
+    Ok(review::history(&store.review_file()?, &evidence_id)?)
+}
+
+#[tauri::command]
+fn get_review_counts(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<ReviewCounts> {
+    store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    Ok(review::counts(backend.as_ref(), &store.review_file()?)?)
+}
+
+#[tauri::command]
+fn get_integrity_report(store: State<'_, SettingsStore>, sessions: State<'_, Sessions>) -> AppResult<IntegrityReport> {
+    store.require(&sessions, Role::Viewer)?;
+    Ok(integrity::report(&store.app_data_file(integrity::INTEGRITY_FILE))?)
+}
+
+#[tauri::command]
+fn get_system_stats(store: State<'_, SettingsStore>, sessions: State<'_, Sessions>) -> AppResult<SystemStats> {
+    store.require(&sessions, Role::Viewer)?;
     let mut sys = System::new_all();
     sys.refresh_all();
     
     // Pequena pausa para o sysinfo calcular o uso da CPU corretamente na primeira vez (ou se for chamado rápido demais)
     std::thread::sleep(std::time::Duration::from_millis(100));
-    sys.refresh_cpu();
+    sys.refresh_cpu_usage();
 
-    let cpu_usage = sys.global_cpu_info().cpu_usage();
+    let cpu_usage = sys.global_cpu_usage();
     let cpu_count = sys.cpus().len();
     let memory_total = sys.total_memory() / 1024 / 1024; // MB
     let memory_used = sys.used_memory() / 1024 / 1024;   // MB
-    let uptime = sys.uptime();
+    let uptime = System::uptime();
     
     let cores_usage: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
 
     // Tenta pegar a temperatura do primeiro componente relevante (Linux)
     let mut temp = 0.0;
-    for component in sys.components() {
+    for component in &Components::new_with_refreshed_list() {
         if component.label().contains("CPU") || component.label().contains("Package") || component.label().contains("k10temp") {
-            temp = component.temperature();
+            temp = component.temperature().unwrap_or(0.0);
             break;
         }
     }
 
-    SystemStats {
+    Ok(SystemStats {
         cpu_usage,
         cpu_count,
         memory_total,
@@ -131,35 +187,40 @@ fn get_system_stats() -> SystemStats {
         temp,
         uptime,
         cores_usage,
-    }
+    })
 }
 
 #[tauri::command]
-fn get_live_id(search_term: String) -> Result<String, String> {
+fn get_live_id(
+    search_term: String,
+    store:       State<'_, SettingsStore>,
+    sessions:    State<'_, Sessions>,
+) -> AppResult<String> {
+    store.require(&sessions, Role::Viewer)?;
     use std::process::Command;
     // Executa o yt-dlp para buscar o ID mais recente
     // Usamos o comando que já validamos no Python
     let search_query = format!("ytsearch1:{} live", search_term);
-    let output = Command::new("yt-dlp")
-        .args(&[
+    let output = Command::new(YT_DLP)
+        .args([
             "--get-id",
             "--no-warnings",
             "--flat-playlist",
             &search_query
         ])
         .output()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| AppError::service_unavailable(YT_DLP, e))?;
 
     if output.status.success() {
         let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
         if id.is_empty() {
-            Err("Nenhum vídeo encontrado".to_string())
+            Err(AppError::NotFound(search_term))
         } else {
             Ok(id)
         }
     } else {
         let err = String::from_utf8_lossy(&output.stderr);
-        Err(err.to_string())
+        Err(AppError::service_failed(YT_DLP, err.trim()))
     }
 }
 
@@ -172,7 +233,49 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet, get_live_id, get_cameras, get_recent_sightings, get_system_stats])
+        .plugin(tauri_plugin_dialog::init())
+        .manage(BackendSlot::default())
+        .manage(RetentionState::default())
+        .manage(AccessLog::default())
+        .manage(Sessions::default())
+        .setup(|app| {
+            app.manage(settings::load(app.handle())?);
+            retention::spawn_scheduler(app.handle().clone());
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            auth::get_session,
+            auth::setup_admin,
+            auth::login,
+            auth::logout,
+            auth::lock_session,
+            auth::touch_session,
+            auth::change_password,
+            auth::list_accounts,
+            auth::create_account,
+            auth::update_account,
+            get_live_id,
+            get_cameras,
+            get_recent_sightings,
+            review_sighting,
+            get_review_history,
+            get_review_counts,
+            verify_evidence,
+            get_integrity_report,
+            retention::preview_retention_purge,
+            retention::run_retention_purge,
+            retention::get_retention_history,
+            audit_log::verify_audit_log,
+            audit_log::get_audit_log,
+            audit_log::export_audit_log,
+            get_schema_info,
+            get_system_stats,
+            settings::get_settings,
+            settings::update_settings,
+            settings::validate_data_root,
+            settings::pick_data_root,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,119 @@
+// Política de retenção das evidências (settings.json `retention`): prévia, expurgo
+// manual e o agendador, que roda o expurgo ao abrir o app e depois a cada
+// `interval_hours` enquanto a política estiver ligada. Todo expurgo real vai para o
+// log de auditoria (audit_log.rs) com o relatório completo. Só admins expurgam,
+// veem a prévia e o histórico.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::audit_log::{self, AuditEvent, AuditFilter};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::retention::{self, PurgeReport, RetentionPolicy};
+use intelligence_db::{integrity, review, AppResult, BackendSlot};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::settings::{DashboardStore, SettingsStore};
+
+pub const AUDIT_ACTION: &str = "retention_purge";
+
+const TICK:            Duration = Duration::from_secs(60);
+const SCHEDULER_ACTOR: &str = "retention-scheduler";
+
+#[derive(Default)]
+pub struct RetentionState {
+    // Um expurgo por vez (agendador e botão); guarda quando foi o último
+    last_run: Mutex<Option<Instant>>,
+}
+
+fn purge(
+    store:   &SettingsStore,
+    slot:    &BackendSlot,
+    state:   &RetentionState,
+    policy:  &RetentionPolicy,
+    actor:   &str,
+    dry_run: bool,
+) -> AppResult<PurgeReport> {
+    let backend = crate::db(store, slot)?;
+    let mut last_run = state.last_run.lock().unwrap();
+    let report = retention::run(backend.as_ref(), policy, store.data_root().as_deref(), dry_run)?;
+    if dry_run {
+        return Ok(report);
+    }
+    *last_run = Some(Instant::now());
+
+    // Conferências e decisões de revisão das evidências apagadas (o histórico fica)
+    let deleted = report.deleted_ids();
+    if !deleted.is_empty() {
+        if let Err(e) = integrity::forget(&store.app_data_file(integrity::INTEGRITY_FILE), &deleted) {
+            println!("[OSS] Retenção: integridade não atualizada: {}", e);
+        }
+        if let Err(e) = store.review_file().and_then(|file| Ok(review::forget(&file, &deleted)?)) {
+            println!("[OSS] Retenção: revisões não atualizadas: {}", e);
+        }
+    }
+    let detail = serde_json::to_value(&report).unwrap_or_default();
+    audit_log::record(&store.audit_file(), actor, AUDIT_ACTION, None, &detail)?;
+    Ok(report)
+}
+
+// Thread do agendador: confere a cada minuto se a política está ligada e se o
+// intervalo já passou desde o último expurgo
+pub fn spawn_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let store = app.state::<SettingsStore>();
+        let state = app.state::<RetentionState>();
+        let policy = store.get().app.retention;
+        let due = state.last_run.lock().unwrap().is_none_or(|t| t.elapsed() >= policy.interval());
+        if policy.enabled && due && store.paths().is_some() {
+            let slot = app.state::<BackendSlot>();
+            if let Err(e) = purge(&store, &slot, &state, &policy, SCHEDULER_ACTOR, false) {
+                println!("[OSS] Retenção: expurgo agendado falhou: {}", e);
+                // Sem repetir a cada minuto: espera o próximo intervalo
+                *state.last_run.lock().unwrap() = Some(Instant::now());
+            }
+        }
+        std::thread::sleep(TICK);
+    });
+}
+
+// ─── Comandos ─────────────────────────────────────────────────────────────────
+
+// Sem apagar nada: o que venceria com `policy` (ou com a política salva)
+#[tauri::command]
+pub fn preview_retention_purge(
+    policy:   Option<RetentionPolicy>,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    state:    State<'_, RetentionState>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<PurgeReport> {
+    let admin = store.require(&sessions, Role::Admin)?;
+    let policy = policy.unwrap_or_else(|| store.get().app.retention);
+    purge(&store, &slot, &state, &policy, &admin.username, true)
+}
+
+// Expurgo imediato com a política salva (mesmo com o agendador desligado)
+#[tauri::command]
+pub fn run_retention_purge(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    state:    State<'_, RetentionState>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<PurgeReport> {
+    let admin = store.require(&sessions, Role::Admin)?;
+    let policy = store.get().app.retention;
+    purge(&store, &slot, &state, &policy, &admin.username, false)
+}
+
+// Últimos expurgos registrados no log de auditoria
+#[tauri::command]
+pub fn get_retention_history(
+    limit:    Option<u32>,
+    store:    State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Vec<AuditEvent>> {
+    store.require(&sessions, Role::Admin)?;
+    let filter = AuditFilter { action: Some(AUDIT_ACTION.into()), limit: Some(limit.unwrap_or(20)), ..Default::default() };
+    Ok(audit_log::list(&store.audit_file(), &filter)?)
+}
@@ -0,0 +1,74 @@
+// Configuração persistente do dashboard. O comum aos dois apps (settings.json, pasta
+// de dados, banco, bloqueio e os comandos de configuração) está em
+// intelligence_db::settings; aqui só a política de retenção e o review.db.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::retention::RetentionPolicy;
+use intelligence_db::review;
+use intelligence_db::settings::{self, env_path, AppSettings};
+use intelligence_db::{AppError, AppResult, BackendSlot, DatabaseSettings};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+const ENV_REVIEW_DB: &str = "OSS_REVIEW_DB";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DashboardSettings {
+    pub retention: RetentionPolicy,
+}
+
+impl AppSettings for DashboardSettings {
+    const ENV_VARS: &'static [&'static str] = &[ENV_REVIEW_DB];
+}
+
+pub type SettingsStore = settings::SettingsStore<DashboardSettings>;
+pub type SettingsView = settings::SettingsView<DashboardSettings>;
+
+pub fn load(app: &AppHandle) -> AppResult<SettingsStore> {
+    let config_dir = app.path().app_config_dir().map_err(|e| AppError::Settings(e.to_string()))?;
+    // Sidecars do app (integridade das evidências etc.): nunca dentro da pasta de dados
+    // (o review.db, compartilhado com a ingestão, é a exceção)
+    let data_dir = app.path().app_data_dir().map_err(|e| AppError::Settings(e.to_string()))?;
+    Ok(SettingsStore::load(&config_dir, data_dir))
+}
+
+// Caminhos só do dashboard, sobre o SettingsStore comum
+pub trait DashboardStore {
+    fn review_file(&self) -> AppResult<PathBuf>;
+}
+
+impl DashboardStore for SettingsStore {
+    // Revisões dos avistamentos: ao lado do intelligence.db (ou em OSS_REVIEW_DB), o
+    // mesmo arquivo que o dossiê Python lê para excluir os rejeitados
+    fn review_file(&self) -> AppResult<PathBuf> {
+        match env_path(ENV_REVIEW_DB) {
+            Some(path) => Ok(path),
+            None => Ok(self.require_paths()?.db_file.with_file_name(review::REVIEW_FILE)),
+        }
+    }
+}
+
+// ─── Comandos ─────────────────────────────────────────────────────────────────
+
+intelligence_db::settings_commands!(DashboardSettings);
+
+#[tauri::command]
+pub fn update_settings(
+    data_root:         Option<String>,
+    database:          Option<DatabaseSettings>,
+    retention:         Option<RetentionPolicy>,
+    idle_lock_minutes: Option<u32>,
+    store:             State<'_, SettingsStore>,
+    slot:              State<'_, BackendSlot>,
+    sessions:          State<'_, Sessions>,
+) -> AppResult<SettingsView> {
+    store.require(&sessions, Role::Admin)?;
+    if let Some(retention) = retention {
+        retention.validate()?;
+        store.update(|s| s.app.retention = retention)?;
+    }
+    store.update_common(&slot, data_root, database, idle_lock_minutes)
+}
@@ -0,0 +1,65 @@
+// Trilha de acesso do catálogo: buscas, fichas, comparações, imagens abertas e
+// exportações vão para o log de auditoria encadeado (intelligence_db::audit_log).
+// Se o evento não puder ser gravado o comando falha — nada é exibido sem registro.
+// Miniaturas do grid não geram evento: a busca que as listou já registrou os ids.
+// Gravação e supervisão são as de intelligence_db::operator, comuns ao dashboard.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::audit::ExportFormat;
+use intelligence_db::audit_log::{self, AuditEvent, AuditFilter, ChainReport};
+use intelligence_db::operator::{self, OperatorStore};
+use intelligence_db::{AppError, AppResult};
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::settings::SettingsStore;
+
+// Só os campos preenchidos do filtro (o resto é null)
+pub fn compact(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map.into_iter().filter(|(_, v)| !v.is_null()).collect(),
+        other => other,
+    }
+}
+
+// ─── Comandos (supervisão) ────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn verify_audit_log(store: State<'_, SettingsStore>, sessions: State<'_, Sessions>) -> AppResult<ChainReport> {
+    operator::verify_audit_log(&*store, &sessions)
+}
+
+#[tauri::command]
+pub fn get_audit_log(
+    filter:   Option<AuditFilter>,
+    store:    State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Vec<AuditEvent>> {
+    operator::get_audit_log(&*store, &sessions, &filter.unwrap_or_default())
+}
+
+// Eventos do filtro + estado da cadeia (JSON) ou só os eventos (CSV), onde o usuário escolher
+#[tauri::command]
+pub async fn export_audit_log(
+    filter:   Option<AuditFilter>,
+    format:   ExportFormat,
+    app:      AppHandle,
+    store:    State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Option<PathBuf>> {
+    let admin = store.require(&sessions, Role::Admin)?;
+    let filter = filter.unwrap_or_default();
+    let Some(picked) = app
+        .dialog()
+        .file()
+        .set_file_name(audit_log::export_file_name(format))
+        .add_filter(format.extension().to_uppercase(), &[format.extension()])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| AppError::invalid_input("path", e))?;
+    operator::export_audit_log(&*store, &admin, &filter, format, &path)?;
+    Ok(Some(path))
+}
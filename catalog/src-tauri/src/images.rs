@@ -0,0 +1,276 @@
+// Protocolo catalog:// — imagens do catálogo lidas do disco direto pelo WebView,
+// sem passar o arquivo inteiro em base64 pelo IPC:
+//
+//   catalog://image/<id>       imagem principal (individuals.img_path)
+//   catalog://image/<id>/<n>   n-ésima imagem da galeria, na ordem de get_individual (1 = primeira)
+//   catalog://thumb/<id>/<n>?size=small|medium|large   miniatura da mesma imagem (ver thumbnails.rs)
+//
+// No Windows o WebView2 expõe o esquema como http://catalog.localhost/image/...
+// O tipo vem do conteúdo (a extensão nem sempre bate), com ETag e Range. Exige
+// sessão aberta (401 com a sessão trancada); cada imagem entregue (não as
+// miniaturas) é registrada no log de auditoria.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::{layout, repo, AppError, AppResult, BackendSlot};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+use tauri::http::{header, Method, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, Runtime, UriSchemeContext, UriSchemeResponder};
+
+use crate::settings::{CatalogStore, SettingsStore};
+use crate::thumbnails::{ThumbSize, ThumbnailCache};
+
+pub const SCHEME: &str = "catalog";
+
+const CACHE_CONTROL: &str = "private, max-age=3600";
+const SNIFF_LEN:     usize = 32;
+
+// Leitura no pool de threads bloqueantes do runtime: o handler roda no loop de
+// eventos do WebView, e uma galeria dispara dezenas de requisições de uma vez
+pub fn handle<R: Runtime>(ctx: UriSchemeContext<'_, R>, request: Request<Vec<u8>>, responder: UriSchemeResponder) {
+    let app = ctx.app_handle().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let response = serve(&app, &request).unwrap_or_else(|e| {
+            println!("[CATALOG] {} {}: {}", request.method(), request.uri(), e);
+            error_response(&e)
+        });
+        responder.respond(response);
+    });
+}
+
+fn serve<R: Runtime>(app: &AppHandle<R>, request: &Request<Vec<u8>>) -> AppResult<Response<Vec<u8>>> {
+    let account = app.state::<SettingsStore>().require(&app.state::<Sessions>(), Role::Viewer)?;
+    let target = route(request)?;
+    let source = resolve(app, &target.id, target.n)?;
+    let path = match target.thumb {
+        Some(size) => {
+            let limit = app.state::<SettingsStore>().thumbnail_cache_limit();
+            app.state::<ThumbnailCache>().get(&source, size, limit)?
+        }
+        None => source,
+    };
+
+    let mut file = File::open(&path).map_err(|e| AppError::io(&path, e))?;
+    let meta = file.metadata().map_err(|e| AppError::io(&path, e))?;
+    let len = meta.len();
+    let modified = meta.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map_or(0, |d| d.as_nanos());
+    let etag = format!("\"{len:x}-{modified:x}\"");
+
+    let builder = Response::builder()
+        .header(header::ETAG, &etag)
+        .header(header::CACHE_CONTROL, CACHE_CONTROL)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok());
+    if if_none_match.is_some_and(|tags| tags.split(',').any(|t| t.trim() == etag || t.trim() == "*")) {
+        return Ok(builder.status(StatusCode::NOT_MODIFIED).body(vec![]).unwrap_or_default());
+    }
+
+    let mut head = [0u8; SNIFF_LEN];
+    let sniffed = file.read(&mut head).map_err(|e| AppError::io(&path, e))?;
+    let builder = builder.header(header::CONTENT_TYPE, sniff(&head[..sniffed]));
+
+    let range = request.headers().get(header::RANGE).and_then(|v| v.to_str().ok());
+    let (status, start, end) = match range.map(|r| byte_range(r, len)) {
+        None => (StatusCode::OK, 0, len),
+        Some(Some((start, end))) => (StatusCode::PARTIAL_CONTENT, start, end),
+        Some(None) => {
+            return Ok(builder
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{len}"))
+                .body(vec![])
+                .unwrap_or_default());
+        }
+    };
+
+    let mut builder = builder.status(status).header(header::CONTENT_LENGTH, end - start);
+    if status == StatusCode::PARTIAL_CONTENT {
+        builder = builder.header(header::CONTENT_RANGE, format!("bytes {start}-{}/{len}", end - 1));
+    }
+    if *request.method() == Method::HEAD {
+        return Ok(builder.body(vec![]).unwrap_or_default());
+    }
+    // Um evento por entrega: sem miniaturas, 304 nem continuações de Range
+    if target.thumb.is_none() && start == 0 {
+        let store = app.state::<SettingsStore>();
+        store.record(&account.username, "view_image", Some(&target.id), &serde_json::json!({ "n": target.n }))?;
+    }
+
+    let mut body = Vec::with_capacity((end - start) as usize);
+    file.seek(SeekFrom::Start(start)).map_err(|e| AppError::io(&path, e))?;
+    file.take(end - start).read_to_end(&mut body).map_err(|e| AppError::io(&path, e))?;
+    Ok(builder.body(body).unwrap_or_default())
+}
+
+struct Target {
+    id:    String,
+    n:     usize,
+    thumb: Option<ThumbSize>,
+}
+
+// `image|thumb/<id>[/<n>]`; o host é o primeiro segmento (catalog://image/...) exceto
+// no Windows, onde é `catalog.localhost`
+fn route(request: &Request<Vec<u8>>) -> AppResult<Target> {
+    let uri = request.uri();
+    let host = uri.host().filter(|h| !h.ends_with("localhost"));
+    let segments: Vec<String> = host
+        .into_iter()
+        .chain(uri.path().split('/'))
+        .filter(|s| !s.is_empty())
+        .map(percent_decode)
+        .collect();
+
+    let (kind, id, n) = match segments.as_slice() {
+        [kind, id] => (kind, id, 0),
+        [kind, id, n] => {
+            let n = n.parse().map_err(|_| AppError::invalid_input("n", format!("índice de imagem inválido: {n}")))?;
+            (kind, id, n)
+        }
+        _ => return Err(AppError::invalid_input("uri", format!("rota desconhecida: {uri}"))),
+    };
+    let thumb = match kind.as_str() {
+        "image" => None,
+        "thumb" => {
+            let size = uri.query().unwrap_or_default().split('&').find_map(|p| p.strip_prefix("size="));
+            match size {
+                None => Some(ThumbSize::default()),
+                Some(size) => Some(ThumbSize::parse(size).ok_or_else(|| AppError::invalid_input("size", size))?),
+            }
+        }
+        _ => return Err(AppError::invalid_input("uri", format!("rota desconhecida: {uri}"))),
+    };
+    Ok(Target { id: id.clone(), n, thumb })
+}
+
+fn resolve<R: Runtime>(app: &AppHandle<R>, id: &str, n: usize) -> AppResult<PathBuf> {
+    let store = app.state::<SettingsStore>();
+    let slot = app.state::<BackendSlot>();
+    let backend = crate::db(&store, &slot)?;
+    let relative = repo::image_path(backend.as_ref(), id, n)?.ok_or_else(|| AppError::NotFound(format!("{id}/{n}")))?;
+
+    // Só caminhos gravados no banco, e confinados à pasta de imagens configurada
+    layout::confine(&store.images_dir()?, &relative)
+}
+
+fn error_response(e: &AppError) -> Response<Vec<u8>> {
+    let status = match e {
+        AppError::NotFound(_) | AppError::FileNotFound(_) => StatusCode::NOT_FOUND,
+        AppError::InvalidInput { .. } => StatusCode::BAD_REQUEST,
+        AppError::NotAuthenticated => StatusCode::UNAUTHORIZED,
+        AppError::PathNotAllowed(_) | AppError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        AppError::DataRootNotConfigured | AppError::DatabaseUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(e.to_string().into_bytes())
+        .unwrap_or_default()
+}
+
+// ─── Conteúdo ─────────────────────────────────────────────────────────────────
+
+// Tipo pelos primeiros bytes do arquivo (assinaturas dos formatos de imagem)
+fn sniff(head: &[u8]) -> &'static str {
+    match head {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        [b'B', b'M', ..] => "image/bmp",
+        [0, 0, 1, 0, ..] => "image/x-icon",
+        [b'I', b'I', 42, 0, ..] | [b'M', b'M', 0, 42, ..] => "image/tiff",
+        [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f', ..] => "image/avif",
+        [_, _, _, _, b'f', b't', b'y', b'p', b'h', b'e', b'i', b'c', ..] => "image/heic",
+        _ => "application/octet-stream",
+    }
+}
+
+// Um único intervalo `bytes=a-b`, `bytes=a-` ou `bytes=-n` → [start, end);
+// None quando não dá para atender (416)
+fn byte_range(value: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let n: u64 = suffix.parse().ok()?;
+            (len.saturating_sub(n), len)
+        }
+        (start, "") => (start.parse().ok()?, len),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.saturating_add(1).min(len)),
+    };
+    (start < end && start < len).then_some((start, end))
+}
+
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_range_forms() {
+        assert_eq!(byte_range("bytes=0-99", 1000), Some((0, 100)));
+        assert_eq!(byte_range(" bytes=500- ", 1000), Some((500, 1000)));
+        assert_eq!(byte_range("bytes=-100", 1000), Some((900, 1000)));
+        // Fim além do arquivo é cortado; sufixo maior que o arquivo pega tudo
+        assert_eq!(byte_range("bytes=900-5000", 1000), Some((900, 1000)));
+        assert_eq!(byte_range("bytes=-5000", 1000), Some((0, 1000)));
+    }
+
+    #[test]
+    fn byte_range_unsatisfiable() {
+        for value in ["bytes=1000-", "bytes=1000-1100", "bytes=50-10", "bytes=-0", "bytes=0-1,5-9", "bytes=a-b", "items=0-1", "bytes=5"] {
+            assert_eq!(byte_range(value, 1000), None, "{value}");
+        }
+        assert_eq!(byte_range("bytes=0-", 0), None);
+    }
+
+    #[test]
+    fn sniff_by_signature() {
+        assert_eq!(sniff(&[0xFF, 0xD8, 0xFF, 0xE0]), "image/jpeg");
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n"), "image/png");
+        assert_eq!(sniff(b"GIF89a"), "image/gif");
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(sniff(b"\0\0\0\x1cftypavif"), "image/avif");
+        assert_eq!(sniff(b"II*\0"), "image/tiff");
+        // Extensão não importa: texto e arquivo curto demais não viram imagem
+        assert_eq!(sniff(b"<svg xmlns="), "application/octet-stream");
+        assert_eq!(sniff(b"RIFF"), "application/octet-stream");
+        assert_eq!(sniff(&[]), "application/octet-stream");
+    }
+
+    #[test]
+    fn percent_decode_segments() {
+        assert_eq!(percent_decode("fbi%2Djohn%20doe"), "fbi-john doe");
+        assert_eq!(percent_decode("jos%C3%A9"), "josé");
+        // Escape incompleto ou inválido fica como veio
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("a%zzb%4"), "a%zzb%4");
+    }
+}
@@ -1,105 +1,62 @@
 // Intelligence Catalog — Backend Tauri (Rust)
 // Lê intelligence.db via rusqlite e expõe comandos ao frontend.
 
-use rusqlite::{Connection, Result as SqlResult, params};
-use serde::{Deserialize, Serialize};
+mod audit_log;
+mod auth;
+mod images;
+mod settings;
+mod thumbnails;
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::audit::{self, AuditReport, ExportFormat};
+use intelligence_db::linkage::{self, Comparison, Decision, DuplicateCluster, DuplicateFilter, LinkDecision};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::search_index::{self, IndexStatus};
+use intelligence_db::{
+    repo, search, AppError, AppResult, Backend, BackendSlot, FacetCount, IndividualDetail, IngestionStats,
+    IngestionStatsFilter, LocationLevel, LocationValuesFilter, SchemaInfo, SearchFilter, SearchPage, SortKey, Stats,
+};
+use settings::{CatalogStore, SettingsStore};
+use thumbnails::ThumbnailCache;
 use std::path::PathBuf;
-use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
-
-fn db_path() -> PathBuf {
-    let candidates = [
-        "/home/douglasdsr/Documentos/Projects/FBI/Dashboard/intelligence/data/intelligence.db",
-        "../../intelligence/data/intelligence.db",
-        "../intelligence/data/intelligence.db",
-        "data/intelligence.db",
-    ];
-    for c in &candidates {
-        let p = PathBuf::from(c);
-        if p.exists() { 
-            println!("[CATALOG] Usando banco de dados em: {:?}", p);
-            return p; 
-        }
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+
+// Backend (SQLite somente-leitura ou Postgres) aberto na primeira chamada e
+// descartado quando as configurações mudam
+fn db(store: &SettingsStore, slot: &BackendSlot) -> AppResult<Arc<dyn Backend>> {
+    if let Some(backend) = slot.get() {
+        return Ok(backend);
     }
-    let fallback = PathBuf::from("/home/douglasdsr/Documentos/Projects/FBI/Dashboard/intelligence/data/intelligence.db");
-    println!("[CATALOG] Banco não encontrado nos candidatos, tentando fallback: {:?}", fallback);
-    fallback
-}
-
-fn open_db() -> SqlResult<Connection> {
-    Connection::open(db_path())
-}
-
-#[derive(Serialize, Deserialize)]
-struct Individual {
-    id:            String,
-    name:          String,
-    category:      String,
-    source:        String,
-    birth_date:    Option<String>,
-    nationalities: Option<String>,
-    description:   Option<String>,
-    reward:        Option<String>,
-    img_path:      Option<String>,
-    has_embedding: i32,
-    ingested_at:   Option<String>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct IndividualImage {
-    img_url:     Option<String>,
-    img_path:    Option<String>,
-    caption:     Option<String>,
-    is_primary:  i32,
-}
-
-#[derive(Serialize, Deserialize)]
-struct IndividualDetail {
-    id:            String,
-    name:          String,
-    category:      String,
-    source:        String,
-    birth_date:    Option<String>,
-    nationalities: Option<String>,
-    description:   Option<String>,
-    reward:        Option<String>,
-    img_path:      Option<String>,
-    has_embedding: i32,
-    aliases:       Option<String>,
-    sex:           Option<String>,
-    url:           Option<String>,
-    // Novos campos
-    height_cm:     Option<f64>,
-    weight_kg:     Option<f64>,
-    eye_color:     Option<String>,
-    hair_color:    Option<String>,
-    occupation:    Option<String>,
-    images:        Vec<IndividualImage>,
-    crimes:        Vec<String>,
-    locations:     Vec<Location>,
-    ingested_at:   Option<String>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct Location {
-    loc_type: String,
-    country:  Option<String>,
-    state:    Option<String>,
-    city:     Option<String>,
-    details:  Option<String>,
+    let cfg = store.backend_config()?;
+    let index_file = store.app_data_file(search_index::INDEX_FILE);
+    let linkage_file = store.app_data_file(linkage::LINKAGE_FILE);
+    slot.get_or_open_with(&cfg, |backend| {
+        let schema = backend.schema();
+        println!("[CATALOG] Usando banco de dados: {}", backend.describe());
+        println!("[CATALOG] Schema v{}/{} (ausente: {:?})", schema.version, schema.latest, schema.missing);
+        match search_index::refresh(backend, &index_file, false) {
+            Ok(status) => println!("[CATALOG] Índice de busca: {} documentos ({:?})", status.documents, status.path),
+            Err(e) => println!("[CATALOG] Índice de busca indisponível, usando LIKE: {}", e),
+        }
+        if let Err(e) = linkage::attach(backend, &linkage_file) {
+            println!("[CATALOG] Ligações entre registros indisponíveis: {}", e);
+        }
+    })
+    .map_err(AppError::from_open)
 }
 
-#[derive(Serialize, Deserialize)]
-struct Stats {
-    total:           i64,
-    wanted:          i64,
-    missing:         i64,
-    with_biometrics: i64,
-    by_source:       Vec<SourceCount>,
+#[tauri::command]
+fn get_schema_info(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<SchemaInfo> {
+    store.require(&sessions, Role::Viewer)?;
+    Ok(db(&store, &slot)?.schema().clone())
 }
 
-#[derive(Serialize, Deserialize)]
-struct SourceCount { source: String, count: i64 }
-
 struct TranslateState {
     client: reqwest::Client,
 }
@@ -109,220 +66,238 @@ struct TranslateState {
 #[tauri::command]
 fn search_individuals(
     name:          Option<String>,
+    query:         Option<String>,
     category:      Option<String>,
     country:       Option<String>,
     crime:         Option<String>,
     has_embedding: Option<bool>,
     source_filter: Option<String>,
+    location_type:    Option<String>,
+    location_country: Option<String>,
+    location_state:   Option<String>,
+    location_city:    Option<String>,
+    has_images:    Option<bool>,
+    age_min:       Option<u32>,
+    age_max:       Option<u32>,
+    height_min:    Option<f64>,
+    height_max:    Option<f64>,
+    weight_min:    Option<f64>,
+    weight_max:    Option<f64>,
+    eye_color:     Option<String>,
+    hair_color:    Option<String>,
+    fuzzy:         Option<bool>,
+    sort:          Option<SortKey>,
+    descending:    Option<bool>,
+    cursor:        Option<String>,
     page:          Option<u32>,
     limit:         Option<u32>,
-) -> Result<Vec<Individual>, String> {
-    let conn  = open_db().map_err(|e| e.to_string())?;
-    let lim   = limit.unwrap_or(40) as i64;
-    let off   = (page.unwrap_or(0) as i64) * lim;
-
-    let mut conds = vec!["1=1".to_string()];
-    let mut vals: Vec<String> = vec![];
-
-    if let Some(n) = name.as_deref() {
-        if !n.is_empty() {
-            conds.push("(i.name LIKE ? OR i.description LIKE ?)".into());
-            vals.push(format!("%{n}%"));
-            vals.push(format!("%{n}%"));
-        }
-    }
-    if let Some(c) = category.as_deref() {
-        if !c.is_empty() { conds.push("i.category = ?".into()); vals.push(c.into()); }
-    }
-    if let Some(co) = country.as_deref() {
-        if !co.is_empty() { conds.push("i.nationalities LIKE ?".into()); vals.push(format!("%{co}%")); }
-    }
-    if let Some(src) = source_filter.as_deref() {
-        if !src.is_empty() { conds.push("i.source LIKE ?".into()); vals.push(format!("%{src}%")); }
-    }
-    if let Some(has_bio) = has_embedding {
-        conds.push("i.has_embedding = ?".into());
-        vals.push(if has_bio { "1".into() } else { "0".into() });
-    }
-
-    let crime_join = if let Some(cr) = crime.as_deref() {
-        if !cr.is_empty() {
-            conds.push("c.crime LIKE ?".into());
-            vals.push(format!("%{cr}%"));
-            "LEFT JOIN crimes c ON c.individual_id = i.id"
-        } else { "" }
-    } else { "" };
-
-    let sql = format!(
-        "SELECT DISTINCT i.id, i.name, i.category, i.source, i.birth_date, i.nationalities,
-                i.description, i.reward, i.img_path, i.has_embedding, i.ingested_at
-         FROM individuals i {crime_join}
-         WHERE {where_clause}
-         ORDER BY i.has_embedding DESC, i.ingested_at DESC, i.name ASC LIMIT ? OFFSET ?",
-        crime_join = crime_join,
-        where_clause = conds.join(" AND ")
-    );
-
-    println!("[TAURI-DEBUG] SQL: {}", sql);
-    println!("[TAURI-DEBUG] Params: {:?}", vals);
-
-    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
-    
-    let mut query_params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-    for v in &vals {
-        query_params.push(v);
-    }
-    query_params.push(&lim);
-    query_params.push(&off);
-
-    let rows = stmt.query_map(rusqlite::params_from_iter(query_params), |row| {
-        Ok(Individual {
-            id:            row.get(0)?,
-            name:          row.get(1)?,
-            category:      row.get(2)?,
-            source:        row.get(3)?,
-            birth_date:    row.get(4)?,
-            nationalities: row.get(5)?,
-            description:   row.get(6)?,
-            reward:        row.get(7)?,
-            img_path:      row.get(8)?,
-            has_embedding: row.get(9)?,
-            ingested_at:   row.get(10)?,
-        })
-    }).map_err(|e| e.to_string())?;
-
-    let mut results = Vec::new();
-    for row in rows {
-        results.push(row.map_err(|e| e.to_string())?);
-    }
-    println!("[TAURI-DEBUG] Encontrados: {} indivíduos", results.len());
+    facets:        Option<bool>,
+    group_linked:  Option<bool>,
+    store:         State<'_, SettingsStore>,
+    slot:          State<'_, BackendSlot>,
+    sessions:      State<'_, Sessions>,
+) -> AppResult<SearchPage> {
+    let account = store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let filter = SearchFilter {
+        name, query, category, country, crime, has_embedding,
+        source: source_filter,
+        location_type, location_country, location_state, location_city,
+        has_images,
+        age_min, age_max, height_min, height_max, weight_min, weight_max,
+        eye_color, hair_color,
+        fuzzy,
+        sort, descending, cursor,
+        page, limit, facets, group_linked,
+    };
+    let results = search::search_individuals(backend.as_ref(), &filter)?;
+    let ids: Vec<&str> = results.items.iter().map(|i| i.id.as_str()).collect();
+    let filter = audit_log::compact(serde_json::to_value(&filter).unwrap_or_default());
+    let detail = serde_json::json!({ "filter": filter, "total": results.total, "ids": ids });
+    store.record(&account.username, "search_individuals", None, &detail)?;
     Ok(results)
 }
 
+// Valores distintos de `locations` (tipo, país, estado, cidade) para o autocomplete
 #[tauri::command]
-fn get_individual(id: String) -> Result<IndividualDetail, String> {
-    let conn = open_db().map_err(|e| e.to_string())?;
-    
-    let mut stmt = conn.prepare(
-        "SELECT id,name,category,source,birth_date,nationalities,description,
-                reward,img_path,has_embedding,aliases,sex,url,ingested_at,
-                height_cm, weight_kg, eye_color, hair_color, occupation
-         FROM individuals WHERE id=?"
-    ).map_err(|e| e.to_string())?;
-    
-    let row = stmt.query_row(params![id], |r| Ok(IndividualDetail {
-            id:            r.get(0)?,
-            name:          r.get(1)?,
-            category:      r.get(2)?,
-            source:        r.get(3)?,
-            birth_date:    r.get(4)?,
-            nationalities: r.get(5)?,
-            description:   r.get(6)?,
-            reward:        r.get(7)?,
-            img_path:      r.get(8)?,
-            has_embedding: r.get(9)?,
-            aliases:       r.get(10)?,
-            sex:           r.get(11)?,
-            url:           r.get(12)?,
-            ingested_at:   r.get(13)?,
-            height_cm:     r.get(14)?,
-            weight_kg:     r.get(15)?,
-            eye_color:     r.get(16)?,
-            hair_color:    r.get(17)?,
-            occupation:    r.get(18)?,
-            images:        vec![], // Preenchido depois
-            crimes:        vec![],
-            locations:     vec![],
-        })).map_err(|e| e.to_string())?;
+fn list_location_values(
+    level:    Option<LocationLevel>,
+    prefix:   Option<String>,
+    loc_type: Option<String>,
+    country:  Option<String>,
+    state:    Option<String>,
+    limit:    Option<u32>,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Vec<FacetCount>> {
+    store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let filter = LocationValuesFilter { level: level.unwrap_or_default(), prefix, loc_type, country, state, limit };
+    Ok(repo::location_values(backend.as_ref(), &filter)?)
+}
 
-    // Crimes
-    let mut stmt_crimes = conn.prepare("SELECT crime FROM crimes WHERE individual_id=?")
-        .map_err(|e| e.to_string())?;
-    let crimes: Vec<String> = stmt_crimes.query_map(params![id], |r| r.get(0))
-        .map_err(|e| e.to_string())?
-        .filter_map(|r| r.ok()).collect();
+#[tauri::command]
+fn get_individual(
+    id:       String,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<IndividualDetail> {
+    let account = store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let individual = repo::get_individual(backend.as_ref(), &id)?;
+    store.record(&account.username, "get_individual", Some(&id), &serde_json::json!({}))?;
+    Ok(individual)
+}
 
-    // Locations
-    let mut stmt_locs = conn.prepare("SELECT type, country, state, city, details FROM locations WHERE individual_id=?")
-        .map_err(|e| e.to_string())?;
-    let locations: Vec<Location> = stmt_locs.query_map(params![id], |r| {
-        Ok(Location {
-            loc_type: r.get(0)?,
-            country:  r.get(1)?,
-            state:    r.get(2)?,
-            city:     r.get(3)?,
-            details:  r.get(4)?,
-        })
-    }).map_err(|e| e.to_string())?.filter_map(|r| r.ok()).collect();
+// ─── Duplicatas entre fontes ──────────────────────────────────────────────────
 
-    // Images (Galeria)
-    let mut stmt_imgs = conn.prepare("SELECT img_url, img_path, caption, is_primary FROM individual_images WHERE individual_id=?")
-        .map_err(|e| e.to_string())?;
-    let images: Vec<IndividualImage> = stmt_imgs.query_map(params![id], |r| {
-        Ok(IndividualImage {
-            img_url:  r.get(0)?,
-            img_path: r.get(1)?,
-            caption:  r.get(2)?,
-            is_primary: r.get(3)?,
-        })
-    }).map_err(|e| e.to_string())?.filter_map(|r| r.ok()).collect();
+#[tauri::command]
+fn find_duplicates(
+    min_score:       Option<f64>,
+    cross_source:    Option<bool>,
+    include_decided: Option<bool>,
+    limit:           Option<u32>,
+    store:           State<'_, SettingsStore>,
+    slot:            State<'_, BackendSlot>,
+    sessions:        State<'_, Sessions>,
+) -> AppResult<Vec<DuplicateCluster>> {
+    store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let filter = DuplicateFilter { min_score, cross_source, include_decided, limit };
+    let linkage_file = store.app_data_file(linkage::LINKAGE_FILE);
+    Ok(linkage::find_duplicates(backend.as_ref(), &linkage_file, &filter)?)
+}
 
-    Ok(IndividualDetail { crimes, locations, images, ..row })
+#[tauri::command]
+fn compare_records(
+    id_a:     String,
+    id_b:     String,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Comparison> {
+    let account = store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let linkage_file = store.app_data_file(linkage::LINKAGE_FILE);
+    let comparison = linkage::compare(backend.as_ref(), &linkage_file, &id_a, &id_b)?;
+    store.record(&account.username, "compare_records", None, &serde_json::json!({ "ids": [id_a, id_b] }))?;
+    Ok(comparison)
+}
+
+// `decision: null` desfaz a decisão do par. Só revisores.
+#[tauri::command]
+fn set_link_decision(
+    id_a:     String,
+    id_b:     String,
+    decision: Option<Decision>,
+    note:     Option<String>,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Option<LinkDecision>> {
+    store.require(&sessions, Role::Reviewer)?;
+    let backend = db(&store, &slot)?;
+    let linkage_file = store.app_data_file(linkage::LINKAGE_FILE);
+    Ok(linkage::decide(backend.as_ref(), &linkage_file, &id_a, &id_b, decision, note.as_deref())?)
 }
 
 #[tauri::command]
-fn get_stats() -> Result<Stats, String> {
-    let conn = open_db().map_err(|e| e.to_string())?;
-    
-    let total: i64 = conn.query_row("SELECT COUNT(*) FROM individuals", params![], |r| r.get(0))
-        .map_err(|e| format!("Erro total: {}", e))?;
-        
-    let wanted: i64 = conn.query_row("SELECT COUNT(*) FROM individuals WHERE category='wanted'", params![], |r| r.get(0))
-        .map_err(|e| format!("Erro wanted: {}", e))?;
-        
-    let missing: i64 = conn.query_row("SELECT COUNT(*) FROM individuals WHERE category='missing'", params![], |r| r.get(0))
-        .map_err(|e| format!("Erro missing: {}", e))?;
-        
-    let with_biometrics: i64 = conn.query_row("SELECT COUNT(*) FROM individuals WHERE has_embedding=1", params![], |r| r.get(0))
-        .map_err(|e| format!("Erro bio: {}", e))?;
+fn get_stats(store: State<'_, SettingsStore>, slot: State<'_, BackendSlot>, sessions: State<'_, Sessions>) -> AppResult<Stats> {
+    store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    let stats = repo::get_stats(backend.as_ref())?;
+    Ok(stats)
+}
 
-    println!("[TAURI-DEBUG] Stats - Total: {}, Bio: {}", total, with_biometrics);
+// Linha do tempo de ingestão, completude por campo e registros parados por fonte
+#[tauri::command]
+fn get_ingestion_stats(
+    days:       Option<u32>,
+    stale_days: Option<u32>,
+    top:        Option<u32>,
+    store:      State<'_, SettingsStore>,
+    slot:       State<'_, BackendSlot>,
+    sessions:   State<'_, Sessions>,
+) -> AppResult<IngestionStats> {
+    store.require(&sessions, Role::Viewer)?;
+    let backend = db(&store, &slot)?;
+    Ok(repo::ingestion_stats(backend.as_ref(), &IngestionStatsFilter { days, stale_days, top })?)
+}
 
-    let mut stmt = conn.prepare("SELECT source,COUNT(*) FROM individuals GROUP BY source ORDER BY COUNT(*) DESC LIMIT 10")
-        .map_err(|e| e.to_string())?;
-    let rows = stmt.query_map([], |r| Ok(SourceCount { source: r.get(0)?, count: r.get(1)? }))
-        .map_err(|e| e.to_string())?;
-    
-    let mut by_source = Vec::new();
-    for r in rows {
-        by_source.push(r.map_err(|e| e.to_string())?);
-    }
+// Força a reconstrução do índice FTS (ex.: depois de uma ingestão com o app aberto)
+#[tauri::command]
+fn rebuild_search_index(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<IndexStatus> {
+    store.require(&sessions, Role::Admin)?;
+    let backend = db(&store, &slot)?;
+    let index_file = store.app_data_file(search_index::INDEX_FILE);
+    Ok(search_index::refresh(backend.as_ref(), &index_file, true)?)
+}
 
-    Ok(Stats { total, wanted, missing, with_biometrics, by_source })
+// Auditoria de qualidade: nomes vazios, imagens sem arquivo e linhas órfãs (revisores)
+fn quality_report(store: &SettingsStore, slot: &BackendSlot) -> AppResult<AuditReport> {
+    let backend = db(store, slot)?;
+    let images_dir = store.images_dir()?;
+    let report = audit::run(backend.as_ref(), Some(&images_dir))?;
+    println!("[CATALOG] Auditoria: {} problemas em {} indivíduos", report.total, report.individuals);
+    Ok(report)
 }
 
 #[tauri::command]
-fn get_image_base64(img_path: String) -> Result<String, String> {
-    // Resolve caminho relativo para absoluto baseado na localização do banco
-    let base_dir = PathBuf::from("/home/douglasdsr/Documentos/Projects/FBI/Dashboard/intelligence/");
-    let mut abs_path = base_dir;
-    abs_path.push(img_path);
-    
-    if !abs_path.exists() {
-        println!("[CATALOG] Imagem não encontrada: {:?}", abs_path);
-        return Err(format!("Imagem inexistente: {:?}", abs_path));
-    }
+fn run_audit(
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<AuditReport> {
+    store.require(&sessions, Role::Reviewer)?;
+    quality_report(&store, &slot)
+}
 
-    let bytes = std::fs::read(&abs_path).map_err(|e| e.to_string())?;
-    Ok(format!("data:image/jpeg;base64,{}", B64.encode(&bytes)))
+// Roda a auditoria e salva o relatório (JSON ou CSV) onde o usuário escolher
+#[tauri::command]
+async fn export_audit(
+    format:   ExportFormat,
+    app:      AppHandle,
+    store:    State<'_, SettingsStore>,
+    slot:     State<'_, BackendSlot>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<Option<PathBuf>> {
+    let account = store.require(&sessions, Role::Reviewer)?;
+    let report = quality_report(&store, &slot)?;
+    let file_name = format!("audit-{}.{}", report.generated_at.replace(':', ""), format.extension());
+    let Some(picked) = app
+        .dialog()
+        .file()
+        .set_file_name(&file_name)
+        .add_filter(format.extension().to_uppercase(), &[format.extension()])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| AppError::invalid_input("path", e))?;
+    let detail = serde_json::json!({ "format": format.extension(), "path": path, "issues": report.total });
+    store.record(&account.username, "export_audit", None, &detail)?;
+    std::fs::write(&path, audit::export(&report, format)).map_err(|e| AppError::io(&path, e))?;
+    Ok(Some(path))
 }
+
+const TRANSLATE_SERVICE: &str = "libretranslate";
+
 #[tauri::command]
 async fn translate_text(
     q: String,
     source: String,
     target: String,
     state: tauri::State<'_, TranslateState>,
-) -> Result<String, String> {
+    store: State<'_, SettingsStore>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<String> {
+    store.require(&sessions, Role::Viewer)?;
     let res = state.client
         .post("http://localhost:5000/translate")
         .json(&serde_json::json!({
@@ -334,16 +309,16 @@ async fn translate_text(
         }))
         .send()
         .await
-        .map_err(|e| format!("Erro na requisição: {}", e))?;
+        .map_err(|e| AppError::service_unavailable(TRANSLATE_SERVICE, e))?;
 
     if !res.status().is_success() {
-        return Err(format!("LibreTranslate retornou erro: {}", res.status()));
+        return Err(AppError::service_failed(TRANSLATE_SERVICE, res.status()));
     }
 
-    let json: serde_json::Value = res.json().await.map_err(|e| format!("Erro ao ler JSON: {}", e))?;
+    let json: serde_json::Value = res.json().await.map_err(|e| AppError::service_failed(TRANSLATE_SERVICE, e))?;
     let translated = json["translatedText"]
         .as_str()
-        .ok_or_else(|| "translatedText não encontrado no JSON".to_string())?;
+        .ok_or_else(|| AppError::service_failed(TRANSLATE_SERVICE, "translatedText não encontrado no JSON"))?;
 
     Ok(translated.to_string())
 }
@@ -353,13 +328,49 @@ async fn translate_text(
 pub fn run() {
     tauri::Builder::default()
         .manage(TranslateState { client: reqwest::Client::new() })
+        .manage(BackendSlot::default())
+        .manage(Sessions::default())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .register_asynchronous_uri_scheme_protocol(images::SCHEME, images::handle)
+        .setup(|app| {
+            app.manage(ThumbnailCache::new(settings::app_cache_path(app.handle(), thumbnails::CACHE_DIR)?));
+            app.manage(settings::load(app.handle())?);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
+            auth::get_session,
+            auth::setup_admin,
+            auth::login,
+            auth::logout,
+            auth::lock_session,
+            auth::touch_session,
+            auth::change_password,
+            auth::list_accounts,
+            auth::create_account,
+            auth::update_account,
+            settings::get_settings,
+            settings::update_settings,
+            settings::validate_data_root,
+            settings::pick_data_root,
+            get_schema_info,
             search_individuals,
+            list_location_values,
             get_individual,
             get_stats,
-            get_image_base64,
+            get_ingestion_stats,
+            find_duplicates,
+            compare_records,
+            set_link_decision,
+            rebuild_search_index,
+            run_audit,
+            export_audit,
             translate_text,
+            thumbnails::get_thumbnail_cache,
+            thumbnails::clear_thumbnail_cache,
+            audit_log::verify_audit_log,
+            audit_log::get_audit_log,
+            audit_log::export_audit_log,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
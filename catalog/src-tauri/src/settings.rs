@@ -0,0 +1,90 @@
+// Configuração persistente do catálogo. O comum aos dois apps (settings.json, pasta
+// de dados, banco, bloqueio e os comandos de configuração) está em
+// intelligence_db::settings; aqui só a pasta de imagens e o cache de miniaturas.
+
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::settings::{self, env_path, AppSettings};
+use intelligence_db::{AppError, AppResult, BackendSlot, DatabaseSettings};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::thumbnails::DEFAULT_CACHE_MB;
+
+const ENV_IMAGES_DIR: &str = "OSS_IMAGES_DIR";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CatalogSettings {
+    pub images_dir:         Option<PathBuf>,
+    pub thumbnail_cache_mb: Option<u64>,
+}
+
+impl AppSettings for CatalogSettings {
+    const ENV_VARS: &'static [&'static str] = &[ENV_IMAGES_DIR];
+}
+
+pub type SettingsStore = settings::SettingsStore<CatalogSettings>;
+pub type SettingsView = settings::SettingsView<CatalogSettings>;
+
+pub fn load(app: &AppHandle) -> AppResult<SettingsStore> {
+    let config_dir = app.path().app_config_dir().map_err(|e| AppError::Settings(e.to_string()))?;
+    // Sidecars do app (índice de busca etc.): nunca dentro da pasta de dados
+    let data_dir = app.path().app_data_dir().map_err(|e| AppError::Settings(e.to_string()))?;
+    Ok(SettingsStore::load(&config_dir, data_dir))
+}
+
+// Dados descartáveis (miniaturas): podem ser apagados a qualquer momento
+pub fn app_cache_path(app: &AppHandle, name: &str) -> AppResult<PathBuf> {
+    let cache_dir = app.path().app_cache_dir().map_err(|e| AppError::Settings(e.to_string()))?;
+    Ok(cache_dir.join(name))
+}
+
+// Caminhos só do catálogo, sobre o SettingsStore comum
+pub trait CatalogStore {
+    fn images_dir(&self) -> AppResult<PathBuf>;
+    fn thumbnail_cache_limit(&self) -> u64;
+}
+
+impl CatalogStore for SettingsStore {
+    // img_path no banco é relativo à pasta `intelligence/` (onde a ingestão roda)
+    fn images_dir(&self) -> AppResult<PathBuf> {
+        let db_file = self.require_paths()?.db_file;
+        let root = self.data_root();
+        Ok(env_path(ENV_IMAGES_DIR)
+            .or(self.get().app.images_dir)
+            .or_else(|| root.as_ref().map(|r| r.join("intelligence")).filter(|p| p.is_dir()))
+            .or(root)
+            .unwrap_or_else(|| db_file.parent().map(Path::to_path_buf).unwrap_or_default()))
+    }
+
+    fn thumbnail_cache_limit(&self) -> u64 {
+        self.get().app.thumbnail_cache_mb.unwrap_or(DEFAULT_CACHE_MB) * 1024 * 1024
+    }
+}
+
+// ─── Comandos ─────────────────────────────────────────────────────────────────
+
+intelligence_db::settings_commands!(CatalogSettings);
+
+#[tauri::command]
+pub fn update_settings(
+    data_root:          Option<String>,
+    images_dir:         Option<String>,
+    database:           Option<DatabaseSettings>,
+    thumbnail_cache_mb: Option<u64>,
+    idle_lock_minutes:  Option<u32>,
+    store:              State<'_, SettingsStore>,
+    slot:               State<'_, BackendSlot>,
+    sessions:           State<'_, Sessions>,
+) -> AppResult<SettingsView> {
+    store.require(&sessions, Role::Admin)?;
+    if let Some(mb) = thumbnail_cache_mb {
+        store.update(|s| s.app.thumbnail_cache_mb = (mb > 0).then_some(mb))?;
+    }
+    if let Some(dir) = images_dir {
+        store.update(|s| s.app.images_dir = (!dir.is_empty()).then(|| PathBuf::from(dir)))?;
+    }
+    store.update_common(&slot, data_root, database, idle_lock_minutes)
+}
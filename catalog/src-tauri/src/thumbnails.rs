@@ -0,0 +1,234 @@
+// Miniaturas do grid: geradas sob demanda a partir da imagem original (JPEG, PNG,
+// WebP) e guardadas no diretório de cache do app como `<sha256 do arquivo>-<px>.<ext>`.
+// Se o arquivo de origem muda, o hash muda e a miniatura antiga deixa de ser usada
+// até sair pelo LRU: o mtime de cada miniatura é renovado a cada acesso e, passando
+// do limite (settings.json `thumbnail_cache_mb`), as menos usadas são apagadas.
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{ImageFormat, ImageReader};
+use intelligence_db::accounts::{Role, Sessions};
+use intelligence_db::operator::OperatorStore;
+use intelligence_db::{AppError, AppResult};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::settings::{CatalogStore, SettingsStore};
+
+pub const CACHE_DIR:        &str = "thumbnails";
+pub const DEFAULT_CACHE_MB: u64 = 256;
+
+const JPEG_QUALITY: u8 = 82;
+// Depois de passar do limite, apaga até sobrar esta fração (evita evicção a cada miniatura)
+const EVICT_TO: f64 = 0.9;
+// Originais com hash memorizado; cheio, o memo recomeça (só evita reler o arquivo)
+const HASH_MEMO_LIMIT: usize = 4096;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThumbSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ThumbSize {
+    pub fn px(self) -> u32 {
+        match self {
+            ThumbSize::Small  => 160,
+            ThumbSize::Medium => 320,
+            ThumbSize::Large  => 640,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "small"  => Some(ThumbSize::Small),
+            "medium" => Some(ThumbSize::Medium),
+            "large"  => Some(ThumbSize::Large),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CacheStats {
+    pub dir:         PathBuf,
+    pub entries:     usize,
+    pub bytes:       u64,
+    pub limit_bytes: u64,
+}
+
+// Arquivo de origem (tamanho, mtime) → sha256, para não reler o original a cada miniatura
+type Fingerprint = (u64, u128);
+
+pub struct ThumbnailCache {
+    dir:    PathBuf,
+    hashes: Mutex<HashMap<PathBuf, (Fingerprint, String)>>,
+    // Geração + evicção serializadas: duas requisições da mesma miniatura não gravam juntas
+    write:  Mutex<()>,
+}
+
+impl ThumbnailCache {
+    pub fn new(dir: PathBuf) -> Self {
+        ThumbnailCache { dir, hashes: Mutex::default(), write: Mutex::default() }
+    }
+
+    // Caminho da miniatura de `source` (já confinado à pasta de imagens), gerando se preciso
+    pub fn get(&self, source: &Path, size: ThumbSize, limit_bytes: u64) -> AppResult<PathBuf> {
+        let hash = self.source_hash(source)?;
+        let stem = format!("{hash}-{}", size.px());
+        if let Some(hit) = self.lookup(&stem) {
+            return Ok(hit);
+        }
+
+        let _guard = self.write.lock().unwrap();
+        if let Some(hit) = self.lookup(&stem) {
+            return Ok(hit);
+        }
+        let decode_err = |e: image::ImageError| AppError::Io { path: source.to_path_buf(), detail: e.to_string() };
+        let img = ImageReader::open(source)
+            .map_err(|e| AppError::io(source, e))?
+            .with_guessed_format()
+            .map_err(|e| AppError::io(source, e))?
+            .decode()
+            .map_err(decode_err)?;
+
+        // Nunca amplia: imagens menores que a caixa ficam no tamanho original
+        let px = size.px();
+        let thumb = if img.width() > px || img.height() > px { img.thumbnail(px, px) } else { img };
+
+        // Com transparência fica PNG; o resto vira JPEG
+        let (ext, bytes) = if thumb.color().has_alpha() {
+            let mut out = Cursor::new(vec![]);
+            thumb.write_to(&mut out, ImageFormat::Png).map_err(decode_err)?;
+            ("png", out.into_inner())
+        } else {
+            let mut out = vec![];
+            let rgb = thumb.to_rgb8();
+            JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY).encode_image(&rgb).map_err(decode_err)?;
+            ("jpg", out)
+        };
+
+        fs::create_dir_all(&self.dir).map_err(|e| AppError::io(&self.dir, e))?;
+        let path = self.dir.join(format!("{stem}.{ext}"));
+        let tmp = path.with_extension(format!("{ext}.tmp"));
+        fs::write(&tmp, &bytes).map_err(|e| AppError::io(&tmp, e))?;
+        fs::rename(&tmp, &path).map_err(|e| AppError::io(&path, e))?;
+
+        self.evict(limit_bytes);
+        Ok(path)
+    }
+
+    fn lookup(&self, stem: &str) -> Option<PathBuf> {
+        let path = ["jpg", "png"].iter().map(|ext| self.dir.join(format!("{stem}.{ext}"))).find(|p| p.is_file())?;
+        // LRU pelo mtime: marca o acesso
+        if let Ok(file) = File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(path)
+    }
+
+    fn source_hash(&self, source: &Path) -> AppResult<String> {
+        let meta = fs::metadata(source).map_err(|e| AppError::io(source, e))?;
+        let modified = meta.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map_or(0, |d| d.as_nanos());
+        let fingerprint = (meta.len(), modified);
+        if let Some((seen, hash)) = self.hashes.lock().unwrap().get(source) {
+            if *seen == fingerprint {
+                return Ok(hash.clone());
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        let mut file = File::open(source).map_err(|e| AppError::io(source, e))?;
+        std::io::copy(&mut file, &mut hasher).map_err(|e| AppError::io(source, e))?;
+        let hash = format!("{:x}", hasher.finalize());
+        let mut hashes = self.hashes.lock().unwrap();
+        if hashes.len() >= HASH_MEMO_LIMIT {
+            hashes.clear();
+        }
+        hashes.insert(source.to_path_buf(), (fingerprint, hash.clone()));
+        Ok(hash)
+    }
+
+    // (caminho, bytes, último acesso) das miniaturas no cache
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return vec![];
+        };
+        dir.flatten()
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+                Some((entry.path(), meta.len(), meta.modified().unwrap_or(UNIX_EPOCH)))
+            })
+            .collect()
+    }
+
+    fn evict(&self, limit_bytes: u64) {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        if total <= limit_bytes {
+            return;
+        }
+        let target = (limit_bytes as f64 * EVICT_TO) as u64;
+        entries.sort_by_key(|(_, _, used)| *used);
+        let mut removed = 0;
+        for (path, len, _) in entries {
+            if total <= target {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= len;
+                removed += 1;
+            }
+        }
+        println!("[CATALOG] Cache de miniaturas: {} removidas, {} bytes", removed, total);
+    }
+
+    pub fn stats(&self, limit_bytes: u64) -> CacheStats {
+        let entries = self.entries();
+        CacheStats {
+            dir:         self.dir.clone(),
+            entries:     entries.len(),
+            bytes:       entries.iter().map(|(_, len, _)| len).sum(),
+            limit_bytes,
+        }
+    }
+
+    pub fn clear(&self) -> AppResult<()> {
+        let _guard = self.write.lock().unwrap();
+        for (path, _, _) in self.entries() {
+            fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+        }
+        Ok(())
+    }
+}
+
+// ─── Comandos ─────────────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_thumbnail_cache(
+    store:    State<'_, SettingsStore>,
+    cache:    State<'_, ThumbnailCache>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<CacheStats> {
+    store.require(&sessions, Role::Viewer)?;
+    Ok(cache.stats(store.thumbnail_cache_limit()))
+}
+
+#[tauri::command]
+pub fn clear_thumbnail_cache(
+    store:    State<'_, SettingsStore>,
+    cache:    State<'_, ThumbnailCache>,
+    sessions: State<'_, Sessions>,
+) -> AppResult<CacheStats> {
+    store.require(&sessions, Role::Admin)?;
+    cache.clear()?;
+    Ok(cache.stats(store.thumbnail_cache_limit()))
+}
@@ -1,8 +1,10 @@
-import { useState, useEffect, useRef, useCallback } from 'react';
+import { useState, useEffect, useRef, useCallback, useMemo, createContext, useContext } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useTranslation } from 'react-i18next';
 import { translateBlock, translateArray, translateLocations } from './services/translate';
-import { Search, Info, Download, X, User, ChevronDown, Fingerprint, MapPin, Briefcase, Globe } from 'lucide-react';
+import { errorMessage, isAppError } from './services/errors';
+import { imageUrl, thumbUrl } from './services/images';
+import { Search, Info, Download, X, User, ChevronDown, Fingerprint, MapPin, Briefcase, Globe, Languages, ArrowUpDown, SlidersHorizontal, Link2, Copy, ShieldCheck, History, Lock, LogOut, KeyRound, Users } from 'lucide-react';
 import { motion, AnimatePresence } from 'framer-motion';
 import { clsx, type ClassValue } from 'clsx';
 import { twMerge } from 'tailwind-merge';
@@ -18,12 +20,102 @@ interface Individual {
     name: string;
     category: string;
     source: string;
+    birth_date_iso?: string | null;
+    age?: number | null;
     img_path?: string;
     has_embedding: number;
     reward?: string;
     ingested_at?: string;
+    snippet?: string | null;
+    match_score?: number | null;
+    linked: LinkedRecord[];
 }
 
+interface LinkedRecord {
+    id: string;
+    name: string;
+    source: string;
+}
+
+// Duplicatas entre fontes (crates/intelligence-db/src/linkage.rs)
+type Agreement = 'exact' | 'partial' | 'conflict' | 'unknown';
+type Decision = 'same' | 'different';
+
+interface MatchEvidence {
+    score: number;
+    name: number;
+    alias_match: boolean;
+    birth: Agreement;
+    nationality: Agreement;
+}
+
+interface DuplicateCluster {
+    score: number;
+    members: Array<{ id: string; name: string; source: string; birth_date_iso?: string | null; nationalities: string[] }>;
+    pairs: Array<{ id_a: string; id_b: string; evidence: MatchEvidence }>;
+}
+
+interface LinkDecision {
+    id_a: string;
+    id_b: string;
+    decision: Decision;
+    note?: string | null;
+    decided_at: string;
+}
+
+interface Comparison {
+    left: IndividualDetail;
+    right: IndividualDetail;
+    evidence: MatchEvidence;
+    fields: Array<{ field: string; left?: string | null; right?: string | null; agreement: Agreement }>;
+    decision: LinkDecision | null;
+}
+
+interface FacetCount {
+    value: string;
+    count: number;
+}
+
+interface Facets {
+    category: FacetCount[];
+    source: FacetCount[];
+    nationality: FacetCount[];
+    crime: FacetCount[];
+    location_country: FacetCount[];
+    has_images: FacetCount[];
+}
+
+interface SearchPage {
+    items: Individual[];
+    total: number;
+    next_cursor: string | null;
+    limit: number;
+    facets: Facets | null;
+}
+
+// Filtros sobre descritores normalizados (idade, altura em cm, peso em kg, cores)
+interface Descriptors {
+    age_min: string;
+    age_max: string;
+    height_min: string;
+    height_max: string;
+    weight_min: string;
+    weight_max: string;
+    eye_color: string;
+    hair_color: string;
+}
+
+const NO_DESCRIPTORS: Descriptors = {
+    age_min: '', age_max: '', height_min: '', height_max: '', weight_min: '', weight_max: '', eye_color: '', hair_color: ''
+};
+
+const EYE_COLORS = ['black', 'blue', 'brown', 'gray', 'green', 'hazel'];
+const HAIR_COLORS = ['bald', 'black', 'blond', 'brown', 'gray', 'red', 'white'];
+
+type SortKey = 'relevance' | 'name' | 'ingested_at' | 'source' | 'reward' | 'birth_date';
+
+const PAGE_SIZE = 40;
+
 interface Stats {
     total: number;
     wanted: number;
@@ -31,6 +123,97 @@ interface Stats {
     with_biometrics: number;
 }
 
+// Saúde da ingestão (repo::ingestion_stats)
+interface Completeness {
+    images: number;
+    birth_date: number;
+    description: number;
+    locations: number;
+}
+
+interface IngestionStats {
+    days: number;
+    stale_days: number;
+    timeline: Array<{ day: string; source: string; count: number }>;
+    completeness: Completeness;
+    sources: Array<{ source: string; total: number; stale: number; last_ingested?: string | null; last_refresh?: string | null; completeness: Completeness }>;
+    top_crimes: FacetCount[];
+    top_nationalities: FacetCount[];
+    stale: number;
+}
+
+// Auditoria de qualidade (crates/intelligence-db/src/audit.rs)
+interface AuditReport {
+    generated_at: string;
+    images_dir?: string | null;
+    individuals: number;
+    total: number;
+    categories: Array<{ kind: string; count: number; issues: Array<{ id: string; table: string; detail?: string | null }> }>;
+}
+
+// Log de auditoria encadeado (crates/intelligence-db/src/audit_log.rs)
+interface AuditEvent {
+    id: number;
+    at: string;
+    actor: string;
+    action: string;
+    target?: string | null;
+    detail: unknown;
+    hash?: string | null;
+}
+
+interface ChainReport {
+    verified_at: string;
+    events: number;
+    valid: boolean;
+    head_hash?: string | null;
+    breaks: Array<{ id: number; kind: string; detail: string }>;
+}
+
+// Contas locais e sessão (crates/intelligence-db/src/accounts.rs)
+type Role = 'viewer' | 'reviewer' | 'admin';
+const ROLES: Role[] = ['viewer', 'reviewer', 'admin'];
+
+interface Account {
+    username: string;
+    role: Role;
+    disabled: boolean;
+    created_at: string;
+    updated_at: string;
+    last_login?: string | null;
+}
+
+interface SessionStatus {
+    setup_required: boolean;
+    account?: Account | null;
+    locked_user?: string | null;
+    idle_lock_minutes: number;
+}
+
+// Atividade do operador vai ao backend no máximo a cada 30 s
+const TOUCH_INTERVAL_MS = 30_000;
+const IDLE_CHECK_MS = 15_000;
+const ACTIVITY_EVENTS = ['mousemove', 'mousedown', 'keydown', 'wheel', 'touchstart'];
+
+const SOURCE_COLORS = ['bg-accent-amber', 'bg-accent-emerald', 'bg-sky-400', 'bg-red-400', 'bg-violet-400', 'bg-pink-400', 'bg-lime-400', 'bg-orange-300'];
+
+interface ThumbnailCacheStats {
+    entries: number;
+    bytes: number;
+    limit_bytes: number;
+}
+
+interface SchemaInfo {
+    version: number;
+    latest: number;
+    missing: string[];
+}
+
+interface SettingsView {
+    configured: boolean;
+    config_file: string;
+}
+
 interface Location {
     loc_type: string;
     country?: string | null;
@@ -50,6 +233,7 @@ interface IndividualDetail extends Individual {
     eye_color?: string;
     hair_color?: string;
     occupation?: string;
+    normalized: { height_cm?: number | null; weight_kg?: number | null; eye_color?: string | null; hair_color?: string | null };
     images: Array<{ img_path: string; is_primary: number }>;
     crimes: string[];
     locations: Location[];
@@ -57,21 +241,375 @@ interface IndividualDetail extends Individual {
 
 // ─── Componentes ─────────────────────────────────────────────────────────────
 
+interface Session {
+    account: Account;
+    can: (role: Role) => boolean;
+    apply: (status: SessionStatus) => void;
+}
+
+const SessionContext = createContext<Session | null>(null);
+
+function useSession(): Session {
+    return useContext(SessionContext)!;
+}
+
+// O backend confere o perfil em cada comando e tranca a sessão ociosa; aqui ficam a
+// tela de login/bloqueio e o aviso de atividade. Trancado, o catálogo continua
+// montado por baixo (a busca volta como estava); outro operador começa do zero.
 export default function App() {
+    const [status, setStatus] = useState<SessionStatus | null>(null);
+    const [error, setError] = useState<string | null>(null);
+    const lastActivity = useRef(Date.now());
+    const lastTouch = useRef(0);
+    const shown = useRef<Session | null>(null);
+
+    const apply = useCallback((next: SessionStatus) => {
+        setStatus(next);
+        lastActivity.current = Date.now();
+        lastTouch.current = Date.now();
+    }, []);
+
+    const refresh = useCallback(() => {
+        invoke<SessionStatus>('get_session').then(apply).catch(err => setError(errorMessage(err)));
+    }, [apply]);
+
+    useEffect(() => {
+        if (!(window as any).__TAURI_INTERNALS__) {
+            setError("TAURI_NOT_DETECTED: Execute via 'npm run tauri dev'");
+            return;
+        }
+        refresh();
+    }, [refresh]);
+
+    const account = status?.account ?? null;
+    const idleMs = (status?.idle_lock_minutes ?? 0) * 60_000;
+
+    useEffect(() => {
+        if (!account) return;
+        const onActivity = () => {
+            lastActivity.current = Date.now();
+            if (Date.now() - lastTouch.current < TOUCH_INTERVAL_MS) return;
+            lastTouch.current = Date.now();
+            invoke<Account>('touch_session').catch(refresh);
+        };
+        ACTIVITY_EVENTS.forEach(e => window.addEventListener(e, onActivity, { passive: true }));
+        const timer = setInterval(() => {
+            if (Date.now() - lastActivity.current >= idleMs) {
+                invoke<SessionStatus>('lock_session').then(apply).catch(refresh);
+            }
+        }, IDLE_CHECK_MS);
+        return () => {
+            ACTIVITY_EVENTS.forEach(e => window.removeEventListener(e, onActivity));
+            clearInterval(timer);
+        };
+    }, [account, idleMs, apply, refresh]);
+
+    const session = useMemo<Session | null>(() => account && {
+        account,
+        can: (role: Role) => ROLES.indexOf(account.role) >= ROLES.indexOf(role),
+        apply,
+    }, [account, apply]);
+    if (session) shown.current = session;
+
+    return (
+        <>
+            {shown.current && (
+                <SessionContext.Provider value={shown.current}>
+                    <Catalog key={shown.current.account.username} />
+                </SessionContext.Provider>
+            )}
+            {!session && <AuthScreen status={status} error={error} onSession={apply} />}
+        </>
+    );
+}
+
+function AuthScreen({ status, error, onSession }: {
+    status: SessionStatus | null,
+    error: string | null,
+    onSession: (status: SessionStatus) => void
+}) {
+    const { t } = useTranslation();
+    const [username, setUsername] = useState('');
+    const [password, setPassword] = useState('');
+    const [confirm, setConfirm] = useState('');
+    const [message, setMessage] = useState<string | null>(null);
+    const setup = !!status?.setup_required;
+
+    useEffect(() => {
+        setUsername(status?.locked_user ?? '');
+        setPassword('');
+        setConfirm('');
+        setMessage(null);
+    }, [status]);
+
+    async function submit(e: React.FormEvent) {
+        e.preventDefault();
+        if (setup && password !== confirm) {
+            setMessage(t('auth.mismatch'));
+            return;
+        }
+        try {
+            onSession(await invoke<SessionStatus>(setup ? 'setup_admin' : 'login', { username, password }));
+        } catch (err) {
+            setPassword('');
+            setMessage(errorMessage(err));
+        }
+    }
+
+    const title = setup ? 'auth.setup_title' : status?.locked_user ? 'auth.locked_title' : 'auth.title';
+    return (
+        <div className="fixed inset-0 z-[200] bg-background flex items-center justify-center">
+            <form onSubmit={submit} className="glass-panel rounded-2xl p-10 w-full max-w-sm flex flex-col gap-4">
+                <div className="flex items-center justify-center gap-3">
+                    <div className="w-10 h-10 bg-accent-amber rounded-lg flex items-center justify-center text-black font-black text-xl">O</div>
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t(title)}</h2>
+                </div>
+                {setup && <p className="text-xs text-muted text-center">{t('auth.setup_hint')}</p>}
+                <input
+                    type="text"
+                    value={username}
+                    autoFocus={!status?.locked_user}
+                    autoComplete="username"
+                    placeholder={t('auth.username')}
+                    onChange={e => setUsername(e.target.value)}
+                    className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none"
+                />
+                <input
+                    type="password"
+                    value={password}
+                    autoFocus={!!status?.locked_user}
+                    autoComplete={setup ? 'new-password' : 'current-password'}
+                    placeholder={t('auth.password')}
+                    onChange={e => setPassword(e.target.value)}
+                    className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none"
+                />
+                {setup && (
+                    <input
+                        type="password"
+                        value={confirm}
+                        autoComplete="new-password"
+                        placeholder={t('auth.confirm')}
+                        onChange={e => setConfirm(e.target.value)}
+                        className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none"
+                    />
+                )}
+                {(message || error) && <p className="text-[11px] text-red-400">{message || error}</p>}
+                <button type="submit" className="h-10 px-6 rounded-full bg-accent-amber text-black text-[11px] font-black tracking-widest">
+                    {t(setup ? 'auth.create_admin' : 'auth.login')}
+                </button>
+            </form>
+        </div>
+    );
+}
+
+// Conta logada, troca de senha, contas (admin), trancar e sair
+function SessionControls() {
+    const { t } = useTranslation();
+    const { account, can, apply } = useSession();
+    const [showAccounts, setShowAccounts] = useState(false);
+    const [showPassword, setShowPassword] = useState(false);
+
+    const end = (command: 'lock_session' | 'logout') => invoke<SessionStatus>(command).then(apply).catch(console.error);
+    const iconButton = "w-9 h-9 rounded-full border border-white/10 text-muted hover:bg-white/5 hover:text-white transition-all flex items-center justify-center";
+    return (
+        <div className="flex items-center gap-2">
+            <span className="text-[10px] font-mono text-muted whitespace-nowrap">
+                {account.username} · <span className="text-accent-amber">{t(`roles.${account.role}`)}</span>
+            </span>
+            {can('admin') && (
+                <button onClick={() => setShowAccounts(true)} title={t('accounts.open')} className={iconButton}>
+                    <Users className="w-3.5 h-3.5" />
+                </button>
+            )}
+            <button onClick={() => setShowPassword(true)} title={t('auth.change_password')} className={iconButton}>
+                <KeyRound className="w-3.5 h-3.5" />
+            </button>
+            <button onClick={() => end('lock_session')} title={t('auth.lock')} className={iconButton}>
+                <Lock className="w-3.5 h-3.5" />
+            </button>
+            <button onClick={() => end('logout')} title={t('auth.logout')} className={iconButton}>
+                <LogOut className="w-3.5 h-3.5" />
+            </button>
+            {showAccounts && <AccountsModal onClose={() => setShowAccounts(false)} />}
+            {showPassword && <PasswordModal onClose={() => setShowPassword(false)} />}
+        </div>
+    );
+}
+
+function PasswordModal({ onClose }: { onClose: () => void }) {
+    const { t } = useTranslation();
+    const [current, setCurrent] = useState('');
+    const [next, setNext] = useState('');
+    const [confirm, setConfirm] = useState('');
+    const [message, setMessage] = useState<string | null>(null);
+
+    async function save(e: React.FormEvent) {
+        e.preventDefault();
+        if (next !== confirm) {
+            setMessage(t('auth.mismatch'));
+            return;
+        }
+        try {
+            await invoke('change_password', { current, new: next });
+            onClose();
+        } catch (err) {
+            setMessage(errorMessage(err));
+        }
+    }
+
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <form onSubmit={save} className="glass-panel rounded-2xl w-full max-w-sm p-8 flex flex-col gap-4" onClick={e => e.stopPropagation()}>
+                <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('auth.change_password')}</h2>
+                <input type="password" value={current} autoComplete="current-password" placeholder={t('auth.current_password')} onChange={e => setCurrent(e.target.value)} className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none" />
+                <input type="password" value={next} autoComplete="new-password" placeholder={t('auth.new_password')} onChange={e => setNext(e.target.value)} className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none" />
+                <input type="password" value={confirm} autoComplete="new-password" placeholder={t('auth.confirm')} onChange={e => setConfirm(e.target.value)} className="h-10 px-4 rounded-full border border-white/10 bg-transparent text-sm focus:border-accent-amber/50 outline-none" />
+                {message && <p className="text-[11px] text-red-400">{message}</p>}
+                <div className="flex items-center justify-end gap-3">
+                    <FilterButton active={false} onClick={onClose}>{t('auth.cancel')}</FilterButton>
+                    <button type="submit" className="h-9 px-6 rounded-full bg-accent-amber text-black text-[10px] font-black tracking-widest">{t('auth.save')}</button>
+                </div>
+            </form>
+        </div>
+    );
+}
+
+// Contas dos operadores (admin): perfil, ativa/desativada, nova senha e criação
+function AccountsModal({ onClose }: { onClose: () => void }) {
+    const { t } = useTranslation();
+    const { apply } = useSession();
+    const [accounts, setAccounts] = useState<Account[] | null>(null);
+    const [resets, setResets] = useState<Record<string, string>>({});
+    const [username, setUsername] = useState('');
+    const [password, setPassword] = useState('');
+    const [role, setRole] = useState<Role>('viewer');
+    const [message, setMessage] = useState<string | null>(null);
+
+    const load = useCallback(() => {
+        invoke<Account[]>('list_accounts').then(setAccounts).catch(err => setMessage(errorMessage(err)));
+    }, []);
+    useEffect(load, [load]);
+
+    async function update(target: string, change: { role?: Role; disabled?: boolean; password?: string }) {
+        setMessage(null);
+        try {
+            await invoke<Account>('update_account', { username: target, change });
+            if (change.password) {
+                setResets(r => ({ ...r, [target]: '' }));
+                setMessage(t('accounts.password_reset', { username: target }));
+            }
+            // O próprio admin pode ter mudado de perfil
+            apply(await invoke<SessionStatus>('get_session'));
+        } catch (err) {
+            setMessage(errorMessage(err));
+        }
+        load();
+    }
+
+    async function create(e: React.FormEvent) {
+        e.preventDefault();
+        setMessage(null);
+        try {
+            await invoke<Account>('create_account', { username, password, role });
+            setUsername('');
+            setPassword('');
+            load();
+        } catch (err) {
+            setMessage(errorMessage(err));
+        }
+    }
+
+    const roleSelect = (value: Role, onChange: (role: Role) => void) => (
+        <div className="h-9 px-4 rounded-full border border-white/10 flex items-center">
+            <select value={value} onChange={e => onChange(e.target.value as Role)} className="ghost-select text-[10px] font-black tracking-widest uppercase">
+                {ROLES.map(r => <option key={r} value={r}>{t(`roles.${r}`)}</option>)}
+            </select>
+        </div>
+    );
+
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <div className="glass-panel rounded-2xl w-full max-w-4xl max-h-full overflow-y-auto p-8 flex flex-col gap-6" onClick={e => e.stopPropagation()}>
+                <div className="flex items-center justify-between">
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('accounts.title')}</h2>
+                    <button onClick={onClose} className="text-muted hover:text-white"><X className="w-5 h-5" /></button>
+                </div>
+                <p className="text-[11px] text-muted">{t('accounts.hint')}</p>
+
+                {!accounts && !message && <div className="w-6 h-6 border-2 border-accent-amber border-t-transparent rounded-full animate-spin" />}
+                {accounts && (
+                    <div className="flex flex-col gap-2">
+                        {accounts.map(a => (
+                            <div key={a.username} className="flex items-center gap-3 text-[11px]">
+                                <span className="w-40 truncate font-mono text-white" title={t('accounts.last_login', { at: a.last_login || t('accounts.never') })}>{a.username}</span>
+                                {roleSelect(a.role, r => update(a.username, { role: r }))}
+                                <FilterButton active={!a.disabled} onClick={() => update(a.username, { disabled: !a.disabled })}>
+                                    {t(a.disabled ? 'accounts.disabled' : 'accounts.active')}
+                                </FilterButton>
+                                <input
+                                    type="password"
+                                    value={resets[a.username] ?? ''}
+                                    autoComplete="new-password"
+                                    placeholder={t('auth.new_password')}
+                                    onChange={e => setResets(r => ({ ...r, [a.username]: e.target.value }))}
+                                    className="h-9 px-4 rounded-full border border-white/10 bg-transparent text-[11px] focus:border-accent-amber/50 outline-none flex-1"
+                                />
+                                <FilterButton active={false} onClick={() => resets[a.username] && update(a.username, { password: resets[a.username] })}>
+                                    {t('accounts.reset')}
+                                </FilterButton>
+                            </div>
+                        ))}
+                    </div>
+                )}
+
+                <form onSubmit={create} className="flex items-center gap-3 border-t border-white/5 pt-6">
+                    <input type="text" value={username} placeholder={t('auth.username')} onChange={e => setUsername(e.target.value)} className="h-9 px-4 rounded-full border border-white/10 bg-transparent text-[11px] focus:border-accent-amber/50 outline-none flex-1" />
+                    <input type="password" value={password} autoComplete="new-password" placeholder={t('auth.password')} onChange={e => setPassword(e.target.value)} className="h-9 px-4 rounded-full border border-white/10 bg-transparent text-[11px] focus:border-accent-amber/50 outline-none flex-1" />
+                    {roleSelect(role, setRole)}
+                    <button type="submit" className="h-9 px-6 rounded-full bg-accent-amber text-black text-[10px] font-black tracking-widest">{t('accounts.create')}</button>
+                </form>
+                {message && <p className="text-[11px] text-muted">{message}</p>}
+            </div>
+        </div>
+    );
+}
+
+function Catalog() {
     const { t, i18n } = useTranslation();
+    const { can } = useSession();
     const [individuals, setIndividuals] = useState<Individual[]>([]);
     const [stats, setStats] = useState<Stats | null>(null);
+    const [schema, setSchema] = useState<SchemaInfo | null>(null);
     const [searchQuery, setSearchQuery] = useState('');
     const [category, setCategory] = useState<string>('');
     const [bioOnly, setBioOnly] = useState(false);
+    const [fuzzy, setFuzzy] = useState(false);
     const [source, setSource] = useState('');
     const [country, setCountry] = useState('');
-    const [page, setPage] = useState(0);
+    const [crime, setCrime] = useState('');
+    const [locationType, setLocationType] = useState('');
+    const [locationCountry, setLocationCountry] = useState('');
+    const [locationState, setLocationState] = useState('');
+    const [locationCity, setLocationCity] = useState('');
+    const [hasImages, setHasImages] = useState<boolean | null>(null);
+    const [descriptors, setDescriptors] = useState<Descriptors>(NO_DESCRIPTORS);
+    const [showDescriptors, setShowDescriptors] = useState(false);
+    const [groupLinked, setGroupLinked] = useState(true);
+    const [showDuplicates, setShowDuplicates] = useState(false);
+    const [showHealth, setShowHealth] = useState(false);
+    const [showAudit, setShowAudit] = useState(false);
+    const [showAccessLog, setShowAccessLog] = useState(false);
+    const [facets, setFacets] = useState<Facets | null>(null);
+    const [sort, setSort] = useState<SortKey>('relevance');
+    const [descending, setDescending] = useState<boolean | null>(null);
+    const [cursor, setCursor] = useState<string | null>(null);
+    const [total, setTotal] = useState<number | null>(null);
     const [loading, setLoading] = useState(false);
-    const [hasMore, setHasMore] = useState(true);
     const [selectedId, setSelectedId] = useState<string | null>(null);
     const [detail, setDetail] = useState<IndividualDetail | null>(null);
     const [tauriError, setTauriError] = useState<string | null>(null);
+    const [queryError, setQueryError] = useState<{ message: string; position: number } | null>(null);
+    const [needsSetup, setNeedsSetup] = useState(false);
 
     const observer = useRef<IntersectionObserver | null>(null);
     const lastElementRef = useRef<HTMLDivElement | null>(null);
@@ -82,9 +620,29 @@ export default function App() {
             setTauriError("TAURI_NOT_DETECTED: Execute via 'npm run tauri dev'");
             return;
         }
-        invoke<Stats>('get_stats').then(setStats).catch(err => setTauriError(String(err)));
+        invoke<SettingsView>('get_settings').then(view => {
+            setNeedsSetup(!view.configured);
+            if (view.configured) {
+                invoke<SchemaInfo>('get_schema_info').then(setSchema).catch(err => setTauriError(errorMessage(err)));
+                invoke<Stats>('get_stats').then(setStats).catch(err => setTauriError(errorMessage(err)));
+            }
+        }).catch(err => setTauriError(errorMessage(err)));
     }, []);
 
+    async function pickDataRoot() {
+        try {
+            const view = await invoke<SettingsView | null>('pick_data_root');
+            if (!view?.configured) return;
+            setNeedsSetup(false);
+            setTauriError(null);
+            invoke<SchemaInfo>('get_schema_info').then(setSchema).catch(err => setTauriError(errorMessage(err)));
+            invoke<Stats>('get_stats').then(setStats).catch(err => setTauriError(errorMessage(err)));
+            loadPage(true);
+        } catch (err) {
+            setTauriError(`${t('setup.invalid')}: ${errorMessage(err)}`);
+        }
+    }
+
     // Atalhos de Teclado
     useEffect(() => {
         const handleKeyDown = (e: KeyboardEvent) => {
@@ -101,37 +659,66 @@ export default function App() {
     // Busca e Scroll
     useEffect(() => {
         const timeout = setTimeout(() => {
-            loadPage(0, true);
+            loadPage(true);
         }, 300);
         return () => clearTimeout(timeout);
-    }, [searchQuery, category, bioOnly, source, country]);
+    }, [searchQuery, category, bioOnly, source, country, crime, locationType, locationCountry, locationState, locationCity, hasImages, descriptors, groupLinked, fuzzy, sort, descending]);
 
-    async function loadPage(p: number, reset = false) {
+    // Próxima página pelo cursor da anterior (reset: primeira página)
+    async function loadPage(reset = false) {
         if (!(window as any).__TAURI_INTERNALS__) return;
         if (loading && !reset) return;
         setLoading(true);
         try {
-            const results = await invoke<Individual[]>('search_individuals', {
-                name: searchQuery || null,
+            const results = await invoke<SearchPage>('search_individuals', {
+                query: searchQuery || null,
                 category: category || null,
                 has_embedding: bioOnly || null,
                 source_filter: source || null,
                 country: country || null,
-                page: p,
-                limit: 40
+                crime: crime || null,
+                location_type: locationType || null,
+                location_country: locationCountry || null,
+                location_state: locationState || null,
+                location_city: locationCity || null,
+                has_images: hasImages,
+                age_min: numberOrNull(descriptors.age_min),
+                age_max: numberOrNull(descriptors.age_max),
+                height_min: numberOrNull(descriptors.height_min),
+                height_max: numberOrNull(descriptors.height_max),
+                weight_min: numberOrNull(descriptors.weight_min),
+                weight_max: numberOrNull(descriptors.weight_max),
+                eye_color: descriptors.eye_color || null,
+                hair_color: descriptors.hair_color || null,
+                fuzzy: fuzzy || null,
+                sort,
+                descending,
+                group_linked: groupLinked,
+                cursor: reset ? null : cursor,
+                limit: PAGE_SIZE,
+                facets: reset
             });
             if (reset) {
-                setIndividuals(results);
-                setPage(0);
+                setIndividuals(results.items);
+                setFacets(results.facets);
             } else {
-                setIndividuals(prev => [...prev, ...results]);
-                setPage(p);
+                setIndividuals(prev => [...prev, ...results.items]);
             }
-            setHasMore(results.length === 40);
+            setCursor(results.next_cursor);
+            setTotal(results.total);
             setTauriError(null);
+            setQueryError(null);
         } catch (err) {
             console.error(err);
-            setTauriError(String(err));
+            // Erro de sintaxe da busca: aponta o trecho na caixa, sem derrubar o resto da UI
+            if (isAppError(err) && err.code === 'QUERY_SYNTAX') {
+                setQueryError({
+                    message: t(`query_errors.${err.context.reason}`, { ...err.context, defaultValue: errorMessage(err) }),
+                    position: Number(err.context.position)
+                });
+            } else {
+                setTauriError(errorMessage(err));
+            }
         } finally {
             setLoading(false);
         }
@@ -142,12 +729,12 @@ export default function App() {
         if (loading) return;
         if (observer.current) observer.current.disconnect();
         observer.current = new IntersectionObserver(entries => {
-            if (entries[0].isIntersecting && hasMore) {
-                loadPage(page + 1);
+            if (entries[0].isIntersecting && cursor) {
+                loadPage();
             }
         });
         if (lastElementRef.current) observer.current.observe(lastElementRef.current);
-    }, [loading, hasMore]);
+    }, [loading, cursor]);
 
     // Carregar Detalhe
     useEffect(() => {
@@ -175,7 +762,16 @@ export default function App() {
                     </div>
                 )}
                 <div className="flex gap-4">
+                    <ThumbnailCacheStatus />
                     <span>{t('common.db_status')}</span>
+                    {schema && (
+                        <span
+                            className={cn(schema.version < schema.latest && 'text-accent-amber')}
+                            title={schema.missing.length ? `${t('common.schema_missing')}: ${schema.missing.join(', ')}` : undefined}
+                        >
+                            {t('common.schema_version', { version: schema.version, latest: schema.latest })}
+                        </span>
+                    )}
                     <span>v1.0.5-ULTRA</span>
                 </div>
             </div>
@@ -192,14 +788,20 @@ export default function App() {
                     </div>
 
                     {/* STATS */}
-                    <div className="hidden lg:flex items-center gap-8 border-l border-white/10 pl-12 h-10">
+                    <button
+                        onClick={() => setShowHealth(true)}
+                        title={t('health.open')}
+                        className="hidden lg:flex items-center gap-8 border-l border-white/10 pl-12 h-10 text-left hover:opacity-80 transition-all"
+                    >
                         <StatItem label={t('stats.wanted')} value={stats?.wanted} color="text-red-500" />
                         <StatItem label={t('stats.missing')} value={stats?.missing} color="text-accent-amber" />
                         <StatItem label={t('stats.biometrics')} value={stats?.with_biometrics} color="text-accent-emerald" />
-                    </div>
+                    </button>
                 </div>
 
                 <div className="flex items-center gap-6">
+                    <SessionControls />
+
                     {/* LANGUAGE SELECTOR */}
                     <div className="flex items-center gap-2 bg-white/[0.03] border border-white/10 rounded-full px-4 h-9 hover:bg-white/[0.05] transition-all group-focus-within:border-accent-amber/50">
                         <Globe className="w-3 h-3 text-muted" />
@@ -220,10 +822,23 @@ export default function App() {
                         <input
                             type="text"
                             placeholder={t('common.search_placeholder')}
-                            className="w-full h-11 bg-white/[0.03] border border-white/10 rounded-full pl-11 pr-4 text-sm font-medium focus:outline-none focus:border-accent-amber/50 focus:bg-white/[0.05] transition-all"
+                            title={t('common.query_hint')}
+                            className={cn(
+                                "w-full h-11 bg-white/[0.03] border border-white/10 rounded-full pl-11 pr-4 text-sm font-medium focus:outline-none focus:border-accent-amber/50 focus:bg-white/[0.05] transition-all",
+                                queryError && "border-red-500/60 focus:border-red-500/60"
+                            )}
                             value={searchQuery}
                             onChange={(e) => setSearchQuery(e.target.value)}
                         />
+                        {queryError && (
+                            <div className="absolute left-4 right-4 top-full mt-1 text-[10px] font-mono text-red-400">
+                                <div className="truncate">
+                                    {searchQuery.slice(0, queryError.position)}
+                                    <span className="bg-red-500/30 text-white">{searchQuery.slice(queryError.position) || ' '}</span>
+                                </div>
+                                <div>{queryError.message}</div>
+                            </div>
+                        )}
                     </div>
                 </div>
             </header>
@@ -247,6 +862,17 @@ export default function App() {
                         <Fingerprint className="w-3.5 h-3.5" /> {t('filters.biometry')}
                     </button>
 
+                    <button
+                        onClick={() => setFuzzy(!fuzzy)}
+                        title={t('filters.fuzzy_hint')}
+                        className={cn(
+                            "h-9 px-4 rounded-full border text-[10px] font-black tracking-widest transition-all flex items-center gap-2",
+                            fuzzy ? "bg-accent-amber/20 border-accent-amber text-accent-amber" : "border-white/10 text-muted hover:bg-white/5"
+                        )}
+                    >
+                        <Languages className="w-3.5 h-3.5" /> {t('filters.fuzzy')}
+                    </button>
+
                     <div className="relative group">
                         <select
                             value={source}
@@ -275,18 +901,150 @@ export default function App() {
                         </select>
                         <ChevronDown className="absolute right-4 top-1/2 -translate-y-1/2 w-3 h-3 text-muted pointer-events-none" />
                     </div>
+
+                    <div className="w-[1px] h-4 bg-white/10 mx-4" />
+
+                    <button
+                        onClick={() => setShowDescriptors(!showDescriptors)}
+                        className={cn(
+                            "h-9 px-4 rounded-full border text-[10px] font-black tracking-widest transition-all flex items-center gap-2",
+                            Object.values(descriptors).some(v => v !== '') ? "bg-accent-amber/20 border-accent-amber text-accent-amber" : "border-white/10 text-muted hover:bg-white/5"
+                        )}
+                    >
+                        <SlidersHorizontal className="w-3.5 h-3.5" /> {t('filters.descriptors')}
+                    </button>
+
+                    <div className="w-[1px] h-4 bg-white/10 mx-4" />
+
+                    <LocationFilter
+                        type={locationType}
+                        country={locationCountry}
+                        state={locationState}
+                        city={locationCity}
+                        onType={setLocationType}
+                        onCountry={value => { setLocationCountry(value); setLocationState(''); setLocationCity(''); }}
+                        onState={value => { setLocationState(value); setLocationCity(''); }}
+                        onCity={setLocationCity}
+                    />
+
+                    <div className="w-[1px] h-4 bg-white/10 mx-4" />
+
+                    <div className="relative group">
+                        <select
+                            value={sort}
+                            onChange={(e) => { setSort(e.target.value as SortKey); setDescending(null); }}
+                            className="appearance-none h-9 pl-4 pr-10 rounded-full border border-white/10 bg-transparent text-[10px] font-black tracking-widest hover:bg-white/5 transition-all outline-none cursor-pointer"
+                        >
+                            {(['relevance', 'name', 'ingested_at', 'source', 'reward', 'birth_date'] as SortKey[]).map(key => (
+                                <option key={key} value={key} className="bg-surface">{t(`filters.sort_${key}`)}</option>
+                            ))}
+                        </select>
+                        <ChevronDown className="absolute right-4 top-1/2 -translate-y-1/2 w-3 h-3 text-muted pointer-events-none" />
+                    </div>
+
+                    {sort !== 'relevance' && (
+                        <button
+                            onClick={() => setDescending(descending === null ? !['ingested_at', 'reward'].includes(sort) : !descending)}
+                            title={t('filters.sort_direction')}
+                            className="h-9 w-9 rounded-full border border-white/10 text-muted hover:bg-white/5 transition-all flex items-center justify-center"
+                        >
+                            <ArrowUpDown className="w-3.5 h-3.5" />
+                        </button>
+                    )}
                 </div>
 
+                <div className="flex items-center gap-3">
+                <button
+                    onClick={() => setGroupLinked(!groupLinked)}
+                    title={t('linkage.group_hint')}
+                    className={cn(
+                        "h-9 px-4 rounded-full border text-[10px] font-black tracking-widest transition-all flex items-center gap-2",
+                        groupLinked ? "bg-accent-amber/20 border-accent-amber text-accent-amber" : "border-white/10 text-muted hover:bg-white/5"
+                    )}
+                >
+                    <Link2 className="w-3.5 h-3.5" /> {t('linkage.group')}
+                </button>
+
+                <button
+                    onClick={() => setShowDuplicates(true)}
+                    className="h-9 px-4 rounded-full border border-white/10 text-muted text-[10px] font-black tracking-widest hover:bg-white/5 transition-all flex items-center gap-2"
+                >
+                    <Copy className="w-3.5 h-3.5" /> {t('linkage.duplicates')}
+                </button>
+
+                {can('reviewer') && (
+                    <button
+                        onClick={() => setShowAudit(true)}
+                        className="h-9 px-4 rounded-full border border-white/10 text-muted text-[10px] font-black tracking-widest hover:bg-white/5 transition-all flex items-center gap-2"
+                    >
+                        <ShieldCheck className="w-3.5 h-3.5" /> {t('audit.open')}
+                    </button>
+                )}
+
+                {can('admin') && (
+                    <button
+                        onClick={() => setShowAccessLog(true)}
+                        className="h-9 px-4 rounded-full border border-white/10 text-muted text-[10px] font-black tracking-widest hover:bg-white/5 transition-all flex items-center gap-2"
+                    >
+                        <History className="w-3.5 h-3.5" /> {t('access_log.open')}
+                    </button>
+                )}
+
                 <button
                     onClick={() => invoke('export_csv').catch(alert)}
                     className="h-9 px-5 rounded-full border border-accent-amber/20 text-accent-amber text-[10px] font-black tracking-widest hover:bg-accent-amber/10 transition-all flex items-center gap-2 shadow-[0_0_15px_rgba(245,158,11,0.05)]"
                 >
                     <Download className="w-3.5 h-3.5" /> {t('common.export_csv')}
                 </button>
+                </div>
             </div>
 
+            {showDescriptors && (
+                <DescriptorFilter
+                    value={descriptors}
+                    onChange={setDescriptors}
+                    onClear={() => setDescriptors(NO_DESCRIPTORS)}
+                />
+            )}
+
+            <div className="flex-1 flex">
+            {/* FACETAS */}
+            {facets && (
+                <FacetSidebar
+                    facets={facets}
+                    selected={{
+                        category,
+                        source,
+                        nationality: country,
+                        crime,
+                        location_country: locationCountry,
+                        has_images: hasImages === null ? '' : String(hasImages),
+                    }}
+                    onSelect={(facet, value) => {
+                        switch (facet) {
+                            case 'category': setCategory(value); break;
+                            case 'source': setSource(value); break;
+                            case 'nationality': setCountry(value); break;
+                            case 'crime': setCrime(value); break;
+                            case 'location_country': setLocationCountry(value); break;
+                            case 'has_images': setHasImages(value === '' ? null : value === 'true'); break;
+                        }
+                    }}
+                />
+            )}
+
             {/* GRID */}
             <main className="flex-1 px-8 pb-12">
+                {total !== null && (
+                    <p className="py-4 text-[10px] font-black tracking-widest text-muted uppercase">
+                        {t('common.results_count', {
+                            shown: individuals.length.toLocaleString(),
+                            total: total.toLocaleString(),
+                            page: Math.max(1, Math.ceil(individuals.length / PAGE_SIZE)),
+                            pages: Math.max(1, Math.ceil(total / PAGE_SIZE)),
+                        })}
+                    </p>
+                )}
                 <div className="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-4 xl:grid-cols-5 2xl:grid-cols-6 gap-6">
                     {individuals.map((person) => (
                         <IndividualCard key={person.id} person={person} onClick={() => setSelectedId(person.id)} />
@@ -299,6 +1057,23 @@ export default function App() {
                     )}
                 </div>
             </main>
+            </div>
+
+            {/* PRIMEIRO USO: seleção da pasta de dados */}
+            {needsSetup && (
+                <div className="fixed inset-0 z-[100] bg-black/80 backdrop-blur flex items-center justify-center">
+                    <div className="glass-panel rounded-2xl p-10 max-w-md text-center flex flex-col items-center gap-6">
+                        <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('setup.title')}</h2>
+                        <p className="text-xs text-muted">{t('setup.hint')}</p>
+                        <button
+                            onClick={pickDataRoot}
+                            className="h-10 px-6 rounded-full bg-accent-amber text-black text-[11px] font-black tracking-widest"
+                        >
+                            {t('setup.pick')}
+                        </button>
+                    </div>
+                </div>
+            )}
 
             {/* MODAL / DOSSIER */}
             <AnimatePresence>
@@ -309,12 +1084,57 @@ export default function App() {
                     />
                 )}
             </AnimatePresence>
+
+            {/* SAÚDE DA INGESTÃO */}
+            {showHealth && <IngestionModal onClose={() => setShowHealth(false)} />}
+
+            {/* AUDITORIA DE QUALIDADE */}
+            {showAudit && (
+                <AuditModal
+                    onClose={() => setShowAudit(false)}
+                    onSelect={id => { setShowAudit(false); setSelectedId(id); }}
+                />
+            )}
+
+            {/* LOG DE AUDITORIA (SUPERVISÃO) */}
+            {showAccessLog && <AccessLogModal onClose={() => setShowAccessLog(false)} />}
+
+            {/* DUPLICATAS ENTRE FONTES */}
+            {showDuplicates && (
+                <DuplicatesModal
+                    onClose={() => setShowDuplicates(false)}
+                    onDecided={() => loadPage(true)}
+                />
+            )}
         </div>
     );
 }
 
 // ─── Sub-componentes ─────────────────────────────────────────────────────────
 
+// Uso do cache de miniaturas (limite em settings.json `thumbnail_cache_mb`); clique limpa
+function ThumbnailCacheStatus() {
+    const { t } = useTranslation();
+    const { can } = useSession();
+    const [cache, setCache] = useState<ThumbnailCacheStats | null>(null);
+    useEffect(() => {
+        if (!(window as any).__TAURI_INTERNALS__) return;
+        invoke<ThumbnailCacheStats>('get_thumbnail_cache').then(setCache).catch(() => setCache(null));
+    }, []);
+    if (!cache) return null;
+
+    const mb = (bytes: number) => (bytes / 1024 / 1024).toFixed(1);
+    return (
+        <button
+            onClick={() => can('admin') && invoke<ThumbnailCacheStats>('clear_thumbnail_cache').then(setCache).catch(() => {})}
+            title={can('admin') ? t('common.thumbnail_cache_clear') : undefined}
+            className="hover:text-white transition-colors"
+        >
+            {t('common.thumbnail_cache', { used: mb(cache.bytes), limit: mb(cache.limit_bytes), count: cache.entries })}
+        </button>
+    );
+}
+
 function StatItem({ label, value, color }: { label: string, value?: number, color: string }) {
     return (
         <div className="flex flex-col">
@@ -338,17 +1158,639 @@ function FilterButton({ children, active, onClick }: { children: React.ReactNode
     );
 }
 
-function IndividualCard({ person, onClick }: { person: Individual, onClick: () => void }) {
+// Barra lateral de facetas: contagens para o filtro atual; clicar filtra, clicar de novo limpa
+function FacetSidebar({ facets, selected, onSelect }: {
+    facets: Facets,
+    selected: Record<keyof Facets, string>,
+    onSelect: (facet: keyof Facets, value: string) => void
+}) {
+    const { t } = useTranslation();
+    const sections: Array<keyof Facets> = ['category', 'source', 'nationality', 'crime', 'location_country', 'has_images'];
+    const label = (facet: keyof Facets, value: string) => {
+        if (facet === 'has_images') return t(value === 'true' ? 'facets.with_images' : 'facets.without_images');
+        if (facet === 'category') return t(`filters.${value}`, { defaultValue: value });
+        return value;
+    };
+    return (
+        <aside className="w-64 shrink-0 border-r border-white/5 px-6 py-6 flex flex-col gap-6 overflow-y-auto">
+            {sections.filter(facet => facets[facet].length > 0).map(facet => (
+                <div key={facet}>
+                    <h3 className="text-[9px] font-black uppercase text-muted tracking-widest mb-2">{t(`facets.${facet}`)}</h3>
+                    <ul className="flex flex-col gap-0.5">
+                        {facets[facet].map(({ value, count }) => {
+                            const active = selected[facet] === value;
+                            return (
+                                <li key={value}>
+                                    <button
+                                        onClick={() => onSelect(facet, active ? '' : value)}
+                                        className={cn(
+                                            "w-full flex justify-between gap-2 px-2 py-1 rounded text-[11px] text-left transition-all",
+                                            active ? "bg-accent-amber/20 text-accent-amber" : "text-white/70 hover:bg-white/5"
+                                        )}
+                                    >
+                                        <span className="truncate">{label(facet, value)}</span>
+                                        <span className="font-mono text-muted">{count.toLocaleString()}</span>
+                                    </button>
+                                </li>
+                            );
+                        })}
+                    </ul>
+                </div>
+            ))}
+        </aside>
+    );
+}
+
+function numberOrNull(value: string): number | null {
+    const n = Number(value);
+    return value.trim() !== '' && Number.isFinite(n) ? n : null;
+}
+
+// Faixas de idade/altura/peso e cores (valores normalizados no backend)
+function DescriptorFilter({ value, onChange, onClear }: {
+    value: Descriptors,
+    onChange: (value: Descriptors) => void,
+    onClear: () => void
+}) {
+    const { t } = useTranslation();
+    const set = (key: keyof Descriptors) => (e: React.ChangeEvent<HTMLInputElement | HTMLSelectElement>) =>
+        onChange({ ...value, [key]: e.target.value });
+    const range = (label: string, min: keyof Descriptors, max: keyof Descriptors) => (
+        <div className="flex items-center gap-2">
+            <span className="text-[9px] font-black uppercase text-muted tracking-widest">{label}</span>
+            {[min, max].map(key => (
+                <input
+                    key={key}
+                    type="number"
+                    min={0}
+                    value={value[key]}
+                    placeholder={t(key === min ? 'filters.min' : 'filters.max')}
+                    onChange={set(key)}
+                    className="h-8 w-20 px-3 rounded-full border border-white/10 bg-transparent text-[11px] font-mono focus:border-accent-amber/50 transition-all outline-none"
+                />
+            ))}
+        </div>
+    );
+    const colors = (label: string, key: 'eye_color' | 'hair_color', options: string[]) => (
+        <div className="relative group flex items-center gap-2">
+            <span className="text-[9px] font-black uppercase text-muted tracking-widest">{label}</span>
+            <select
+                value={value[key]}
+                onChange={set(key)}
+                className="appearance-none h-8 pl-4 pr-10 rounded-full border border-white/10 bg-transparent text-[10px] font-black tracking-widest hover:bg-white/5 transition-all outline-none cursor-pointer"
+            >
+                <option value="" className="bg-surface">{t('filters.any')}</option>
+                {options.map(color => <option key={color} value={color} className="bg-surface">{t(`colors.${color}`)}</option>)}
+            </select>
+            <ChevronDown className="absolute right-4 top-1/2 -translate-y-1/2 w-3 h-3 text-muted pointer-events-none" />
+        </div>
+    );
+    return (
+        <div className="px-8 py-4 flex flex-wrap items-center gap-6 border-b border-white/5 bg-white/[0.01]">
+            {range(t('filters.age'), 'age_min', 'age_max')}
+            {range(t('filters.height_cm'), 'height_min', 'height_max')}
+            {range(t('filters.weight_kg'), 'weight_min', 'weight_max')}
+            {colors(t('filters.eyes'), 'eye_color', EYE_COLORS)}
+            {colors(t('filters.hair'), 'hair_color', HAIR_COLORS)}
+            <button onClick={onClear} className="text-[10px] font-black tracking-widest text-muted hover:text-white transition-all">
+                {t('filters.clear')}
+            </button>
+        </div>
+    );
+}
+
+type LocationLevel = 'type' | 'country' | 'state' | 'city';
+
+// Valores de `locations` para o autocomplete, restritos aos níveis acima já escolhidos
+function useLocationValues(level: LocationLevel, prefix: string, parents: { loc_type?: string, country?: string, state?: string }) {
+    const [values, setValues] = useState<FacetCount[]>([]);
+    const { loc_type, country, state } = parents;
+    useEffect(() => {
+        if (!(window as any).__TAURI_INTERNALS__) return;
+        const timeout = setTimeout(() => {
+            invoke<FacetCount[]>('list_location_values', {
+                level,
+                prefix: prefix || null,
+                loc_type: loc_type || null,
+                country: country || null,
+                state: state || null
+            }).then(setValues).catch(() => setValues([]));
+        }, 200);
+        return () => clearTimeout(timeout);
+    }, [level, prefix, loc_type, country, state]);
+    return values;
+}
+
+// Filtro por local (tipo, país, estado, cidade) sobre a tabela `locations`
+function LocationFilter({ type, country, state, city, onType, onCountry, onState, onCity }: {
+    type: string, country: string, state: string, city: string,
+    onType: (value: string) => void,
+    onCountry: (value: string) => void,
+    onState: (value: string) => void,
+    onCity: (value: string) => void
+}) {
+    const { t } = useTranslation();
+    const types = useLocationValues('type', '', {});
+    const countries = useLocationValues('country', country, { loc_type: type });
+    const states = useLocationValues('state', state, { loc_type: type, country });
+    const cities = useLocationValues('city', city, { loc_type: type, country, state });
+    const input = (level: Exclude<LocationLevel, 'type'>, value: string, onChange: (value: string) => void, options: FacetCount[]) => (
+        <>
+            <input
+                type="search"
+                list={`location-${level}`}
+                value={value}
+                placeholder={t(`filters.location_${level}`)}
+                onChange={(e) => onChange(e.target.value)}
+                className="h-9 w-32 px-4 rounded-full border border-white/10 bg-transparent text-[10px] font-black tracking-widest hover:bg-white/5 focus:border-accent-amber/50 transition-all outline-none"
+            />
+            <datalist id={`location-${level}`}>
+                {options.map(({ value, count }) => <option key={value} value={value}>{count.toLocaleString()}</option>)}
+            </datalist>
+        </>
+    );
+    return (
+        <div className="flex items-center gap-2" title={t('filters.location_hint')}>
+            <MapPin className="w-3.5 h-3.5 text-muted" />
+            <div className="relative group">
+                <select
+                    value={type}
+                    onChange={(e) => onType(e.target.value)}
+                    className="appearance-none h-9 pl-4 pr-10 rounded-full border border-white/10 bg-transparent text-[10px] font-black tracking-widest hover:bg-white/5 transition-all outline-none cursor-pointer"
+                >
+                    <option value="" className="bg-surface">{t('filters.location_type_all')}</option>
+                    {types.map(({ value }) => (
+                        <option key={value} value={value} className="bg-surface">{t(`filters.location_types.${value}`, { defaultValue: value })}</option>
+                    ))}
+                </select>
+                <ChevronDown className="absolute right-4 top-1/2 -translate-y-1/2 w-3 h-3 text-muted pointer-events-none" />
+            </div>
+            {input('country', country, onCountry, countries)}
+            {input('state', state, onState, states)}
+            {input('city', city, onCity, cities)}
+        </div>
+    );
+}
+
+// Linha do tempo de ingestão por fonte, completude dos campos e registros sem atualização
+function IngestionModal({ onClose }: { onClose: () => void }) {
+    const { t } = useTranslation();
+    const [staleDays, setStaleDays] = useState(30);
+    const [days, setDays] = useState(90);
+    const [health, setHealth] = useState<IngestionStats | null>(null);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        invoke<IngestionStats>('get_ingestion_stats', { days, stale_days: staleDays })
+            .then(setHealth)
+            .catch(err => setError(errorMessage(err)));
+    }, [days, staleDays]);
+
+    const colors = new Map(health?.sources.map((s, n) => [s.source, SOURCE_COLORS[n % SOURCE_COLORS.length]]));
+    const byDay = new Map<string, Array<{ source: string; count: number }>>();
+    health?.timeline.forEach(p => byDay.set(p.day, [...(byDay.get(p.day) || []), p]));
+    const dayTotals = [...byDay.values()].map(points => points.reduce((sum, p) => sum + p.count, 0));
+    const peak = Math.max(1, ...dayTotals);
+
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <div className="glass-panel rounded-2xl w-full max-w-6xl max-h-full overflow-y-auto p-8 flex flex-col gap-8" onClick={e => e.stopPropagation()}>
+                <div className="flex items-center justify-between">
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('health.title')}</h2>
+                    <div className="flex items-center gap-4 text-[10px] font-black tracking-widest text-muted">
+                        <label className="flex items-center gap-2">
+                            {t('health.window')}
+                            <select value={days} onChange={e => setDays(Number(e.target.value))} className="ghost-select">
+                                {[30, 90, 365].map(n => <option key={n} value={n} className="bg-surface">{t('health.days', { count: n })}</option>)}
+                            </select>
+                        </label>
+                        <label className="flex items-center gap-2">
+                            {t('health.stale_after')}
+                            <input
+                                type="number"
+                                min={1}
+                                value={staleDays}
+                                onChange={e => setStaleDays(Math.max(1, Number(e.target.value) || 1))}
+                                className="w-16 h-7 px-2 rounded border border-white/10 bg-transparent text-white"
+                            />
+                        </label>
+                        <button onClick={onClose} className="text-muted hover:text-white"><X className="w-5 h-5" /></button>
+                    </div>
+                </div>
+
+                {error && <p className="text-[11px] text-red-400">{error}</p>}
+                {!health && !error && <div className="w-6 h-6 border-2 border-accent-amber border-t-transparent rounded-full animate-spin" />}
+                {health && (
+                    <>
+                        {/* LINHA DO TEMPO */}
+                        <section className="flex flex-col gap-3">
+                            <h3 className="text-[9px] font-black uppercase text-muted tracking-widest">{t('health.timeline')}</h3>
+                            {byDay.size === 0 ? (
+                                <p className="text-[11px] text-muted">{t('health.no_ingestion', { count: health.days })}</p>
+                            ) : (
+                                <div className="flex items-end gap-[2px] h-32">
+                                    {[...byDay.entries()].map(([day, points], n) => (
+                                        <div
+                                            key={day}
+                                            className="flex-1 min-w-[3px] flex flex-col-reverse"
+                                            style={{ height: `${(dayTotals[n] / peak) * 100}%` }}
+                                            title={`${day}\n${points.map(p => `${p.source}: ${p.count}`).join('\n')}`}
+                                        >
+                                            {points.map(p => (
+                                                <div key={p.source} className={colors.get(p.source)} style={{ height: `${(p.count / dayTotals[n]) * 100}%` }} />
+                                            ))}
+                                        </div>
+                                    ))}
+                                </div>
+                            )}
+                        </section>
+
+                        {/* COMPLETUDE E REGISTROS PARADOS POR FONTE */}
+                        <section className="flex flex-col gap-3">
+                            <h3 className="text-[9px] font-black uppercase text-muted tracking-widest">
+                                {t('health.sources')} — {t('health.stale_total', { count: health.stale, days: health.stale_days })}
+                            </h3>
+                            <table className="w-full text-[11px]">
+                                <thead>
+                                    <tr className="text-[9px] font-black uppercase text-muted tracking-widest text-left">
+                                        <th className="py-2">{t('health.source')}</th>
+                                        <th className="py-2">{t('health.records')}</th>
+                                        {(['images', 'birth_date', 'description', 'locations'] as const).map(field => (
+                                            <th key={field} className="py-2">{t(`health.fields.${field}`)}</th>
+                                        ))}
+                                        <th className="py-2">{t('health.stale')}</th>
+                                        <th className="py-2">{t('health.last_refresh')}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {[{ source: t('health.all'), total: totalRecords(health), stale: health.stale, last_refresh: null, completeness: health.completeness }, ...health.sources].map((s, n) => (
+                                        <tr key={s.source} className={cn("border-t border-white/5", n === 0 && "font-black")}>
+                                            <td className="py-2 flex items-center gap-2">
+                                                {n > 0 && <span className={cn("w-2 h-2 rounded-full", colors.get(s.source))} />}
+                                                {s.source}
+                                            </td>
+                                            <td className="py-2 font-mono">{s.total.toLocaleString()}</td>
+                                            {(['images', 'birth_date', 'description', 'locations'] as const).map(field => (
+                                                <td key={field} className="py-2 pr-4">
+                                                    <div className="h-1.5 bg-white/5 rounded-full overflow-hidden">
+                                                        <div className="h-full bg-accent-emerald" style={{ width: `${s.completeness[field]}%` }} />
+                                                    </div>
+                                                    <span className="text-[9px] font-mono text-muted">{s.completeness[field]}%</span>
+                                                </td>
+                                            ))}
+                                            <td className={cn("py-2 font-mono", s.stale > 0 ? "text-red-400" : "text-muted")}>{s.stale.toLocaleString()}</td>
+                                            <td className="py-2 font-mono text-muted">{s.last_refresh || '—'}</td>
+                                        </tr>
+                                    ))}
+                                </tbody>
+                            </table>
+                        </section>
+
+                        {/* TOP CRIMES E NACIONALIDADES */}
+                        <section className="grid grid-cols-2 gap-8">
+                            {([['top_crimes', health.top_crimes], ['top_nationalities', health.top_nationalities]] as const).map(([key, rows]) => (
+                                <div key={key} className="flex flex-col gap-2">
+                                    <h3 className="text-[9px] font-black uppercase text-muted tracking-widest">{t(`health.${key}`)}</h3>
+                                    {rows.map(row => (
+                                        <div key={row.value} className="flex justify-between text-[11px] border-b border-white/5 py-1">
+                                            <span className="truncate">{row.value}</span>
+                                            <span className="font-mono text-muted">{row.count.toLocaleString()}</span>
+                                        </div>
+                                    ))}
+                                </div>
+                            ))}
+                        </section>
+                    </>
+                )}
+            </div>
+        </div>
+    );
+}
+
+// Relatório da auditoria por categoria, com os ids afetados e exportação JSON/CSV
+function AuditModal({ onClose, onSelect }: { onClose: () => void, onSelect: (id: string) => void }) {
+    const { t } = useTranslation();
+    const [report, setReport] = useState<AuditReport | null>(null);
+    const [open, setOpen] = useState<string | null>(null);
+    const [message, setMessage] = useState<string | null>(null);
+
+    useEffect(() => {
+        invoke<AuditReport>('run_audit').then(setReport).catch(err => setMessage(errorMessage(err)));
+    }, []);
+
+    async function exportReport(format: 'json' | 'csv') {
+        try {
+            const path = await invoke<string | null>('export_audit', { format });
+            if (path) setMessage(t('audit.saved', { path }));
+        } catch (err) {
+            setMessage(errorMessage(err));
+        }
+    }
+
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <div className="glass-panel rounded-2xl w-full max-w-3xl max-h-full overflow-y-auto p-8 flex flex-col gap-6" onClick={e => e.stopPropagation()}>
+                <div className="flex items-center justify-between">
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('audit.title')}</h2>
+                    <div className="flex items-center gap-3">
+                        <FilterButton active={false} onClick={() => exportReport('json')}>JSON</FilterButton>
+                        <FilterButton active={false} onClick={() => exportReport('csv')}>CSV</FilterButton>
+                        <button onClick={onClose} className="text-muted hover:text-white"><X className="w-5 h-5" /></button>
+                    </div>
+                </div>
+
+                {message && <p className="text-[11px] text-muted">{message}</p>}
+                {!report && !message && <div className="w-6 h-6 border-2 border-accent-amber border-t-transparent rounded-full animate-spin" />}
+                {report && (
+                    <>
+                        <p className="text-[10px] font-mono text-muted">
+                            {t('audit.summary', { total: report.total, count: report.individuals })} · {report.generated_at}
+                        </p>
+                        {report.categories.map(category => (
+                            <div key={category.kind} className="border border-white/10 rounded-lg">
+                                <button
+                                    onClick={() => setOpen(open === category.kind ? null : category.kind)}
+                                    disabled={category.count === 0}
+                                    className="w-full flex items-center justify-between px-4 py-3 text-[11px]"
+                                >
+                                    <span>{t(`audit.kinds.${category.kind}`)}</span>
+                                    <span className={cn("font-mono font-black", category.count > 0 ? "text-red-400" : "text-accent-emerald")}>
+                                        {category.count.toLocaleString()}
+                                    </span>
+                                </button>
+                                {open === category.kind && (
+                                    <div className="border-t border-white/5 max-h-64 overflow-y-auto">
+                                        {category.issues.map((issue, n) => (
+                                            <div key={n} className="flex items-center gap-4 px-4 py-1 text-[10px] font-mono">
+                                                {issue.table === 'individuals' || issue.table === 'individual_images' ? (
+                                                    <button onClick={() => onSelect(issue.id)} className="text-accent-amber hover:underline">{issue.id}</button>
+                                                ) : (
+                                                    <span className="text-accent-amber">{issue.id || '∅'}</span>
+                                                )}
+                                                <span className="text-muted truncate">{issue.detail}</span>
+                                            </div>
+                                        ))}
+                                    </div>
+                                )}
+                            </div>
+                        ))}
+                    </>
+                )}
+            </div>
+        </div>
+    );
+}
+
+// Quem viu o quê: eventos filtráveis, verificação da cadeia de hashes e exportação JSON/CSV
+function AccessLogModal({ onClose }: { onClose: () => void }) {
+    const { t } = useTranslation();
+    const [chain, setChain] = useState<ChainReport | null>(null);
+    const [events, setEvents] = useState<AuditEvent[] | null>(null);
+    const [actor, setActor] = useState('');
+    const [action, setAction] = useState('');
+    const [target, setTarget] = useState('');
+    const [message, setMessage] = useState<string | null>(null);
+
+    const filter = { actor: actor || null, action: action || null, target: target || null };
+
+    const verify = useCallback(() => {
+        invoke<ChainReport>('verify_audit_log').then(setChain).catch(err => setMessage(errorMessage(err)));
+    }, []);
+    useEffect(verify, [verify]);
+
+    useEffect(() => {
+        invoke<AuditEvent[]>('get_audit_log', { filter: { actor: actor || null, action: action || null, target: target || null } })
+            .then(setEvents)
+            .catch(err => setMessage(errorMessage(err)));
+    }, [actor, action, target]);
+
+    async function exportLog(format: 'json' | 'csv') {
+        try {
+            const path = await invoke<string | null>('export_audit_log', { filter, format });
+            if (path) setMessage(t('access_log.saved', { path }));
+        } catch (err) {
+            setMessage(errorMessage(err));
+        }
+    }
+
+    const field = (value: string, onChange: (value: string) => void, placeholder: string) => (
+        <input
+            type="search"
+            value={value}
+            placeholder={placeholder}
+            onChange={(e) => onChange(e.target.value)}
+            className="h-9 flex-1 px-4 rounded-full border border-white/10 bg-transparent text-[10px] font-black tracking-widest hover:bg-white/5 focus:border-accent-amber/50 transition-all outline-none"
+        />
+    );
+
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <div className="glass-panel rounded-2xl w-full max-w-4xl max-h-full overflow-y-auto p-8 flex flex-col gap-6" onClick={e => e.stopPropagation()}>
+                <div className="flex items-center justify-between">
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('access_log.title')}</h2>
+                    <div className="flex items-center gap-3">
+                        <FilterButton active={false} onClick={verify}>{t('access_log.verify')}</FilterButton>
+                        <FilterButton active={false} onClick={() => exportLog('json')}>JSON</FilterButton>
+                        <FilterButton active={false} onClick={() => exportLog('csv')}>CSV</FilterButton>
+                        <button onClick={onClose} className="text-muted hover:text-white"><X className="w-5 h-5" /></button>
+                    </div>
+                </div>
+
+                {chain && (
+                    <div className={cn("border rounded-lg px-4 py-3 text-[10px] font-mono flex flex-col gap-1", chain.valid ? "border-accent-emerald/30" : "border-red-400/50")}>
+                        <span className={cn("font-black tracking-widest", chain.valid ? "text-accent-emerald" : "text-red-400")}>
+                            {t(chain.valid ? 'access_log.valid' : 'access_log.broken')}
+                        </span>
+                        <span className="text-muted">{t('access_log.summary', { count: chain.events, at: chain.verified_at })}</span>
+                        {chain.head_hash && <span className="text-muted break-all">{t('access_log.head', { hash: chain.head_hash })}</span>}
+                        {chain.breaks.map((b, n) => (
+                            <span key={n} className="text-red-400">#{b.id} {t(`access_log.breaks.${b.kind}`)} — {b.detail}</span>
+                        ))}
+                    </div>
+                )}
+
+                <div className="flex items-center gap-3">
+                    {field(actor, setActor, t('access_log.actor'))}
+                    {field(action, setAction, t('access_log.action'))}
+                    {field(target, setTarget, t('access_log.target'))}
+                </div>
+
+                {message && <p className="text-[11px] text-muted">{message}</p>}
+                {!events && !message && <div className="w-6 h-6 border-2 border-accent-amber border-t-transparent rounded-full animate-spin" />}
+                {events && events.length === 0 && <p className="text-[11px] text-muted">{t('access_log.empty')}</p>}
+                {events && events.length > 0 && (
+                    <div className="border border-white/10 rounded-lg max-h-96 overflow-y-auto">
+                        {events.map(e => (
+                            <div key={e.id} title={e.hash || ''} className="flex items-center gap-4 px-4 py-1 text-[10px] font-mono border-b border-white/5">
+                                <span className="text-muted">#{e.id}</span>
+                                <span className="text-muted whitespace-nowrap">{e.at}</span>
+                                <span className="text-white">{e.actor}</span>
+                                <span className="text-accent-amber">{e.action}</span>
+                                {e.target && <span>{e.target}</span>}
+                                <span className="text-muted truncate">{JSON.stringify(e.detail)}</span>
+                            </div>
+                        ))}
+                    </div>
+                )}
+            </div>
+        </div>
+    );
+}
+
+function totalRecords(health: IngestionStats) {
+    return health.sources.reduce((sum, s) => sum + s.total, 0);
+}
+
+const AGREEMENT_STYLE: Record<Agreement, string> = {
+    exact: 'text-accent-emerald',
+    partial: 'text-accent-amber',
+    conflict: 'text-red-400',
+    unknown: 'text-muted',
+};
+
+// Grupos de prováveis duplicatas e comparação lado a lado de um par, com a decisão do analista
+function DuplicatesModal({ onClose, onDecided }: { onClose: () => void, onDecided: () => void }) {
     const { t } = useTranslation();
-    const [imgUrl, setImgUrl] = useState<string | null>(null);
+    const { can } = useSession();
+    const [clusters, setClusters] = useState<DuplicateCluster[] | null>(null);
+    const [pair, setPair] = useState<[string, string] | null>(null);
+    const [comparison, setComparison] = useState<Comparison | null>(null);
+    const [note, setNote] = useState('');
+    const [error, setError] = useState<string | null>(null);
+
+    const loadClusters = useCallback(() => {
+        invoke<DuplicateCluster[]>('find_duplicates', {}).then(setClusters).catch(err => setError(errorMessage(err)));
+    }, []);
+    useEffect(loadClusters, [loadClusters]);
 
     useEffect(() => {
-        if (person.img_path) {
-            invoke<string>('get_image_base64', { imgPath: person.img_path })
-                .then(setImgUrl)
-                .catch(() => setImgUrl(null));
+        if (!pair) return;
+        setComparison(null);
+        invoke<Comparison>('compare_records', { id_a: pair[0], id_b: pair[1] })
+            .then(c => { setComparison(c); setNote(c.decision?.note || ''); })
+            .catch(err => setError(errorMessage(err)));
+    }, [pair]);
+
+    async function decide(decision: Decision | null) {
+        if (!pair) return;
+        try {
+            const current = await invoke<LinkDecision | null>('set_link_decision', { id_a: pair[0], id_b: pair[1], decision, note: note || null });
+            setComparison(c => c && { ...c, decision: current });
+            loadClusters();
+            onDecided();
+        } catch (err) {
+            setError(errorMessage(err));
         }
-    }, [person.img_path]);
+    }
+
+    const name = (cluster: DuplicateCluster, id: string) => cluster.members.find(m => m.id === id);
+    return (
+        <div className="fixed inset-0 z-[90] bg-black/80 backdrop-blur flex items-center justify-center p-8" onClick={onClose}>
+            <div className="glass-panel rounded-2xl w-full max-w-6xl h-full flex overflow-hidden" onClick={e => e.stopPropagation()}>
+                <aside className="w-96 shrink-0 border-r border-white/5 overflow-y-auto p-6 flex flex-col gap-4">
+                    <h2 className="text-sm font-black tracking-widest text-accent-amber">{t('linkage.duplicates')}</h2>
+                    {error && <p className="text-[11px] text-red-400">{error}</p>}
+                    {clusters === null && <div className="w-6 h-6 border-2 border-accent-amber border-t-transparent rounded-full animate-spin" />}
+                    {clusters?.length === 0 && <p className="text-[11px] text-muted">{t('linkage.none')}</p>}
+                    {clusters?.map((cluster, n) => (
+                        <div key={n} className="border border-white/10 rounded-lg p-3 flex flex-col gap-2">
+                            <div className="flex justify-between text-[10px] font-mono text-muted">
+                                <span>{t('linkage.records', { count: cluster.members.length })}</span>
+                                <span className="text-accent-amber">{Math.round(cluster.score * 100)}%</span>
+                            </div>
+                            {cluster.pairs.slice(0, 5).map(p => (
+                                <button
+                                    key={`${p.id_a}-${p.id_b}`}
+                                    onClick={() => setPair([p.id_a, p.id_b])}
+                                    className={cn(
+                                        "text-left text-[11px] px-2 py-1 rounded hover:bg-white/5 transition-all",
+                                        pair?.[0] === p.id_a && pair?.[1] === p.id_b && "bg-accent-amber/20"
+                                    )}
+                                >
+                                    <div className="truncate">{name(cluster, p.id_a)?.name} <span className="text-muted">({name(cluster, p.id_a)?.source})</span></div>
+                                    <div className="truncate">{name(cluster, p.id_b)?.name} <span className="text-muted">({name(cluster, p.id_b)?.source})</span></div>
+                                </button>
+                            ))}
+                        </div>
+                    ))}
+                </aside>
+
+                <section className="flex-1 overflow-y-auto p-8">
+                    <button onClick={onClose} className="float-right text-muted hover:text-white"><X className="w-5 h-5" /></button>
+                    {!pair && <p className="text-[11px] text-muted">{t('linkage.pick_pair')}</p>}
+                    {comparison && (
+                        <div className="flex flex-col gap-6">
+                            <p className="text-[10px] font-mono text-muted">
+                                {t('linkage.score', { score: Math.round(comparison.evidence.score * 100) })}
+                                {comparison.evidence.alias_match && ` · ${t('linkage.alias_match')}`}
+                            </p>
+                            <table className="w-full text-[11px]">
+                                <thead>
+                                    <tr className="text-[9px] font-black uppercase text-muted tracking-widest">
+                                        <th className="text-left py-2 w-40" />
+                                        <th className="text-left py-2">{comparison.left.id}</th>
+                                        <th className="text-left py-2">{comparison.right.id}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {comparison.fields.map(f => (
+                                        <tr key={f.field} className="border-t border-white/5">
+                                            <td className="py-2 text-[9px] font-black uppercase text-muted tracking-widest">{t(`linkage.fields.${f.field}`)}</td>
+                                            <td className={cn("py-2", AGREEMENT_STYLE[f.agreement])}>{f.left ?? '—'}</td>
+                                            <td className={cn("py-2", AGREEMENT_STYLE[f.agreement])}>{f.right ?? '—'}</td>
+                                        </tr>
+                                    ))}
+                                </tbody>
+                            </table>
+
+                            {can('reviewer') && (
+                                <>
+                                    <input
+                                        type="text"
+                                        value={note}
+                                        placeholder={t('linkage.note')}
+                                        onChange={e => setNote(e.target.value)}
+                                        className="h-9 px-4 rounded-full border border-white/10 bg-transparent text-[11px] focus:border-accent-amber/50 outline-none"
+                                    />
+                                    <div className="flex items-center gap-3">
+                                        <FilterButton active={comparison.decision?.decision === 'same'} onClick={() => decide('same')}>{t('linkage.same')}</FilterButton>
+                                        <FilterButton active={comparison.decision?.decision === 'different'} onClick={() => decide('different')}>{t('linkage.different')}</FilterButton>
+                                        {comparison.decision && (
+                                            <button onClick={() => decide(null)} className="text-[10px] font-black tracking-widest text-muted hover:text-white">
+                                                {t('linkage.undo')}
+                                            </button>
+                                        )}
+                                        {comparison.decision && (
+                                            <span className="ml-auto text-[10px] font-mono text-muted">{comparison.decision.decided_at}</span>
+                                        )}
+                                    </div>
+                                </>
+                            )}
+                        </div>
+                    )}
+                </section>
+            </div>
+        </div>
+    );
+}
+
+// Trecho da busca FTS: termos entre \u0002 e \u0003 (search_index.rs)
+function Highlighted({ text }: { text: string }) {
+    const parts = text.split(/(\u0002[^\u0003]*\u0003)/);
+    return (
+        <>
+            {parts.map((part, i) => part.startsWith('\u0002')
+                ? <mark key={i} className="bg-accent-amber/30 text-white rounded-sm px-0.5">{part.slice(1, -1)}</mark>
+                : <span key={i}>{part}</span>
+            )}
+        </>
+    );
+}
+
+function IndividualCard({ person, onClick }: { person: Individual, onClick: () => void }) {
+    const { t } = useTranslation();
+    const [imgFailed, setImgFailed] = useState(false);
+    const imgUrl = person.img_path && !imgFailed ? thumbUrl(person.id, 0, 'medium') : null;
 
     return (
         <motion.div
@@ -360,7 +1802,7 @@ function IndividualCard({ person, onClick }: { person: Individual, onClick: () =
         >
             <div className="aspect-[3/4] relative bg-neutral-900 overflow-hidden">
                 {imgUrl ? (
-                    <img src={imgUrl} className="w-full h-full object-cover group-hover:scale-105 transition-transform duration-500" alt={person.name} />
+                    <img src={imgUrl} loading="lazy" onError={() => setImgFailed(true)} className="w-full h-full object-cover group-hover:scale-105 transition-transform duration-500" alt={person.name} />
                 ) : (
                     <div className="w-full h-full flex items-center justify-center opacity-30 bg-neutral-950">
                         <User className="w-20 h-20" />
@@ -391,7 +1833,20 @@ function IndividualCard({ person, onClick }: { person: Individual, onClick: () =
                     <h3 className="text-sm font-black uppercase tracking-tight line-clamp-2 leading-tight drop-shadow-lg">{person.name}</h3>
                     <p className="text-[10px] text-muted font-mono mt-2 flex items-center gap-1">
                         <Info className="w-3 h-3" /> {person.source}
+                        {person.match_score != null && (
+                            <span className="ml-auto text-accent-amber">{Math.round(person.match_score * 100)}%</span>
+                        )}
                     </p>
+                    {person.linked.length > 0 && (
+                        <p className="text-[10px] text-accent-amber font-mono mt-1 flex items-center gap-1" title={person.linked.map(l => `${l.source}: ${l.name}`).join('\n')}>
+                            <Link2 className="w-3 h-3" /> {t('linkage.linked_count', { count: person.linked.length, sources: [...new Set(person.linked.map(l => l.source))].join(', ') })}
+                        </p>
+                    )}
+                    {person.snippet && (
+                        <p className="text-[10px] text-white/60 mt-1 line-clamp-2 leading-snug">
+                            <Highlighted text={person.snippet} />
+                        </p>
+                    )}
                 </div>
             </div>
         </motion.div>
@@ -400,7 +1855,7 @@ function IndividualCard({ person, onClick }: { person: Individual, onClick: () =
 
 function DossierModal({ detail, onClose }: { detail: IndividualDetail, onClose: () => void }) {
     const { t, i18n } = useTranslation();
-    const [activeImg, setActiveImg] = useState<string | null>(null);
+    const [activeImg, setActiveImg] = useState<string | null>(detail.img_path ? imageUrl(detail.id) : null);
     const currentLang = i18n.language?.substring(0, 2) || 'pt';
 
     // Estado para conteúdo traduzido
@@ -412,10 +1867,8 @@ function DossierModal({ detail, onClose }: { detail: IndividualDetail, onClose:
     const [translatedLocations, setTranslatedLocations] = useState<Location[] | null>(null);
 
     useEffect(() => {
-        if (detail.img_path) {
-            invoke<string>('get_image_base64', { imgPath: detail.img_path }).then(setActiveImg);
-        }
-    }, [detail.img_path]);
+        setActiveImg(detail.img_path ? imageUrl(detail.id) : null);
+    }, [detail.id, detail.img_path]);
 
     // Traduz texto livre quando o idioma muda
     useEffect(() => {
@@ -512,7 +1965,7 @@ function DossierModal({ detail, onClose }: { detail: IndividualDetail, onClose:
                         <div className="aspect-[3/4] bg-black rounded-lg overflow-hidden border border-white/10 relative">
                             <div className="absolute inset-0 scanline pointer-events-none" />
                             {activeImg ? (
-                                <img src={activeImg} className="w-full h-full object-cover" />
+                                <img src={activeImg} onError={() => setActiveImg(null)} className="w-full h-full object-cover" />
                             ) : (
                                 <div className="w-full h-full flex items-center justify-center opacity-10"><User className="w-32 h-32" /></div>
                             )}
@@ -523,8 +1976,8 @@ function DossierModal({ detail, onClose }: { detail: IndividualDetail, onClose:
                             <h4 className="text-[10px] font-black text-muted tracking-widest uppercase mb-4">{t('dossier.forensic_gallery')}</h4>
                             <div className="grid grid-cols-4 gap-2">
                                 {detail.images.map((img, i) => (
-                                    <GalleryThumb key={i} path={img.img_path} active={img.img_path === activeImg} onClick={() => {
-                                        invoke<string>('get_image_base64', { imgPath: img.img_path }).then(setActiveImg);
+                                    <GalleryThumb key={i} url={thumbUrl(detail.id, i + 1, 'small')} active={imageUrl(detail.id, i + 1) === activeImg} onClick={() => {
+                                        setActiveImg(imageUrl(detail.id, i + 1));
                                     }} />
                                 ))}
                             </div>
@@ -552,13 +2005,16 @@ function DossierModal({ detail, onClose }: { detail: IndividualDetail, onClose:
 
                         <div className="grid grid-cols-2 lg:grid-cols-4 gap-y-8 gap-x-12 mt-12 mb-16">
                             <DetailItem label={t('dossier.fields.gender')} value={dbT(detail.sex)} />
-                            <DetailItem label={t('dossier.fields.birth')} value={translatedBirth || detail.birth_date} />
+                            <DetailItem
+                                label={t('dossier.fields.birth')}
+                                value={detail.birth_date ? `${translatedBirth || detail.birth_date}${detail.age != null ? ` (${t('dossier.fields.age', { age: detail.age })})` : ''}` : null}
+                            />
                             <DetailItem label={t('dossier.fields.nationality')} value={parseJSON(detail.nationalities).map(n => dbT(n) || n).join(", ")} />
                             <DetailItem label={t('dossier.fields.occupation')} value={parseJSON(detail.occupation).map(o => dbT(o) || o).join(", ")} />
-                            <DetailItem label={t('dossier.fields.height')} value={detail.height_cm ? `${detail.height_cm} cm` : null} />
-                            <DetailItem label={t('dossier.fields.weight')} value={detail.weight_kg ? `${detail.weight_kg} kg` : null} />
-                            <DetailItem label={t('dossier.fields.eyes')} value={dbT(detail.eye_color)} />
-                            <DetailItem label={t('dossier.fields.hair')} value={dbT(detail.hair_color)} />
+                            <DetailItem label={t('dossier.fields.height')} value={detail.normalized.height_cm ? `${detail.normalized.height_cm} cm` : null} />
+                            <DetailItem label={t('dossier.fields.weight')} value={detail.normalized.weight_kg ? `${detail.normalized.weight_kg} kg` : null} />
+                            <DetailItem label={t('dossier.fields.eyes')} value={detail.normalized.eye_color ? t(`colors.${detail.normalized.eye_color}`) : dbT(detail.eye_color)} />
+                            <DetailItem label={t('dossier.fields.hair')} value={detail.normalized.hair_color ? t(`colors.${detail.normalized.hair_color}`) : dbT(detail.hair_color)} />
                         </div>
 
                         {detail.reward && (
@@ -730,11 +2186,8 @@ function parseBold(text: string) {
     });
 }
 
-function GalleryThumb({ path, active, onClick }: { path: string, active: boolean, onClick: () => void }) {
-    const [url, setUrl] = useState<string | null>(null);
-    useEffect(() => {
-        invoke<string>('get_image_base64', { imgPath: path }).then(setUrl);
-    }, [path]);
+function GalleryThumb({ url, active, onClick }: { url: string, active: boolean, onClick: () => void }) {
+    const [failed, setFailed] = useState(false);
 
     return (
         <div
@@ -744,7 +2197,7 @@ function GalleryThumb({ path, active, onClick }: { path: string, active: boolean
                 active ? "border-accent-amber scale-95" : "border-white/10 hover:border-white/30"
             )}
         >
-            {url && <img src={url} className="w-full h-full object-cover" />}
+            {!failed && <img src={url} loading="lazy" onError={() => setFailed(true)} className="w-full h-full object-cover" />}
         </div>
     );
 }
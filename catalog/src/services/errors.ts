@@ -0,0 +1,29 @@
+import i18n from '../i18n/config';
+
+/**
+ * Erro dos comandos Tauri (AppError no crate intelligence-db).
+ *
+ * `code` é estável (ex: 'NOT_FOUND', 'DATABASE_UNAVAILABLE') e serve para
+ * decidir o fluxo; `i18n_key` + `context` montam a mensagem traduzida.
+ */
+export interface AppError {
+    code: string;
+    i18n_key: string;
+    message: string;
+    context: Record<string, string>;
+}
+
+export function isAppError(err: unknown): err is AppError {
+    return typeof err === 'object' && err !== null && 'code' in err && 'i18n_key' in err;
+}
+
+/**
+ * Mensagem para exibir ao usuário, no idioma atual da interface.
+ * Sem tradução para o código, usa a mensagem do backend.
+ */
+export function errorMessage(err: unknown): string {
+    if (isAppError(err)) {
+        return i18n.t(err.i18n_key, { ...err.context, defaultValue: err.message });
+    }
+    return String(err);
+}
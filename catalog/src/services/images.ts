@@ -0,0 +1,19 @@
+/**
+ * URLs do protocolo `catalog://` (src-tauri/src/images.rs): o WebView lê a imagem
+ * direto do backend, com cache por ETag, sem base64 pelo IPC.
+ *
+ * `n` = 0 é a imagem principal; 1.. as imagens da galeria na ordem de `get_individual`.
+ * No Windows o WebView2 só aceita o esquema como `http://catalog.localhost`.
+ */
+const BASE = navigator.userAgent.includes('Windows') ? 'http://catalog.localhost/image' : 'catalog://image';
+
+export function imageUrl(id: string, n = 0): string {
+    return `${BASE}/${encodeURIComponent(id)}/${n}`;
+}
+
+export type ThumbSize = 'small' | 'medium' | 'large';
+
+/** Miniatura (160/320/640 px) da mesma imagem, gerada e guardada em cache pelo backend. */
+export function thumbUrl(id: string, n = 0, size: ThumbSize = 'medium'): string {
+    return `${BASE.replace(/image$/, 'thumb')}/${encodeURIComponent(id)}/${n}?size=${size}`;
+}
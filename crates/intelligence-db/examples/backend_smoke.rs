@@ -0,0 +1,44 @@
+// Executa as consultas dos apps contra um banco real, em qualquer backend:
+//
+//   cargo run -p intelligence-db --example backend_smoke -- sqlite intelligence/data/intelligence.db
+//   docker compose up -d db
+//   cargo run -p intelligence-db --example backend_smoke -- postgres "host=localhost user=ghost password=protocol dbname=intelligence"
+
+use intelligence_db::{backend::open_backend, repo, search, BackendConfig, SearchFilter};
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let kind = args.next().unwrap_or_else(|| "sqlite".into());
+    let target = args.next().unwrap_or_else(|| "intelligence/data/intelligence.db".into());
+
+    let cfg = match kind.as_str() {
+        "postgres" => BackendConfig::Postgres { url: target },
+        _ => BackendConfig::Sqlite { path: PathBuf::from(target) },
+    };
+    let db = open_backend(&cfg)?;
+    println!("[smoke] {}", db.describe());
+
+    let stats = repo::get_stats(db.as_ref())?;
+    println!("[smoke] get_stats: total={} wanted={} missing={} bio={}", stats.total, stats.wanted, stats.missing, stats.with_biometrics);
+
+    let page = search::search_individuals(db.as_ref(), &SearchFilter { limit: Some(5), ..Default::default() })?;
+    println!("[smoke] search_individuals: {} de {} resultados", page.items.len(), page.total);
+
+    if let Some(cursor) = &page.next_cursor {
+        let next = search::search_individuals(
+            db.as_ref(),
+            &SearchFilter { limit: Some(5), cursor: Some(cursor.clone()), ..Default::default() },
+        )?;
+        println!("[smoke] search_individuals (cursor): {} resultados", next.items.len());
+    }
+
+    if let Some(first) = page.items.first() {
+        let detail = repo::get_individual(db.as_ref(), &first.id)?;
+        println!("[smoke] get_individual({}): {} crimes, {} imagens", detail.id, detail.crimes.len(), detail.images.len());
+    }
+
+    let sightings = repo::recent_sightings(db.as_ref(), 5, 0, None)?;
+    println!("[smoke] recent_sightings: {} avistamentos", sightings.len());
+    Ok(())
+}
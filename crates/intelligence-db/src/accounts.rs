@@ -0,0 +1,396 @@
+// Contas locais dos operadores, no sidecar accounts.db: senha com argon2id (string
+// PHC, sal próprio por conta) e um perfil — viewer só consulta, reviewer também
+// revisa avistamentos e decide ligações entre registros, admin também configura o
+// app, gerencia contas, expurga evidências e supervisiona o log de auditoria.
+// A sessão fica só na memória do app (`Sessions`): cada comando Tauri exige o
+// perfil mínimo com `require`, e a sessão tranca sozinha depois de `idle` sem
+// atividade do operador (o polling da interface não conta como atividade).
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::sidecar;
+
+pub const ACCOUNTS_FILE: &str = "accounts.db";
+
+pub const MIN_PASSWORD_LEN:          usize = 8;
+pub const MAX_USERNAME_LEN:          usize = 64;
+pub const DEFAULT_IDLE_LOCK_MINUTES: u32 = 10;
+
+// Falhas seguidas de login do mesmo usuário: as primeiras são livres, depois cada
+// uma dobra a espera até a próxima tentativa (2 s, 4 s, ...) até o teto
+const LOGIN_FREE_FAILURES: u32 = 3;
+const LOGIN_MAX_WAIT:      Duration = Duration::from_secs(300);
+// Acima disso o mapa descarta usuários que já podem tentar de novo
+const LOGIN_TRACKED_USERS: usize = 1000;
+
+const MIGRATIONS: &[&str] = &[
+    // username sem distinção de maiúsculas: "Ana" e "ana" são a mesma conta
+    "CREATE TABLE accounts (
+         username      TEXT PRIMARY KEY COLLATE NOCASE,
+         password_hash TEXT NOT NULL,
+         role          TEXT NOT NULL CHECK (role IN ('viewer', 'reviewer', 'admin')),
+         disabled      INTEGER NOT NULL DEFAULT 0,
+         created_at    TEXT NOT NULL,
+         updated_at    TEXT NOT NULL,
+         last_login    TEXT
+     );",
+];
+
+// Ordem importa: cada perfil inclui os anteriores
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Viewer,
+    Reviewer,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer   => "viewer",
+            Role::Reviewer => "reviewer",
+            Role::Admin    => "admin",
+        }
+    }
+
+    // Valor desconhecido no arquivo vale o perfil mais restrito
+    fn parse(s: &str) -> Self {
+        match s {
+            "admin"    => Role::Admin,
+            "reviewer" => Role::Reviewer,
+            _          => Role::Viewer,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Account {
+    pub username:   String,
+    pub role:       Role,
+    pub disabled:   bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub last_login: Option<String>,
+}
+
+// Alteração feita por um admin; campos ausentes ficam como estão
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct AccountUpdate {
+    pub role:     Option<Role>,
+    pub disabled: Option<bool>,
+    pub password: Option<String>,
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+}
+
+fn hash_password(password: &str) -> Result<String> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(Error::InvalidInput("password".into(), format!("mínimo de {MIN_PASSWORD_LEN} caracteres")));
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| Error::InvalidInput("password".into(), e.to_string()))
+}
+
+fn check_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|h| Argon2::default().verify_password(password.as_bytes(), &h).is_ok())
+}
+
+// Usuário inexistente também paga um argon2: o tempo de resposta não revela quem existe
+fn dummy_hash() -> &'static str {
+    static DUMMY: OnceLock<String> = OnceLock::new();
+    DUMMY.get_or_init(|| hash_password("conta-inexistente").unwrap_or_default())
+}
+
+fn validate_username(username: &str) -> Result<String> {
+    let username = username.trim();
+    if username.is_empty() || username.chars().count() > MAX_USERNAME_LEN || username.chars().any(char::is_control) {
+        return Err(Error::InvalidInput("username".into(), format!("de 1 a {MAX_USERNAME_LEN} caracteres, sem controle")));
+    }
+    Ok(username.to_string())
+}
+
+fn open(path: &Path) -> Result<Connection> {
+    Ok(sidecar::open(path, MIGRATIONS)?)
+}
+
+const COLUMNS: &str = "username, role, disabled, created_at, updated_at, last_login";
+
+fn account_row(r: &rusqlite::Row) -> rusqlite::Result<Account> {
+    Ok(Account {
+        username:   r.get(0)?,
+        role:       Role::parse(&r.get::<_, String>(1)?),
+        disabled:   r.get(2)?,
+        created_at: r.get(3)?,
+        updated_at: r.get(4)?,
+        last_login: r.get(5)?,
+    })
+}
+
+fn find(conn: &Connection, username: &str) -> Result<Option<Account>> {
+    Ok(conn
+        .query_row(&format!("SELECT {COLUMNS} FROM accounts WHERE username = ?1"), [username], account_row)
+        .optional()?)
+}
+
+fn active_admins(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row("SELECT COUNT(*) FROM accounts WHERE role = 'admin' AND disabled = 0", [], |r| r.get(0))?)
+}
+
+fn insert(conn: &Connection, username: &str, password: &str, role: Role) -> Result<Account> {
+    let username = validate_username(username)?;
+    if find(conn, &username)?.is_some() {
+        return Err(Error::InvalidInput("username".into(), format!("{username} já existe")));
+    }
+    let hash = hash_password(password)?;
+    let at = now();
+    conn.execute(
+        "INSERT INTO accounts (username, password_hash, role, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?4)",
+        params![username, hash, role.as_str(), at],
+    )?;
+    find(conn, &username)?.ok_or(Error::NotFound(username))
+}
+
+// ─── Contas ───────────────────────────────────────────────────────────────────
+
+// Sem nenhuma conta o app pede a criação do primeiro admin
+pub fn is_empty(path: &Path) -> Result<bool> {
+    let conn = open(path)?;
+    let n: i64 = conn.query_row("SELECT COUNT(*) FROM accounts", [], |r| r.get(0))?;
+    Ok(n == 0)
+}
+
+pub fn list(path: &Path) -> Result<Vec<Account>> {
+    let conn = open(path)?;
+    let mut stmt = conn.prepare(&format!("SELECT {COLUMNS} FROM accounts ORDER BY username"))?;
+    let rows = stmt.query_map([], account_row)?.collect::<rusqlite::Result<_>>()?;
+    Ok(rows)
+}
+
+pub fn get(path: &Path, username: &str) -> Result<Account> {
+    find(&open(path)?, username)?.ok_or_else(|| Error::NotFound(username.to_string()))
+}
+
+// Só funciona com o arquivo vazio (primeiro uso); depois, contas só por um admin
+pub fn create_first_admin(path: &Path, username: &str, password: &str) -> Result<Account> {
+    let mut conn = open(path)?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let n: i64 = tx.query_row("SELECT COUNT(*) FROM accounts", [], |r| r.get(0))?;
+    if n > 0 {
+        return Err(Error::Forbidden(Role::Admin.as_str().into()));
+    }
+    let account = insert(&tx, username, password, Role::Admin)?;
+    tx.commit()?;
+    println!("[ACCOUNTS] Primeira conta admin criada: {}", account.username);
+    Ok(account)
+}
+
+pub fn create(path: &Path, username: &str, password: &str, role: Role) -> Result<Account> {
+    let mut conn = open(path)?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let account = insert(&tx, username, password, role)?;
+    tx.commit()?;
+    Ok(account)
+}
+
+// Nunca deixa o app sem admin ativo (rebaixar ou desativar o último)
+pub fn update(path: &Path, username: &str, change: &AccountUpdate) -> Result<Account> {
+    let mut conn = open(path)?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let current = find(&tx, username)?.ok_or_else(|| Error::NotFound(username.to_string()))?;
+    let role = change.role.unwrap_or(current.role);
+    let disabled = change.disabled.unwrap_or(current.disabled);
+    let was_admin = current.role == Role::Admin && !current.disabled;
+    if was_admin && (role != Role::Admin || disabled) && active_admins(&tx)? <= 1 {
+        return Err(Error::InvalidInput("role".into(), "última conta admin ativa".into()));
+    }
+    let at = now();
+    tx.execute(
+        "UPDATE accounts SET role = ?2, disabled = ?3, updated_at = ?4 WHERE username = ?1",
+        params![current.username, role.as_str(), disabled, at],
+    )?;
+    if let Some(password) = &change.password {
+        tx.execute(
+            "UPDATE accounts SET password_hash = ?2 WHERE username = ?1",
+            params![current.username, hash_password(password)?],
+        )?;
+    }
+    let account = find(&tx, &current.username)?.ok_or_else(|| Error::NotFound(username.to_string()))?;
+    tx.commit()?;
+    Ok(account)
+}
+
+// Troca feita pelo próprio operador: exige a senha atual
+pub fn change_password(path: &Path, username: &str, current: &str, new: &str) -> Result<()> {
+    authenticate(path, username, current)?;
+    update(path, username, &AccountUpdate { password: Some(new.to_string()), ..Default::default() })?;
+    Ok(())
+}
+
+// Conta inexistente, desativada ou senha errada dão o mesmo erro
+pub fn authenticate(path: &Path, username: &str, password: &str) -> Result<Account> {
+    let conn = open(path)?;
+    let stored: Option<(String, bool)> = conn
+        .query_row(
+            "SELECT password_hash, disabled FROM accounts WHERE username = ?1",
+            [username.trim()],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()?;
+    let Some((hash, disabled)) = stored else {
+        check_password(dummy_hash(), password);
+        return Err(Error::InvalidCredentials);
+    };
+    if !check_password(&hash, password) || disabled {
+        return Err(Error::InvalidCredentials);
+    }
+    conn.execute("UPDATE accounts SET last_login = ?2 WHERE username = ?1", params![username.trim(), now()])?;
+    find(&conn, username.trim())?.ok_or(Error::InvalidCredentials)
+}
+
+// ─── Sessão ───────────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct SessionState {
+    account:     Option<Account>,
+    last_active: Option<Instant>,
+    // Quem estava logado quando a sessão trancou (a tela de desbloqueio já vem preenchida)
+    locked_user: Option<String>,
+}
+
+#[derive(Default)]
+struct LoginFailures {
+    count: u32,
+    until: Option<Instant>,
+}
+
+// Uma sessão por instância do app
+#[derive(Default)]
+pub struct Sessions {
+    state:    Mutex<SessionState>,
+    // Por username em minúsculas, existente ou não (não revela quem existe)
+    failures: Mutex<HashMap<String, LoginFailures>>,
+}
+
+fn login_key(username: &str) -> String {
+    username.trim().to_lowercase()
+}
+
+impl Sessions {
+    pub fn start(&self, account: Account) {
+        self.failures.lock().unwrap().remove(&login_key(&account.username));
+        let mut state = self.state.lock().unwrap();
+        state.account = Some(account);
+        state.last_active = Some(Instant::now());
+        state.locked_user = None;
+    }
+
+    pub fn end(&self) -> Option<Account> {
+        std::mem::take(&mut *self.state.lock().unwrap()).account
+    }
+
+    pub fn lock(&self) -> Option<Account> {
+        let mut state = self.state.lock().unwrap();
+        let account = state.account.take();
+        if let Some(a) = &account {
+            state.locked_user = Some(a.username.clone());
+        }
+        account
+    }
+
+    pub fn locked_user(&self) -> Option<String> {
+        self.state.lock().unwrap().locked_user.clone()
+    }
+
+    // Conta logada; passado o tempo ocioso a sessão tranca aqui mesmo
+    pub fn current(&self, idle: Duration) -> Option<Account> {
+        let mut state = self.state.lock().unwrap();
+        let expired = state.last_active.is_none_or(|t| t.elapsed() >= idle);
+        if expired && state.account.is_some() {
+            let account = state.account.take();
+            state.locked_user = account.map(|a| a.username);
+            println!("[ACCOUNTS] Sessão trancada por inatividade");
+        }
+        state.account.clone()
+    }
+
+    // Atividade do operador (teclado, mouse) reportada pela interface
+    pub fn touch(&self, idle: Duration) -> Result<Account> {
+        let account = self.current(idle).ok_or(Error::NotAuthenticated)?;
+        self.state.lock().unwrap().last_active = Some(Instant::now());
+        Ok(account)
+    }
+
+    // Quanto falta para `username` poder tentar o login de novo
+    pub fn login_wait(&self, username: &str) -> Option<Duration> {
+        let failures = self.failures.lock().unwrap();
+        let until = failures.get(&login_key(username))?.until?;
+        until.checked_duration_since(Instant::now()).filter(|d| !d.is_zero())
+    }
+
+    // Senha errada (ou conta inexistente/desativada): devolve a espera imposta
+    pub fn login_failed(&self, username: &str) -> Duration {
+        let mut failures = self.failures.lock().unwrap();
+        if failures.len() >= LOGIN_TRACKED_USERS {
+            let now = Instant::now();
+            failures.retain(|_, f| f.until.is_some_and(|t| t > now));
+        }
+        let entry = failures.entry(login_key(username)).or_default();
+        entry.count += 1;
+        let wait = match entry.count.saturating_sub(LOGIN_FREE_FAILURES) {
+            0 => Duration::ZERO,
+            n => Duration::from_secs(2u64.saturating_pow(n)).min(LOGIN_MAX_WAIT),
+        };
+        entry.until = Some(Instant::now() + wait);
+        wait
+    }
+
+    // Conta logada com pelo menos `role`. A conta é sempre relida de `path`: o outro
+    // app (mesmo OSS_ACCOUNTS_DB) pode ter rebaixado ou desativado o operador depois
+    // do login, e isso vale já, inclusive para quem só consulta
+    pub fn require(&self, path: &Path, role: Role, idle: Duration) -> Result<Account> {
+        let current = self.current(idle).ok_or(Error::NotAuthenticated)?;
+        let account = match find(&open(path)?, &current.username)? {
+            Some(stored) if !stored.disabled => {
+                self.refresh(&stored);
+                stored
+            }
+            _ => {
+                self.end();
+                println!("[ACCOUNTS] Sessão de {} encerrada: conta desativada ou removida", current.username);
+                return Err(Error::NotAuthenticated);
+            }
+        };
+        if account.role < role {
+            return Err(Error::Forbidden(role.as_str().into()));
+        }
+        Ok(account)
+    }
+
+    // Depois de um admin alterar a conta logada: perfil novo vale já; desativada sai
+    pub fn refresh(&self, account: &Account) {
+        let mut state = self.state.lock().unwrap();
+        if state.account.as_ref().is_some_and(|a| a.username.eq_ignore_ascii_case(&account.username)) {
+            if account.disabled {
+                *state = SessionState::default();
+            } else {
+                state.account = Some(account.clone());
+            }
+        }
+    }
+}
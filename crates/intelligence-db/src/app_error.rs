@@ -0,0 +1,211 @@
+// Erro dos comandos Tauri (catálogo e dashboard). Serializado para o frontend como
+// `{ code, i18n_key, message, context }`: `code` é estável e serve para decidir o
+// que fazer, `i18n_key` + `context` para exibir a mensagem no idioma da interface.
+// `message` (pt) fica como fallback e para os logs.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::query::ParseError;
+
+pub type AppResult<T> = std::result::Result<T, AppError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("pasta de dados não configurada (intelligence.db não encontrado)")]
+    DataRootNotConfigured,
+
+    #[error("pasta inválida: {0:?} precisa conter intelligence.db e omni_cams.json")]
+    InvalidDataRoot(PathBuf),
+
+    #[error("banco de dados indisponível: {0}")]
+    DatabaseUnavailable(String),
+
+    #[error("schema incompatível: {0} ausente")]
+    SchemaIncompatible(String),
+
+    #[error("registro não encontrado: {0}")]
+    NotFound(String),
+
+    #[error("erro na consulta: {0}")]
+    Query(String),
+
+    #[error("não suportado com o banco atual: {0}")]
+    Unsupported(String),
+
+    #[error("arquivo não encontrado: {0:?}")]
+    FileNotFound(PathBuf),
+
+    #[error("caminho fora da pasta de imagens: {0:?}")]
+    PathNotAllowed(PathBuf),
+
+    #[error("erro de E/S em {path:?}: {detail}")]
+    Io { path: PathBuf, detail: String },
+
+    #[error("configuração: {0}")]
+    Settings(String),
+
+    #[error("parâmetro inválido `{field}`: {detail}")]
+    InvalidInput { field: String, detail: String },
+
+    #[error("serviço externo indisponível ({service}): {detail}")]
+    ServiceUnavailable { service: String, detail: String },
+
+    #[error("serviço externo ({service}) respondeu com erro: {detail}")]
+    ServiceFailed { service: String, detail: String },
+
+    #[error("sessão encerrada ou trancada: entre novamente")]
+    NotAuthenticated,
+
+    #[error("usuário ou senha inválidos")]
+    InvalidCredentials,
+
+    #[error("muitas tentativas de login: tente de novo em {retry_after} s")]
+    TooManyAttempts { retry_after: u64 },
+
+    #[error("sem permissão: requer perfil {required}")]
+    Forbidden { required: String },
+
+    #[error("consulta inválida na coluna {column}: {0}", column = .0.position + 1)]
+    QuerySyntax(ParseError),
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::DataRootNotConfigured     => "DATA_ROOT_NOT_CONFIGURED",
+            AppError::InvalidDataRoot(_)        => "INVALID_DATA_ROOT",
+            AppError::DatabaseUnavailable(_)    => "DATABASE_UNAVAILABLE",
+            AppError::SchemaIncompatible(_)     => "SCHEMA_INCOMPATIBLE",
+            AppError::NotFound(_)               => "NOT_FOUND",
+            AppError::Query(_)                  => "QUERY_FAILED",
+            AppError::Unsupported(_)            => "UNSUPPORTED",
+            AppError::FileNotFound(_)           => "FILE_NOT_FOUND",
+            AppError::PathNotAllowed(_)         => "PATH_NOT_ALLOWED",
+            AppError::Io { .. }                 => "IO_ERROR",
+            AppError::Settings(_)               => "SETTINGS_ERROR",
+            AppError::InvalidInput { .. }       => "INVALID_INPUT",
+            AppError::ServiceUnavailable { .. } => "SERVICE_UNAVAILABLE",
+            AppError::ServiceFailed { .. }      => "SERVICE_FAILED",
+            AppError::NotAuthenticated          => "NOT_AUTHENTICATED",
+            AppError::InvalidCredentials        => "INVALID_CREDENTIALS",
+            AppError::TooManyAttempts { .. }    => "TOO_MANY_ATTEMPTS",
+            AppError::Forbidden { .. }          => "FORBIDDEN",
+            AppError::QuerySyntax(_)            => "QUERY_SYNTAX",
+        }
+    }
+
+    pub fn i18n_key(&self) -> String {
+        format!("errors.{}", self.code().to_lowercase())
+    }
+
+    // Campos usados na interpolação das mensagens traduzidas
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        let mut ctx = BTreeMap::new();
+        match self {
+            AppError::DataRootNotConfigured | AppError::NotAuthenticated | AppError::InvalidCredentials => {}
+            AppError::Forbidden { required } => {
+                ctx.insert("required", required.clone());
+            }
+            AppError::TooManyAttempts { retry_after } => {
+                ctx.insert("retry_after", retry_after.to_string());
+            }
+            AppError::InvalidDataRoot(path) | AppError::FileNotFound(path) | AppError::PathNotAllowed(path) => {
+                ctx.insert("path", path.display().to_string());
+            }
+            AppError::DatabaseUnavailable(detail) | AppError::Query(detail) | AppError::Settings(detail) => {
+                ctx.insert("detail", detail.clone());
+            }
+            AppError::SchemaIncompatible(object) => {
+                ctx.insert("object", object.clone());
+            }
+            AppError::Unsupported(feature) => {
+                ctx.insert("feature", feature.clone());
+            }
+            AppError::NotFound(id) => {
+                ctx.insert("id", id.clone());
+            }
+            AppError::Io { path, detail } => {
+                ctx.insert("path", path.display().to_string());
+                ctx.insert("detail", detail.clone());
+            }
+            AppError::InvalidInput { field, detail } => {
+                ctx.insert("field", field.clone());
+                ctx.insert("detail", detail.clone());
+            }
+            AppError::ServiceUnavailable { service, detail } | AppError::ServiceFailed { service, detail } => {
+                ctx.insert("service", service.clone());
+                ctx.insert("detail", detail.clone());
+            }
+            // `reason` escolhe a mensagem traduzida; `position` (0..) marca o trecho na caixa de busca
+            AppError::QuerySyntax(e) => {
+                ctx.insert("reason", e.kind.code().to_string());
+                ctx.insert("position", e.position.to_string());
+                ctx.insert("column", (e.position + 1).to_string());
+                ctx.insert("token", e.token.clone());
+                ctx.insert("detail", e.to_string());
+            }
+        }
+        ctx
+    }
+
+    pub fn io(path: &Path, err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            AppError::FileNotFound(path.to_path_buf())
+        } else {
+            AppError::Io { path: path.to_path_buf(), detail: err.to_string() }
+        }
+    }
+
+    pub fn invalid_input(field: &str, detail: impl ToString) -> Self {
+        AppError::InvalidInput { field: field.to_string(), detail: detail.to_string() }
+    }
+
+    pub fn service_unavailable(service: &str, detail: impl ToString) -> Self {
+        AppError::ServiceUnavailable { service: service.to_string(), detail: detail.to_string() }
+    }
+
+    pub fn service_failed(service: &str, detail: impl ToString) -> Self {
+        AppError::ServiceFailed { service: service.to_string(), detail: detail.to_string() }
+    }
+
+    // Falha ao abrir o backend: erro de driver vira "banco indisponível", não "consulta"
+    pub fn from_open(err: Error) -> Self {
+        match err {
+            Error::Sqlite(e) => AppError::DatabaseUnavailable(e.to_string()),
+            Error::Postgres(e) => AppError::DatabaseUnavailable(e.to_string()),
+            other => other.into(),
+        }
+    }
+}
+
+impl From<Error> for AppError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NotFound(id) => AppError::NotFound(id),
+            Error::MissingTable(table) => AppError::SchemaIncompatible(table),
+            Error::MissingColumn(table, column) => AppError::SchemaIncompatible(format!("{table}.{column}")),
+            Error::Unsupported(feature) => AppError::Unsupported(feature),
+            Error::InvalidInput(field, detail) => AppError::InvalidInput { field, detail },
+            Error::QuerySyntax(e) => AppError::QuerySyntax(e),
+            Error::NotAuthenticated => AppError::NotAuthenticated,
+            Error::InvalidCredentials => AppError::InvalidCredentials,
+            Error::TooManyAttempts(retry_after) => AppError::TooManyAttempts { retry_after },
+            Error::Forbidden(required) => AppError::Forbidden { required },
+            other => AppError::Query(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AppError", 4)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("i18n_key", &self.i18n_key())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.serialize_field("context", &self.context())?;
+        s.end()
+    }
+}
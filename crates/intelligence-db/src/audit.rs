@@ -0,0 +1,289 @@
+// Auditoria de qualidade do catálogo: varre o intelligence.db e a pasta de imagens
+// atrás de nomes vazios, `img_path` sem arquivo e linhas órfãs de `individuals`.
+// Só lê — a correção fica com a ingestão Python, dona do banco.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::app_error::AppError;
+use crate::backend::Backend;
+use crate::error::Result;
+use crate::layout;
+use crate::schema::{CRIMES, INDIVIDUALS, INDIVIDUAL_IMAGES, LOCATIONS};
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    EmptyName,
+    MissingImageFile,
+    MissingGalleryFile,
+    ImageOutsideRoot,
+    OrphanCrime,
+    OrphanLocation,
+    OrphanImage,
+}
+
+impl IssueKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IssueKind::EmptyName          => "empty_name",
+            IssueKind::MissingImageFile   => "missing_image_file",
+            IssueKind::MissingGalleryFile => "missing_gallery_file",
+            IssueKind::ImageOutsideRoot   => "image_outside_root",
+            IssueKind::OrphanCrime        => "orphan_crime",
+            IssueKind::OrphanLocation     => "orphan_location",
+            IssueKind::OrphanImage        => "orphan_image",
+        }
+    }
+}
+
+// `id` é o indivíduo afetado (nas órfãs, o `individual_id` que não existe)
+#[derive(Serialize, Clone, Debug)]
+pub struct AuditIssue {
+    pub id:     String,
+    pub table:  &'static str,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AuditCategory {
+    pub kind:   IssueKind,
+    pub count:  usize,
+    pub issues: Vec<AuditIssue>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AuditReport {
+    pub generated_at: String,
+    pub database:     String,
+    pub images_dir:   Option<PathBuf>,
+    pub individuals:  i64,
+    pub total:        usize,
+    pub categories:   Vec<AuditCategory>,
+}
+
+// Sem `images_dir` os arquivos de imagem não são verificados
+pub fn run(db: &dyn Backend, images_dir: Option<&Path>) -> Result<AuditReport> {
+    let s = db.schema();
+    let individuals = db.count("SELECT COUNT(*) FROM individuals", &[])?;
+    let mut categories = vec![];
+
+    let empty_names = db.query(
+        "SELECT i.id, i.source FROM individuals i WHERE TRIM(COALESCE(i.name, '')) = '' ORDER BY i.id",
+        &[],
+        |r| Ok(AuditIssue { id: r.text(0)?, table: INDIVIDUALS, detail: r.opt_text(1)? }),
+    )?;
+    categories.push(category(IssueKind::EmptyName, empty_names));
+
+    if let Some(dir) = images_dir {
+        let mut outside = vec![];
+        let paths = db.query(
+            &format!(
+                "SELECT i.id, {img} FROM individuals i WHERE TRIM(COALESCE({img}, '')) <> '' ORDER BY i.id",
+                img = s.text(INDIVIDUALS, "i", "img_path"),
+            ),
+            &[],
+            |r| Ok((r.text(0)?, r.text(1)?)),
+        )?;
+        categories.push(category(IssueKind::MissingImageFile, missing_files(dir, paths, INDIVIDUALS, &mut outside)));
+
+        if s.has_images() {
+            let paths = db.query(
+                &format!(
+                    "SELECT m.individual_id, {img} FROM individual_images m
+                     WHERE m.individual_id IS NOT NULL AND TRIM(COALESCE({img}, '')) <> ''
+                     ORDER BY m.individual_id",
+                    img = s.text(INDIVIDUAL_IMAGES, "m", "img_path"),
+                ),
+                &[],
+                |r| Ok((r.text(0)?, r.text(1)?)),
+            )?;
+            categories.push(category(IssueKind::MissingGalleryFile, missing_files(dir, paths, INDIVIDUAL_IMAGES, &mut outside)));
+        }
+        categories.push(category(IssueKind::ImageOutsideRoot, outside));
+    }
+
+    // Órfãs: `individual_id` nulo ou sem indivíduo correspondente
+    let orphans = |table: &'static str, alias: &str, detail: String| -> Result<Vec<AuditIssue>> {
+        db.query(
+            &format!(
+                "SELECT COALESCE({alias}.individual_id, ''), {detail} FROM {table} {alias}
+                 WHERE NOT EXISTS (SELECT 1 FROM individuals i WHERE i.id = {alias}.individual_id)
+                 ORDER BY 1"
+            ),
+            &[],
+            |r| Ok(AuditIssue { id: r.text(0)?, table, detail: r.opt_text(1)? }),
+        )
+    };
+    if s.has_crimes() {
+        categories.push(category(IssueKind::OrphanCrime, orphans(CRIMES, "c", s.text(CRIMES, "c", "crime"))?));
+    }
+    if s.has_locations() {
+        let detail = format!(
+            "COALESCE({}, '') || ': ' || COALESCE({}, '') || ' / ' || COALESCE({}, '')",
+            s.text(LOCATIONS, "l", "type"),
+            s.text(LOCATIONS, "l", "country"),
+            s.text(LOCATIONS, "l", "city"),
+        );
+        categories.push(category(IssueKind::OrphanLocation, orphans(LOCATIONS, "l", detail)?));
+    }
+    if s.has_images() {
+        let detail = format!("COALESCE({}, {})", s.text(INDIVIDUAL_IMAGES, "m", "img_path"), s.text(INDIVIDUAL_IMAGES, "m", "img_url"));
+        categories.push(category(IssueKind::OrphanImage, orphans(INDIVIDUAL_IMAGES, "m", detail)?));
+    }
+
+    Ok(AuditReport {
+        generated_at: chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        database:     db.describe(),
+        images_dir:   images_dir.map(Path::to_path_buf),
+        individuals,
+        total:        categories.iter().map(|c| c.count).sum(),
+        categories,
+    })
+}
+
+fn category(kind: IssueKind, issues: Vec<AuditIssue>) -> AuditCategory {
+    AuditCategory { kind, count: issues.len(), issues }
+}
+
+// Arquivos ausentes; caminhos que escapam da pasta de imagens vão para `outside`
+fn missing_files(dir: &Path, paths: Vec<(String, String)>, table: &'static str, outside: &mut Vec<AuditIssue>) -> Vec<AuditIssue> {
+    let mut missing = vec![];
+    for (id, path) in paths {
+        match layout::confine(dir, &path) {
+            Ok(_) => {}
+            Err(AppError::PathNotAllowed(_)) => outside.push(AuditIssue { id, table, detail: Some(path) }),
+            Err(_) => missing.push(AuditIssue { id, table, detail: Some(path) }),
+        }
+    }
+    missing
+}
+
+// ─── Exportação ───────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv  => "csv",
+        }
+    }
+}
+
+pub fn export(report: &AuditReport, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(report).unwrap_or_default(),
+        ExportFormat::Csv  => to_csv(report),
+    }
+}
+
+// Uma linha por problema: category,table,id,detail
+fn to_csv(report: &AuditReport) -> String {
+    let mut out = String::from("category,table,id,detail\n");
+    for category in &report.categories {
+        for issue in &category.issues {
+            let fields = [category.kind.as_str(), issue.table, &issue.id, issue.detail.as_deref().unwrap_or("")];
+            out.push_str(&fields.map(csv_field).join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, Fixture};
+
+    // Órfãs só existem sem a checagem de chave estrangeira (como grava a ingestão)
+    const SEED: &str = "
+        PRAGMA foreign_keys = OFF;
+        INSERT INTO individuals (id, name, category, source, img_path) VALUES
+            ('a', 'Fulano',  'wanted', 'fbi',      'images/a.jpg'),
+            ('b', '   ',     'wanted', 'interpol', 'images/b.jpg'),
+            ('c', 'Ciclano', 'wanted', 'fbi',      '../fora.jpg'),
+            ('d', 'Beltrano', 'wanted', 'fbi',     NULL);
+        INSERT INTO individual_images (individual_id, img_path, img_url) VALUES
+            ('a', 'images/a_1.jpg', NULL), ('a', 'images/a_2.jpg', NULL), ('x', NULL, 'http://img/x.jpg');
+        INSERT INTO crimes (individual_id, crime) VALUES ('a', 'fraud'), ('x', 'theft, armed'), (NULL, 'smuggling');
+        INSERT INTO locations (individual_id, type, country, city) VALUES ('y', 'last_seen', 'Brazil', NULL);";
+
+    fn issues(report: &AuditReport, kind: IssueKind) -> Vec<(String, Option<String>)> {
+        let category = report.categories.iter().find(|c| c.kind == kind).unwrap();
+        assert_eq!(category.count, category.issues.len());
+        category.issues.iter().map(|i| (i.id.clone(), i.detail.clone())).collect()
+    }
+
+    fn setup(name: &str) -> (Fixture, PathBuf) {
+        let dir = temp_dir(&format!("audit_{name}"));
+        std::fs::create_dir_all(dir.join("images")).unwrap();
+        std::fs::write(dir.join("images/a.jpg"), b"a").unwrap();
+        std::fs::write(dir.join("images/a_1.jpg"), b"a").unwrap();
+        (Fixture::new(&format!("audit_{name}"), SEED), dir)
+    }
+
+    #[test]
+    fn finds_empty_names_missing_files_and_orphans() {
+        let (f, dir) = setup("run");
+        let report = run(&f.db, Some(&dir)).unwrap();
+        assert_eq!(report.individuals, 4);
+        assert_eq!(issues(&report, IssueKind::EmptyName), [("b".into(), Some("interpol".into()))]);
+        assert_eq!(issues(&report, IssueKind::MissingImageFile), [("b".into(), Some("images/b.jpg".into()))]);
+        assert_eq!(issues(&report, IssueKind::MissingGalleryFile), [("a".into(), Some("images/a_2.jpg".into()))]);
+        assert_eq!(issues(&report, IssueKind::ImageOutsideRoot), [("c".into(), Some("../fora.jpg".into()))]);
+        assert_eq!(
+            issues(&report, IssueKind::OrphanCrime),
+            [(String::new(), Some("smuggling".into())), ("x".into(), Some("theft, armed".into()))]
+        );
+        assert_eq!(issues(&report, IssueKind::OrphanLocation), [("y".into(), Some("last_seen: Brazil / ".into()))]);
+        assert_eq!(issues(&report, IssueKind::OrphanImage), [("x".into(), Some("http://img/x.jpg".into()))]);
+        assert_eq!(report.total, 8);
+    }
+
+    #[test]
+    fn without_images_dir_or_optional_tables() {
+        let (f, _) = setup("no_dir");
+        let report = run(&f.db, None).unwrap();
+        let kinds: Vec<_> = report.categories.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [IssueKind::EmptyName, IssueKind::OrphanCrime, IssueKind::OrphanLocation, IssueKind::OrphanImage]);
+
+        let seed = format!("{SEED} DROP TABLE crimes; DROP TABLE locations; DROP TABLE individual_images;");
+        let g = Fixture::new("audit_older_schema", &seed);
+        let report = run(&g.db, None).unwrap();
+        assert_eq!(report.categories.len(), 1);
+        assert_eq!(report.total, 1);
+    }
+
+    #[test]
+    fn csv_export_quotes_fields() {
+        let (f, dir) = setup("csv");
+        let report = run(&f.db, Some(&dir)).unwrap();
+        let csv = export(&report, ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "category,table,id,detail");
+        assert_eq!(lines.len(), report.total + 1);
+        assert!(lines.contains(&"orphan_crime,crimes,x,\"theft, armed\""));
+        assert_eq!(csv_field("diz \"oi\""), "\"diz \"\"oi\"\"\"");
+        assert_eq!(csv_field("simples"), "simples");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+
+        let json: serde_json::Value = serde_json::from_str(&export(&report, ExportFormat::Json)).unwrap();
+        assert_eq!(json["categories"][0]["kind"], "empty_name");
+        assert_eq!(json["total"], 8);
+    }
+}
@@ -0,0 +1,509 @@
+// Log de auditoria do app: quem fez o quê e quando (acesso a indivíduos e
+// avistamentos, exportações, expurgos da retenção), no sidecar audit_log.db.
+// Só acrescenta — os gatilhos abortam UPDATE/DELETE feitos pelo SQLite — e cada
+// evento guarda o sha256 do anterior (`prev_hash`) e o próprio (`hash`): alterar,
+// remover ou reordenar eventos direto no arquivo quebra a cadeia, e `verify` aponta
+// onde. Cortar o fim do log só aparece comparando com a `head_hash` de uma
+// verificação/exportação anterior guardada fora do app.
+
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::audit::{csv_field, ExportFormat};
+use crate::error::Result;
+use crate::sidecar;
+
+pub const AUDIT_LOG_FILE: &str = "audit_log.db";
+
+// `prev_hash` do primeiro evento da cadeia
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+pub const DEFAULT_LIST_LIMIT: u32 = 200;
+pub const MAX_LIST_LIMIT:     u32 = 5000;
+
+const MIGRATIONS: &[&str] = &[
+    // detail: JSON livre da ação (ex.: relatório do expurgo)
+    "CREATE TABLE audit_events (
+         id     INTEGER PRIMARY KEY AUTOINCREMENT,
+         at     TEXT NOT NULL,
+         actor  TEXT NOT NULL,
+         action TEXT NOT NULL,
+         target TEXT,
+         detail TEXT NOT NULL DEFAULT '{}'
+     );
+     CREATE INDEX audit_events_action ON audit_events (action);
+     CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+     BEGIN SELECT RAISE(ABORT, 'audit_events é somente-inclusão'); END;
+     CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+     BEGIN SELECT RAISE(ABORT, 'audit_events é somente-inclusão'); END;",
+    // Cadeia de hashes. Eventos gravados antes dela são selados uma única vez em
+    // `open` (o sha256 não sai do SQL), que recria o gatilho de UPDATE e anota em
+    // audit_chain
+    "ALTER TABLE audit_events ADD COLUMN prev_hash TEXT;
+     ALTER TABLE audit_events ADD COLUMN hash TEXT;
+     DROP TRIGGER audit_events_no_update;
+     CREATE INDEX audit_events_actor ON audit_events (actor);
+     CREATE INDEX audit_events_target ON audit_events (target);
+     CREATE TABLE audit_chain (sealed_at TEXT NOT NULL);",
+];
+
+const NO_UPDATE_TRIGGER: &str = "CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+     BEGIN SELECT RAISE(ABORT, 'audit_events é somente-inclusão'); END;";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AuditEvent {
+    pub id:        i64,
+    pub at:        String,
+    pub actor:     String,
+    pub action:    String,
+    pub target:    Option<String>,
+    pub detail:    serde_json::Value,
+    pub prev_hash: Option<String>,
+    pub hash:      Option<String>,
+}
+
+// Todos os campos opcionais; `target` também casa com ids citados no detalhe
+// (ex.: resultados de uma busca)
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub actor:  Option<String>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+    // Prefixos de data/hora comparáveis com `at` (ex.: "2026-10-01")
+    pub since:  Option<String>,
+    pub until:  Option<String>,
+    pub limit:  Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakKind {
+    // Conteúdo não bate com o próprio hash (evento alterado)
+    HashMismatch,
+    // `prev_hash` não é o hash do evento anterior (evento removido, inserido ou re-hasheado)
+    PrevMismatch,
+    // Ids pulados: eventos removidos
+    Gap,
+    // O contador do AUTOINCREMENT passou do último evento: o fim foi cortado
+    Truncated,
+    // Evento sem hash, inserido por fora do app
+    Unsealed,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ChainBreak {
+    pub id:     i64,
+    pub kind:   BreakKind,
+    pub detail: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ChainReport {
+    pub verified_at: String,
+    pub events:      i64,
+    pub valid:       bool,
+    // Hash do último evento: guardado fora do app, prova o que existia até aqui
+    pub head_hash:   Option<String>,
+    pub breaks:      Vec<ChainBreak>,
+}
+
+// Formato de `at` dos eventos
+pub fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+}
+
+// sha256 de [prev_hash, at, actor, action, target, detail] em JSON (sem ambiguidade
+// de separador; `detail` entra como texto, exatamente como gravado)
+fn chain_hash(prev: &str, at: &str, actor: &str, action: &str, target: Option<&str>, detail: &str) -> String {
+    let canonical = serde_json::json!([prev, at, actor, action, target, detail]).to_string();
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+// Último hash da cadeia; eventos sem hash (inseridos por fora) ficam de fora dela
+fn head(conn: &Connection) -> Result<String> {
+    let last: Option<String> = conn
+        .query_row("SELECT hash FROM audit_events WHERE hash IS NOT NULL ORDER BY id DESC LIMIT 1", [], |r| r.get(0))
+        .optional()?;
+    Ok(last.unwrap_or_else(|| GENESIS_HASH.into()))
+}
+
+fn open(path: &Path) -> Result<Connection> {
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    // Só logo depois da migração 2; eventos sem hash que apareçam depois disso não
+    // são selados: `verify` os acusa
+    let sealed: bool = conn.query_row("SELECT EXISTS (SELECT 1 FROM audit_chain)", [], |r| r.get(0))?;
+    if !sealed {
+        seal(&mut conn)?;
+    }
+    Ok(conn)
+}
+
+// Encadeia os eventos anteriores à cadeia, na ordem dos ids, e volta o gatilho
+fn seal(conn: &mut Connection) -> Result<()> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let rows = {
+        let mut stmt = tx.prepare("SELECT id, at, actor, action, target, detail, hash FROM audit_events ORDER BY id")?;
+        let rows = stmt.query_map([], |r| {
+            Ok((
+                r.get::<_, i64>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, String>(2)?,
+                r.get::<_, String>(3)?,
+                r.get::<_, Option<String>>(4)?,
+                r.get::<_, String>(5)?,
+                r.get::<_, Option<String>>(6)?,
+            ))
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()?
+    };
+    let mut prev = GENESIS_HASH.to_string();
+    let mut sealed = 0;
+    for (id, at, actor, action, target, detail, hash) in rows {
+        prev = match hash {
+            Some(hash) => hash,
+            None => {
+                let hash = chain_hash(&prev, &at, &actor, &action, target.as_deref(), &detail);
+                tx.execute("UPDATE audit_events SET prev_hash = ?1, hash = ?2 WHERE id = ?3", params![prev, hash, id])?;
+                sealed += 1;
+                hash
+            }
+        };
+    }
+    tx.execute_batch(&format!("DROP TRIGGER IF EXISTS audit_events_no_update; {NO_UPDATE_TRIGGER}"))?;
+    tx.execute("INSERT INTO audit_chain (sealed_at) VALUES (?1)", [now()])?;
+    tx.commit()?;
+    if sealed > 0 {
+        println!("[AUDIT] {sealed} eventos anteriores à cadeia de hashes selados");
+    }
+    Ok(())
+}
+
+// Acrescenta um evento ao fim da cadeia e devolve o id. A transação IMMEDIATE
+// serializa escritores (outras threads ou o outro app apontando para o mesmo arquivo).
+pub fn record(path: &Path, actor: &str, action: &str, target: Option<&str>, detail: &serde_json::Value) -> Result<i64> {
+    let mut conn = open(path)?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let prev = head(&tx)?;
+    let at = now();
+    let detail = detail.to_string();
+    let hash = chain_hash(&prev, &at, actor, action, target, &detail);
+    tx.execute(
+        "INSERT INTO audit_events (at, actor, action, target, detail, prev_hash, hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![at, actor, action, target, detail, prev, hash],
+    )?;
+    let id = tx.last_insert_rowid();
+    tx.commit()?;
+    Ok(id)
+}
+
+// ─── Verificação ──────────────────────────────────────────────────────────────
+
+fn missing(from: i64, to: i64) -> String {
+    if from == to {
+        format!("evento {from} ausente")
+    } else {
+        format!("eventos {from} a {to} ausentes")
+    }
+}
+
+// Recalcula a cadeia inteira, do primeiro ao último evento
+pub fn verify(path: &Path) -> Result<ChainReport> {
+    let conn = open(path)?;
+    let mut breaks = vec![];
+    let mut prev_id = 0;
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut events = 0;
+    {
+        let mut stmt =
+            conn.prepare("SELECT id, at, actor, action, target, detail, prev_hash, hash FROM audit_events ORDER BY id")?;
+        let mut rows = stmt.query([])?;
+        while let Some(r) = rows.next()? {
+            let id: i64 = r.get(0)?;
+            let target: Option<String> = r.get(4)?;
+            let stored_prev: Option<String> = r.get(6)?;
+            let stored: Option<String> = r.get(7)?;
+            events += 1;
+
+            if id != prev_id + 1 {
+                breaks.push(ChainBreak { id, kind: BreakKind::Gap, detail: missing(prev_id + 1, id - 1) });
+            }
+            if stored_prev.as_deref() != Some(prev_hash.as_str()) {
+                breaks.push(ChainBreak { id, kind: BreakKind::PrevMismatch, detail: format!("esperado {prev_hash}") });
+            }
+            let computed = chain_hash(
+                stored_prev.as_deref().unwrap_or(GENESIS_HASH),
+                &r.get::<_, String>(1)?,
+                &r.get::<_, String>(2)?,
+                &r.get::<_, String>(3)?,
+                target.as_deref(),
+                &r.get::<_, String>(5)?,
+            );
+            match &stored {
+                None => breaks.push(ChainBreak { id, kind: BreakKind::Unsealed, detail: "evento sem hash".into() }),
+                Some(hash) if *hash != computed => {
+                    breaks.push(ChainBreak { id, kind: BreakKind::HashMismatch, detail: format!("calculado {computed}") })
+                }
+                Some(_) => {}
+            }
+            prev_id = id;
+            // Como em `head`: o evento seguinte encadeia no último evento com hash
+            if let Some(hash) = stored {
+                prev_hash = hash;
+            }
+        }
+    }
+
+    let seq: Option<i64> =
+        conn.query_row("SELECT seq FROM sqlite_sequence WHERE name = 'audit_events'", [], |r| r.get(0)).optional()?;
+    if let Some(seq) = seq.filter(|s| *s > prev_id) {
+        breaks.push(ChainBreak { id: seq, kind: BreakKind::Truncated, detail: missing(prev_id + 1, seq) });
+    }
+
+    let report = ChainReport {
+        verified_at: now(),
+        events,
+        valid:       breaks.is_empty(),
+        head_hash:   (events > 0).then_some(prev_hash),
+        breaks,
+    };
+    if report.valid {
+        println!("[AUDIT] Cadeia íntegra: {} eventos", report.events);
+    } else {
+        println!("[AUDIT] Cadeia QUEBRADA: {} problemas em {} eventos", report.breaks.len(), report.events);
+    }
+    Ok(report)
+}
+
+// ─── Consulta e exportação ────────────────────────────────────────────────────
+
+fn map_event(r: &rusqlite::Row) -> rusqlite::Result<AuditEvent> {
+    let detail: String = r.get(5)?;
+    Ok(AuditEvent {
+        id:        r.get(0)?,
+        at:        r.get(1)?,
+        actor:     r.get(2)?,
+        action:    r.get(3)?,
+        target:    r.get(4)?,
+        detail:    serde_json::from_str(&detail).unwrap_or(serde_json::Value::String(detail)),
+        prev_hash: r.get(6)?,
+        hash:      r.get(7)?,
+    })
+}
+
+// Eventos mais recentes primeiro
+pub fn list(path: &Path, filter: &AuditFilter) -> Result<Vec<AuditEvent>> {
+    let conn = open(path)?;
+    let limit = filter.limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+    let mut stmt = conn.prepare(
+        "SELECT id, at, actor, action, target, detail, prev_hash, hash FROM audit_events
+         WHERE (?1 IS NULL OR actor = ?1)
+           AND (?2 IS NULL OR action = ?2)
+           AND (?3 IS NULL OR target = ?3 OR instr(detail, '\"' || ?3 || '\"') > 0)
+           AND (?4 IS NULL OR at >= ?4)
+           AND (?5 IS NULL OR substr(at, 1, length(?5)) <= ?5)
+         ORDER BY id DESC LIMIT ?6 OFFSET ?7",
+    )?;
+    let rows = stmt.query_map(
+        params![filter.actor, filter.action, filter.target, filter.since, filter.until, limit, filter.offset.unwrap_or(0)],
+        map_event,
+    )?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+#[derive(Serialize)]
+struct AuditExport<'a> {
+    exported_at: String,
+    filter:      &'a AuditFilter,
+    chain:       ChainReport,
+    events:      Vec<AuditEvent>,
+}
+
+pub fn export_file_name(format: ExportFormat) -> String {
+    format!("audit-log-{}.{}", chrono::Local::now().format("%Y-%m-%dT%H%M%S"), format.extension())
+}
+
+// Eventos do filtro (sem paginação: até MAX_LIST_LIMIT) com o estado da cadeia
+pub fn export(path: &Path, filter: &AuditFilter, format: ExportFormat) -> Result<String> {
+    let filter = AuditFilter { limit: Some(filter.limit.unwrap_or(MAX_LIST_LIMIT)), ..filter.clone() };
+    let events = list(path, &filter)?;
+    Ok(match format {
+        ExportFormat::Json => {
+            let export = AuditExport { exported_at: now(), filter: &filter, chain: verify(path)?, events };
+            serde_json::to_string_pretty(&export).unwrap_or_default()
+        }
+        ExportFormat::Csv => to_csv(&events),
+    })
+}
+
+// Uma linha por evento: id,at,actor,action,target,detail,prev_hash,hash
+fn to_csv(events: &[AuditEvent]) -> String {
+    let mut out = String::from("id,at,actor,action,target,detail,prev_hash,hash\n");
+    for e in events {
+        let id = e.id.to_string();
+        let detail = e.detail.to_string();
+        let fields = [
+            id.as_str(),
+            &e.at,
+            &e.actor,
+            &e.action,
+            e.target.as_deref().unwrap_or(""),
+            &detail,
+            e.prev_hash.as_deref().unwrap_or(""),
+            e.hash.as_deref().unwrap_or(""),
+        ];
+        out.push_str(&fields.map(csv_field).join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Sidecar novo por teste, em pasta temporária
+    fn fresh(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("audit_log_test_{}_{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn log(name: &str, events: usize) -> PathBuf {
+        let path = fresh(name);
+        for i in 0..events {
+            record(&path, "admin", "view_individual", Some(&format!("ind-{i}")), &serde_json::json!({ "n": i })).unwrap();
+        }
+        path
+    }
+
+    // Mexe no arquivo por fora do app, sem os gatilhos
+    fn tamper(path: &Path, sql: &str) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(&format!(
+            "DROP TRIGGER audit_events_no_update; DROP TRIGGER audit_events_no_delete; {sql}"
+        ))
+        .unwrap();
+    }
+
+    fn kinds(report: &ChainReport) -> Vec<(i64, BreakKind)> {
+        report.breaks.iter().map(|b| (b.id, b.kind)).collect()
+    }
+
+    #[test]
+    fn chain_hash_covers_every_field() {
+        let base = chain_hash(GENESIS_HASH, "2026-10-01T10:00:00", "admin", "login", Some("x"), "{}");
+        assert_eq!(base.len(), 64);
+        assert_eq!(base, chain_hash(GENESIS_HASH, "2026-10-01T10:00:00", "admin", "login", Some("x"), "{}"));
+        assert_ne!(base, chain_hash(&"1".repeat(64), "2026-10-01T10:00:00", "admin", "login", Some("x"), "{}"));
+        assert_ne!(base, chain_hash(GENESIS_HASH, "2026-10-01T10:00:01", "admin", "login", Some("x"), "{}"));
+        assert_ne!(base, chain_hash(GENESIS_HASH, "2026-10-01T10:00:00", "admin", "login", None, "{}"));
+        assert_ne!(base, chain_hash(GENESIS_HASH, "2026-10-01T10:00:00", "admin", "login", Some("x"), "{\"a\":1}"));
+        // Sem ambiguidade de separador
+        assert_ne!(
+            chain_hash(GENESIS_HASH, "t", "ab", "c", None, "{}"),
+            chain_hash(GENESIS_HASH, "t", "a", "bc", None, "{}")
+        );
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let path = log("intact", 3);
+        let report = verify(&path).unwrap();
+        assert!(report.valid, "{:?}", report.breaks);
+        assert_eq!(report.events, 3);
+        let events = list(&path, &AuditFilter::default()).unwrap();
+        assert_eq!(report.head_hash, events[0].hash);
+        assert_eq!(events[2].prev_hash.as_deref(), Some(GENESIS_HASH));
+        assert_eq!(events[1].prev_hash, events[2].hash);
+    }
+
+    #[test]
+    fn empty_log_is_valid() {
+        let report = verify(&fresh("empty")).unwrap();
+        assert!(report.valid);
+        assert_eq!(report.events, 0);
+        assert_eq!(report.head_hash, None);
+    }
+
+    #[test]
+    fn triggers_block_update_and_delete() {
+        let path = log("triggers", 1);
+        let conn = Connection::open(&path).unwrap();
+        assert!(conn.execute("UPDATE audit_events SET actor = 'x'", []).is_err());
+        assert!(conn.execute("DELETE FROM audit_events", []).is_err());
+    }
+
+    #[test]
+    fn tampered_event_is_detected() {
+        let path = log("tampered", 3);
+        tamper(&path, "UPDATE audit_events SET actor = 'outro' WHERE id = 2;");
+        let report = verify(&path).unwrap();
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), vec![(2, BreakKind::HashMismatch)]);
+    }
+
+    #[test]
+    fn rehashed_event_breaks_the_next_link() {
+        // Recalcular o hash do evento alterado só empurra a quebra para o seguinte
+        let path = log("rehashed", 3);
+        let conn = Connection::open(&path).unwrap();
+        let (at, action, target, detail, prev): (String, String, Option<String>, String, String) = conn
+            .query_row("SELECT at, action, target, detail, prev_hash FROM audit_events WHERE id = 2", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+            })
+            .unwrap();
+        let hash = chain_hash(&prev, &at, "outro", &action, target.as_deref(), &detail);
+        tamper(&path, &format!("UPDATE audit_events SET actor = 'outro', hash = '{hash}' WHERE id = 2;"));
+        let report = verify(&path).unwrap();
+        assert_eq!(kinds(&report), vec![(3, BreakKind::PrevMismatch)]);
+    }
+
+    #[test]
+    fn removed_event_is_a_gap() {
+        let path = log("gap", 4);
+        tamper(&path, "DELETE FROM audit_events WHERE id = 2;");
+        let report = verify(&path).unwrap();
+        assert_eq!(kinds(&report), vec![(3, BreakKind::Gap), (3, BreakKind::PrevMismatch)]);
+        assert_eq!(report.breaks[0].detail, "evento 2 ausente");
+    }
+
+    #[test]
+    fn cut_tail_is_truncated() {
+        let path = log("truncated", 5);
+        let head = verify(&path).unwrap().head_hash;
+        tamper(&path, "DELETE FROM audit_events WHERE id >= 4;");
+        let report = verify(&path).unwrap();
+        assert_eq!(kinds(&report), vec![(5, BreakKind::Truncated)]);
+        assert_eq!(report.breaks[0].detail, "eventos 4 a 5 ausentes");
+        assert_ne!(report.head_hash, head);
+    }
+
+    #[test]
+    fn event_inserted_outside_the_app_is_unsealed() {
+        let path = log("unsealed", 2);
+        Connection::open(&path)
+            .unwrap()
+            .execute("INSERT INTO audit_events (at, actor, action, detail) VALUES ('2026-10-01T10:00:00', 'x', 'login', '{}')", [])
+            .unwrap();
+        let report = verify(&path).unwrap();
+        assert_eq!(kinds(&report), vec![(3, BreakKind::PrevMismatch), (3, BreakKind::Unsealed)]);
+        // O app encadeia o próximo no último evento com hash: a quebra não se espalha
+        record(&path, "admin", "login", None, &serde_json::json!({})).unwrap();
+        assert_eq!(kinds(&verify(&path).unwrap()), vec![(3, BreakKind::PrevMismatch), (3, BreakKind::Unsealed)]);
+    }
+
+    #[test]
+    fn list_filters_by_target_in_detail() {
+        let path = log("filter", 3);
+        record(&path, "admin", "search", None, &serde_json::json!({ "results": ["ind-7"] })).unwrap();
+        let filter = AuditFilter { target: Some("ind-7".into()), ..Default::default() };
+        let events = list(&path, &filter).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, "search");
+    }
+}
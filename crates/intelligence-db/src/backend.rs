@@ -0,0 +1,178 @@
+// Backend de dados: o repositório escreve SQL uma vez (placeholders `?`) e cada
+// implementação (SQLite / Postgres) cuida de conexão, parâmetros e leitura de linhas.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::error::{Error, Result};
+use crate::introspect::SchemaInfo;
+use crate::pg::PgBackend;
+use crate::sqlite::SqliteBackend;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+}
+
+// Parâmetro de consulta independente do driver (também serializado nos cursores de paginação)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Value {
+    Null,
+    Int(i64),
+    Real(f64),
+    Text(String),
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self { Value::Text(v.to_string()) }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self { Value::Text(v) }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self { Value::Int(v) }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self { Value::Real(v) }
+}
+
+// Linha de resultado, lida por índice de coluna
+pub trait Record {
+    fn opt_text(&self, idx: usize) -> Result<Option<String>>;
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>>;
+    fn opt_real(&self, idx: usize) -> Result<Option<f64>>;
+
+    fn text(&self, idx: usize) -> Result<String> {
+        self.opt_text(idx)?.ok_or(Error::UnexpectedNull(idx))
+    }
+
+    fn int(&self, idx: usize) -> Result<i64> {
+        self.opt_int(idx)?.ok_or(Error::UnexpectedNull(idx))
+    }
+
+    fn real(&self, idx: usize) -> Result<f64> {
+        self.opt_real(idx)?.ok_or(Error::UnexpectedNull(idx))
+    }
+}
+
+pub trait Backend: Send + Sync {
+    fn dialect(&self) -> Dialect;
+
+    // Descrição para logs/UI (caminho do arquivo ou host do Postgres, sem senha)
+    fn describe(&self) -> String;
+
+    // Schema detectado na abertura (ver introspect.rs)
+    fn schema(&self) -> &SchemaInfo;
+
+    // Sidecars SQLite do app (ver sidecar.rs) anexados às conexões de leitura.
+    // Só o SQLite consegue juntar o sidecar ao banco principal na mesma consulta.
+    fn attach(&self, alias: &str, _path: &Path) -> Result<()> {
+        Err(Error::Unsupported(format!("ATTACH {alias}")))
+    }
+
+    fn is_attached(&self, _alias: &str) -> bool {
+        false
+    }
+
+    // Escrita no banco principal — só a política de retenção (retention.rs) apaga
+    // evidências vencidas; o resto do app continua somente-leitura. Linhas afetadas.
+    fn execute(&self, _sql: &str, _params: &[Value]) -> Result<usize> {
+        Err(Error::Unsupported("escrita".into()))
+    }
+
+    fn for_each(
+        &self,
+        sql:    &str,
+        params: &[Value],
+        f:      &mut dyn FnMut(&dyn Record) -> Result<()>,
+    ) -> Result<()>;
+}
+
+impl<'a> dyn Backend + 'a {
+    pub fn query<T>(
+        &self,
+        sql:     &str,
+        params:  &[Value],
+        mut map: impl FnMut(&dyn Record) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut out = Vec::new();
+        self.for_each(sql, params, &mut |r| {
+            out.push(map(r)?);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    pub fn query_opt<T>(
+        &self,
+        sql:    &str,
+        params: &[Value],
+        map:    impl FnMut(&dyn Record) -> Result<T>,
+    ) -> Result<Option<T>> {
+        Ok(self.query(sql, params, map)?.into_iter().next())
+    }
+
+    pub fn count(&self, sql: &str, params: &[Value]) -> Result<i64> {
+        Ok(self.query_opt(sql, params, |r| r.int(0))?.unwrap_or(0))
+    }
+}
+
+// ─── Seleção do backend ───────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum BackendConfig {
+    Sqlite { path: PathBuf },
+    Postgres { url: String },
+}
+
+pub fn open_backend(cfg: &BackendConfig) -> Result<Arc<dyn Backend>> {
+    Ok(match cfg {
+        BackendConfig::Sqlite { path } => Arc::new(SqliteBackend::open(path)?),
+        BackendConfig::Postgres { url } => Arc::new(PgBackend::connect(url)?),
+    })
+}
+
+// Estado gerenciado pelo Tauri: o backend é aberto sob demanda e descartado quando
+// a pasta de dados ou o banco mudam nas configurações.
+#[derive(Default)]
+pub struct BackendSlot {
+    backend: RwLock<Option<Arc<dyn Backend>>>,
+}
+
+impl BackendSlot {
+    pub fn get(&self) -> Option<Arc<dyn Backend>> {
+        self.backend.read().unwrap().clone()
+    }
+
+    pub fn get_or_open(&self, cfg: &BackendConfig) -> Result<Arc<dyn Backend>> {
+        self.get_or_open_with(cfg, |_| {})
+    }
+
+    // `init` roda uma única vez, logo após abrir (logs, índices do app), ainda
+    // sob o lock: chamadas concorrentes esperam e recebem o backend já pronto.
+    pub fn get_or_open_with(
+        &self,
+        cfg:  &BackendConfig,
+        init: impl FnOnce(&dyn Backend),
+    ) -> Result<Arc<dyn Backend>> {
+        let mut slot = self.backend.write().unwrap();
+        if let Some(backend) = slot.as_ref() {
+            return Ok(backend.clone());
+        }
+        let backend = open_backend(cfg)?;
+        init(backend.as_ref());
+        *slot = Some(backend.clone());
+        Ok(backend)
+    }
+
+    pub fn reset(&self) {
+        *self.backend.write().unwrap() = None;
+    }
+}
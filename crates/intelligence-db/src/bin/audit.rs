@@ -0,0 +1,50 @@
+// Auditoria de qualidade do intelligence.db pela linha de comando (mesmo relatório do catálogo):
+//
+//   cargo run -p intelligence-db --bin audit -- sqlite intelligence/data/intelligence.db --images intelligence
+//   cargo run -p intelligence-db --bin audit -- postgres "host=localhost user=ghost dbname=intelligence" --csv audit.csv
+//
+// Sem --json/--csv imprime o resumo por categoria. Sai com código 1 quando há problemas.
+
+use intelligence_db::audit::{self, ExportFormat};
+use intelligence_db::{backend::open_backend, BackendConfig};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let kind = args.next().unwrap_or_else(|| "sqlite".into());
+    let target = args.next().unwrap_or_else(|| "intelligence/data/intelligence.db".into());
+
+    let mut images_dir = None;
+    let mut output = None;
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{flag} sem valor"))?;
+        match flag.as_str() {
+            "--images" => images_dir = Some(PathBuf::from(value)),
+            "--json"   => output = Some((ExportFormat::Json, PathBuf::from(value))),
+            "--csv"    => output = Some((ExportFormat::Csv, PathBuf::from(value))),
+            _ => return Err(format!("opção desconhecida: {flag}").into()),
+        }
+    }
+
+    let cfg = match kind.as_str() {
+        "postgres" => BackendConfig::Postgres { url: target },
+        _ => BackendConfig::Sqlite { path: PathBuf::from(target) },
+    };
+    let db = open_backend(&cfg)?;
+    let report = audit::run(db.as_ref(), images_dir.as_deref())?;
+
+    println!("[audit] {} — {} indivíduos, {} problemas", report.database, report.individuals, report.total);
+    if report.images_dir.is_none() {
+        println!("[audit] sem --images: arquivos de imagem não verificados");
+    }
+    for category in &report.categories {
+        println!("[audit] {:<22} {}", category.kind.as_str(), category.count);
+    }
+    if let Some((format, path)) = output {
+        std::fs::write(&path, audit::export(&report, format))?;
+        println!("[audit] relatório salvo em {}", path.display());
+    }
+
+    Ok(if report.total == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
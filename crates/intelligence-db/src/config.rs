@@ -0,0 +1,78 @@
+// Seleção do banco nas configurações dos apps, com os mesmos overrides de
+// ambiente da ingestão Python (intelligence_db.py: DB_TYPE, DB_HOST, ...).
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::backend::BackendConfig;
+
+pub const ENV_DB_TYPE: &str = "DB_TYPE";
+pub const ENV_DB_HOST: &str = "DB_HOST";
+pub const ENV_DB_NAME: &str = "DB_NAME";
+pub const ENV_DB_USER: &str = "DB_USER";
+pub const ENV_DB_PASS: &str = "DB_PASS";
+pub const ENV_DB_PORT: &str = "DB_PORT";
+
+const PG_ENV: [(&str, &str); 5] = [
+    (ENV_DB_HOST, "localhost"),
+    (ENV_DB_PORT, "5432"),
+    (ENV_DB_NAME, "intelligence"),
+    (ENV_DB_USER, "ghost"),
+    (ENV_DB_PASS, "protocol"),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Sqlite,
+    Postgres,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct DatabaseSettings {
+    pub backend:      BackendKind,
+    pub postgres_url: Option<String>,
+}
+
+fn env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+impl DatabaseSettings {
+    // Variáveis de ambiente têm prioridade sobre o settings.json
+    pub fn with_env(mut self) -> Self {
+        match env(ENV_DB_TYPE).as_deref() {
+            Some("postgres") => self.backend = BackendKind::Postgres,
+            Some("sqlite") => self.backend = BackendKind::Sqlite,
+            _ => {}
+        }
+        if PG_ENV.iter().any(|(k, _)| env(k).is_some()) || self.postgres_url.is_none() {
+            let [host, port, name, user, pass] =
+                PG_ENV.map(|(k, default)| env(k).unwrap_or_else(|| default.to_string()));
+            self.postgres_url = Some(format!(
+                "host={host} port={port} dbname={name} user={user} password={pass}"
+            ));
+        }
+        self
+    }
+
+    pub fn env_overrides() -> Vec<String> {
+        std::iter::once(ENV_DB_TYPE)
+            .chain(PG_ENV.iter().map(|(k, _)| *k))
+            .filter(|k| env(k).is_some())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn backend_config(&self, sqlite_path: Option<PathBuf>) -> Option<BackendConfig> {
+        match self.backend {
+            BackendKind::Sqlite => sqlite_path.map(|path| BackendConfig::Sqlite { path }),
+            BackendKind::Postgres => self
+                .postgres_url
+                .clone()
+                .map(|url| BackendConfig::Postgres { url }),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Erro único da camada de dados (SQLite ou Postgres).
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("sqlite: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("postgres: {0}")]
+    Postgres(#[from] postgres::Error),
+
+    #[error("registro não encontrado: {0}")]
+    NotFound(String),
+
+    #[error("valor nulo inesperado na coluna {0}")]
+    UnexpectedNull(usize),
+
+    #[error("schema incompatível: tabela obrigatória ausente: {0}")]
+    MissingTable(String),
+
+    #[error("schema incompatível: coluna obrigatória ausente: {0}.{1}")]
+    MissingColumn(String, String),
+
+    #[error("não suportado neste backend: {0}")]
+    Unsupported(String),
+
+    #[error("parâmetro inválido `{0}`: {1}")]
+    InvalidInput(String, String),
+
+    #[error("sessão encerrada ou trancada: entre novamente")]
+    NotAuthenticated,
+
+    #[error("usuário ou senha inválidos")]
+    InvalidCredentials,
+
+    #[error("muitas tentativas de login: tente de novo em {0} s")]
+    TooManyAttempts(u64),
+
+    #[error("sem permissão: requer perfil {0}")]
+    Forbidden(String),
+
+    #[error("consulta inválida na posição {position}: {0}", position = .0.position)]
+    QuerySyntax(#[from] crate::query::ParseError),
+}
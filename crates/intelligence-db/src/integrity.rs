@@ -0,0 +1,465 @@
+// Integridade das evidências: cada arquivo de `evidence` é re-hasheado (sha256) e
+// comparado com `evidence.file_hash`, gravado pelo live_pipeline na captura.
+// O resultado de cada conferência e de cada rodada fica no sidecar integrity.db,
+// junto com o tamanho/mtime vistos no momento: se o arquivo mudar depois, o
+// avistamento volta a ser conferido antes de aparecer (nunca sai como válido sem hash).
+// Também volta a ser conferido se `file_hash`/`file_path` mudarem no banco e, em
+// todo caso, depois de `RECHECK_AFTER_HOURS` (edição com o mtime restaurado).
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::backend::{Backend, Value};
+use crate::error::Result;
+use crate::layout::EVIDENCE_DIR_CANDIDATES;
+use crate::model::{IntegrityStatus, Sighting};
+use crate::schema::EVIDENCE;
+use crate::sidecar;
+
+pub const INTEGRITY_FILE: &str = "integrity.db";
+
+const RECHECK_AFTER_HOURS: i64 = 24;
+
+const MIGRATIONS: &[&str] = &[
+    // file_size/file_mtime (ns): o arquivo como estava na conferência
+    "CREATE TABLE evidence_checks (
+         evidence_id   TEXT PRIMARY KEY,
+         file_path     TEXT NOT NULL,
+         resolved_path TEXT,
+         status        TEXT NOT NULL CHECK (status IN ('verified', 'modified', 'missing', 'unreadable')),
+         expected_hash TEXT NOT NULL,
+         actual_hash   TEXT,
+         detail        TEXT,
+         file_size     INTEGER,
+         file_mtime    INTEGER,
+         verified_at   TEXT NOT NULL
+     );
+     CREATE INDEX evidence_checks_status ON evidence_checks (status);
+     CREATE TABLE verification_runs (
+         id          INTEGER PRIMARY KEY AUTOINCREMENT,
+         started_at  TEXT NOT NULL,
+         finished_at TEXT NOT NULL,
+         checked     INTEGER NOT NULL,
+         verified    INTEGER NOT NULL,
+         modified    INTEGER NOT NULL,
+         missing     INTEGER NOT NULL,
+         unreadable  INTEGER NOT NULL
+     );",
+];
+
+#[derive(Serialize, Clone, Debug)]
+pub struct EvidenceCheck {
+    pub evidence_id:   String,
+    pub file_path:     String,
+    pub resolved_path: Option<PathBuf>,
+    pub status:        IntegrityStatus,
+    pub expected_hash: String,
+    pub actual_hash:   Option<String>,
+    pub detail:        Option<String>,
+    pub verified_at:   String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct VerificationRun {
+    pub started_at:  String,
+    pub finished_at: String,
+    pub checked:     i64,
+    pub verified:    i64,
+    pub modified:    i64,
+    pub missing:     i64,
+    pub unreadable:  i64,
+}
+
+// Última rodada + evidências que hoje não conferem
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct IntegrityReport {
+    pub last_run: Option<VerificationRun>,
+    pub failures: Vec<EvidenceCheck>,
+}
+
+// (tamanho, mtime em ns)
+type Fingerprint = (i64, i64);
+
+struct EvidenceFile {
+    id:        String,
+    file_path: String,
+    file_hash: String,
+}
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+fn now() -> String {
+    chrono::Local::now().format(TIMESTAMP_FORMAT).to_string()
+}
+
+fn expected_hash(file_hash: &str) -> String {
+    file_hash.trim().to_lowercase()
+}
+
+// Conferência antiga demais para confiar só no tamanho/mtime
+fn expired(verified_at: &str) -> bool {
+    chrono::NaiveDateTime::parse_from_str(verified_at, TIMESTAMP_FORMAT)
+        .map_or(true, |at| chrono::Local::now().naive_local() - at >= chrono::Duration::hours(RECHECK_AFTER_HOURS))
+}
+
+fn fingerprint(meta: &fs::Metadata) -> Fingerprint {
+    let modified = meta.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map_or(0, |d| d.as_nanos());
+    (meta.len() as i64, modified as i64)
+}
+
+// Caminho absoluto como está; relativo, procurado a partir da pasta de dados
+pub fn resolve(root: Option<&Path>, file_path: &str) -> Option<PathBuf> {
+    let path = Path::new(file_path.trim());
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let root = root?;
+    EVIDENCE_DIR_CANDIDATES.iter().map(|dir| root.join(dir).join(path)).find(|p| p.is_file())
+}
+
+fn load_evidence(db: &dyn Backend, ids: Option<&[String]>) -> Result<Vec<EvidenceFile>> {
+    let s = db.schema();
+    s.require_table(EVIDENCE)?;
+    let (filter, vals): (String, Vec<Value>) = match ids {
+        // `IN ()` nem é SQL válido: lista vazia não tem o que conferir
+        Some([]) => return Ok(vec![]),
+        Some(ids) => (
+            format!("WHERE e.id IN ({})", vec!["?"; ids.len()].join(", ")),
+            ids.iter().map(|id| id.as_str().into()).collect(),
+        ),
+        None => (String::new(), vec![]),
+    };
+    db.query(
+        &format!(
+            "SELECT e.id, {path}, {hash} FROM evidence e {filter} ORDER BY e.id",
+            path = s.text(EVIDENCE, "e", "file_path"),
+            hash = s.text(EVIDENCE, "e", "file_hash"),
+        ),
+        &vals,
+        |r| {
+            Ok(EvidenceFile {
+                id:        r.text(0)?,
+                file_path: r.opt_text(1)?.unwrap_or_default(),
+                file_hash: r.opt_text(2)?.unwrap_or_default(),
+            })
+        },
+    )
+}
+
+fn check(file: &EvidenceFile, root: Option<&Path>) -> (EvidenceCheck, Option<Fingerprint>) {
+    let mut out = EvidenceCheck {
+        evidence_id:   file.id.clone(),
+        file_path:     file.file_path.clone(),
+        resolved_path: None,
+        status:        IntegrityStatus::Missing,
+        expected_hash: expected_hash(&file.file_hash),
+        actual_hash:   None,
+        detail:        None,
+        verified_at:   now(),
+    };
+    let Some(path) = resolve(root, &file.file_path) else {
+        out.detail = Some("arquivo não encontrado".into());
+        return (out, None);
+    };
+    out.resolved_path = Some(path.clone());
+
+    let seen = fs::metadata(&path).ok().map(|m| fingerprint(&m));
+    let hashed = File::open(&path).and_then(|mut f| {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut f, &mut hasher)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    });
+    match hashed {
+        Ok(actual) => {
+            out.status = if !out.expected_hash.is_empty() && actual == out.expected_hash {
+                IntegrityStatus::Verified
+            } else {
+                IntegrityStatus::Modified
+            };
+            out.actual_hash = Some(actual);
+        }
+        Err(e) => {
+            out.status = IntegrityStatus::Unreadable;
+            out.detail = Some(e.to_string());
+        }
+    }
+    (out, seen)
+}
+
+fn save(conn: &mut Connection, checks: &[(EvidenceCheck, Option<Fingerprint>)]) -> Result<()> {
+    let tx = conn.transaction()?;
+    {
+        let mut upsert = tx.prepare(
+            "INSERT INTO evidence_checks
+                 (evidence_id, file_path, resolved_path, status, expected_hash, actual_hash, detail, file_size, file_mtime, verified_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT (evidence_id) DO UPDATE SET
+                 file_path = excluded.file_path, resolved_path = excluded.resolved_path, status = excluded.status,
+                 expected_hash = excluded.expected_hash, actual_hash = excluded.actual_hash, detail = excluded.detail,
+                 file_size = excluded.file_size, file_mtime = excluded.file_mtime, verified_at = excluded.verified_at",
+        )?;
+        for (c, seen) in checks {
+            upsert.execute(params![
+                c.evidence_id,
+                c.file_path,
+                c.resolved_path.as_ref().map(|p| p.to_string_lossy().into_owned()),
+                c.status.as_str(),
+                c.expected_hash,
+                c.actual_hash,
+                c.detail,
+                seen.map(|s| s.0),
+                seen.map(|s| s.1),
+                c.verified_at,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn map_check(r: &rusqlite::Row) -> rusqlite::Result<(EvidenceCheck, Option<Fingerprint>)> {
+    let status: String = r.get(3)?;
+    let size: Option<i64> = r.get(7)?;
+    let mtime: Option<i64> = r.get(8)?;
+    let check = EvidenceCheck {
+        evidence_id:   r.get(0)?,
+        file_path:     r.get(1)?,
+        resolved_path: r.get::<_, Option<String>>(2)?.map(PathBuf::from),
+        status:        IntegrityStatus::parse(&status),
+        expected_hash: r.get(4)?,
+        actual_hash:   r.get(5)?,
+        detail:        r.get(6)?,
+        verified_at:   r.get(9)?,
+    };
+    Ok((check, size.zip(mtime)))
+}
+
+const CHECK_COLUMNS: &str =
+    "evidence_id, file_path, resolved_path, status, expected_hash, actual_hash, detail, file_size, file_mtime, verified_at";
+
+// ─── Conferência ──────────────────────────────────────────────────────────────
+
+// Re-hasheia as evidências (todas, ou só `ids`) e registra a rodada
+pub fn verify(db: &dyn Backend, path: &Path, root: Option<&Path>, ids: Option<&[String]>) -> Result<IntegrityReport> {
+    let started_at = now();
+    let checks: Vec<_> = load_evidence(db, ids)?.iter().map(|f| check(f, root)).collect();
+
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    save(&mut conn, &checks)?;
+
+    let count = |status: IntegrityStatus| checks.iter().filter(|(c, _)| c.status == status).count() as i64;
+    let run = VerificationRun {
+        started_at,
+        finished_at: now(),
+        checked:     checks.len() as i64,
+        verified:    count(IntegrityStatus::Verified),
+        modified:    count(IntegrityStatus::Modified),
+        missing:     count(IntegrityStatus::Missing),
+        unreadable:  count(IntegrityStatus::Unreadable),
+    };
+    conn.execute(
+        "INSERT INTO verification_runs (started_at, finished_at, checked, verified, modified, missing, unreadable)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![run.started_at, run.finished_at, run.checked, run.verified, run.modified, run.missing, run.unreadable],
+    )?;
+    println!(
+        "[INTEGRITY] {} evidências: {} ok, {} alteradas, {} ausentes, {} ilegíveis",
+        run.checked, run.verified, run.modified, run.missing, run.unreadable
+    );
+
+    let failures = checks.into_iter().map(|(c, _)| c).filter(|c| c.status != IntegrityStatus::Verified).collect();
+    Ok(IntegrityReport { last_run: Some(run), failures })
+}
+
+pub fn report(path: &Path) -> Result<IntegrityReport> {
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let last_run = conn
+        .query_row(
+            "SELECT started_at, finished_at, checked, verified, modified, missing, unreadable
+             FROM verification_runs ORDER BY id DESC LIMIT 1",
+            [],
+            |r| {
+                Ok(VerificationRun {
+                    started_at:  r.get(0)?,
+                    finished_at: r.get(1)?,
+                    checked:     r.get(2)?,
+                    verified:    r.get(3)?,
+                    modified:    r.get(4)?,
+                    missing:     r.get(5)?,
+                    unreadable:  r.get(6)?,
+                })
+            },
+        )
+        .optional()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {CHECK_COLUMNS} FROM evidence_checks WHERE status <> 'verified' ORDER BY verified_at DESC, evidence_id"
+    ))?;
+    let failures = stmt.query_map([], map_check)?.map(|r| r.map(|(c, _)| c)).collect::<rusqlite::Result<_>>()?;
+    Ok(IntegrityReport { last_run, failures })
+}
+
+// Status de cada avistamento. Evidências nunca conferidas, cujo arquivo mudou
+// (tamanho/mtime), cujo hash/caminho no banco não é o da última conferência ou
+// conferidas há mais de `RECHECK_AFTER_HOURS` são re-hasheadas agora; as demais
+// custam só um stat.
+pub fn annotate(db: &dyn Backend, path: &Path, root: Option<&Path>, sightings: &mut [Sighting]) -> Result<()> {
+    let mut ids: Vec<String> = sightings.iter().map(|s| s.id.clone()).collect();
+    ids.sort();
+    ids.dedup();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    let mut known: HashMap<String, (EvidenceCheck, Option<Fingerprint>)> = HashMap::new();
+    {
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!("SELECT {CHECK_COLUMNS} FROM evidence_checks WHERE evidence_id IN ({placeholders})"))?;
+        for row in stmt.query_map(rusqlite::params_from_iter(&ids), map_check)? {
+            let (check, seen) = row?;
+            known.insert(check.evidence_id.clone(), (check, seen));
+        }
+    }
+
+    // Conferência anterior vale só para o mesmo caminho, hash e arquivo
+    let fresh = |file: &EvidenceFile| {
+        known.get(&file.id).is_some_and(|(check, seen)| {
+            let current = resolve(root, &file.file_path).and_then(|p| fs::metadata(p).ok()).map(|m| fingerprint(&m));
+            check.file_path == file.file_path
+                && check.expected_hash == expected_hash(&file.file_hash)
+                && current == *seen
+                && !expired(&check.verified_at)
+        })
+    };
+    let stale: Vec<EvidenceFile> = load_evidence(db, Some(&ids))?.into_iter().filter(|f| !fresh(f)).collect();
+    let mut known: HashMap<String, EvidenceCheck> = known.into_iter().map(|(id, (check, _))| (id, check)).collect();
+
+    if !stale.is_empty() {
+        let checks: Vec<_> = stale.iter().map(|f| check(f, root)).collect();
+        save(&mut conn, &checks)?;
+        for (check, _) in checks {
+            if check.status != IntegrityStatus::Verified {
+                println!("[INTEGRITY] {}: {} ({})", check.evidence_id, check.status.as_str(), check.file_path);
+            }
+            known.insert(check.evidence_id.clone(), check);
+        }
+    }
+
+    for s in sightings.iter_mut() {
+        if let Some(check) = known.get(&s.id) {
+            s.integrity = check.status;
+            s.verified_at = Some(check.verified_at.clone());
+        }
+    }
+    Ok(())
+}
+
+// Esquece as conferências de evidências que saíram do banco (ex.: expurgo da retenção)
+pub fn forget(path: &Path, ids: &[String]) -> Result<usize> {
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let mut removed = 0;
+    for id in ids {
+        removed += conn.execute("DELETE FROM evidence_checks WHERE evidence_id = ?1", [id])?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo;
+    use crate::testing::{temp_dir, Fixture};
+
+    fn sha256(content: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    // Dois avistamentos com arquivo (e1 absoluto, e2 relativo à pasta de dados)
+    fn setup(name: &str) -> (Fixture, PathBuf, PathBuf) {
+        let dir = temp_dir(&format!("integrity_{name}"));
+        fs::write(dir.join("e1.jpg"), b"frame um").unwrap();
+        fs::create_dir_all(dir.join("olho_de_deus")).unwrap();
+        fs::write(dir.join("olho_de_deus/e2.jpg"), b"frame dois").unwrap();
+        let seed = format!(
+            "INSERT INTO individuals (id, name, category, source) VALUES ('i1', 'Fulano', 'wanted', 'fbi');
+             INSERT INTO evidence (id, individual_id, file_hash, file_path, captured_at) VALUES
+                 ('e1', 'i1', '{}', '{}', '2026-01-02 10:00:00'),
+                 ('e2', 'i1', '{}', 'e2.jpg', '2026-01-01 10:00:00');",
+            sha256(b"frame um").to_uppercase(),
+            dir.join("e1.jpg").display(),
+            sha256(b"frame dois"),
+        );
+        (Fixture::new(&format!("integrity_{name}"), &seed), dir.join(INTEGRITY_FILE), dir)
+    }
+
+    fn statuses(f: &Fixture, sidecar: &Path, root: &Path) -> Vec<(String, IntegrityStatus)> {
+        let mut sightings = repo::recent_sightings(&f.db, 10, 0, None).unwrap();
+        annotate(&f.db, sidecar, Some(root), &mut sightings).unwrap();
+        sightings.into_iter().map(|s| (s.id, s.integrity)).collect()
+    }
+
+    #[test]
+    fn annotate_tracks_ok_changed_and_missing_files() {
+        let (f, sidecar, root) = setup("transitions");
+        let ok = IntegrityStatus::Verified;
+        assert_eq!(statuses(&f, &sidecar, &root), [("e1".into(), ok), ("e2".into(), ok)]);
+
+        // Tamanho novo: a conferência anterior não vale mais
+        fs::write(root.join("e1.jpg"), b"frame editado").unwrap();
+        fs::remove_file(root.join("olho_de_deus/e2.jpg")).unwrap();
+        assert_eq!(
+            statuses(&f, &sidecar, &root),
+            [("e1".into(), IntegrityStatus::Modified), ("e2".into(), IntegrityStatus::Missing)]
+        );
+
+        // Arquivo restaurado volta a conferir
+        fs::write(root.join("e1.jpg"), b"frame um").unwrap();
+        assert_eq!(statuses(&f, &sidecar, &root)[0], ("e1".into(), ok));
+        assert_eq!(report(&sidecar).unwrap().failures.len(), 1);
+    }
+
+    #[test]
+    fn annotate_rehashes_only_stale_checks() {
+        let (f, sidecar, root) = setup("freshness");
+        statuses(&f, &sidecar, &root);
+
+        // Mesmo tamanho e mtime restaurado: dentro do prazo vale a conferência anterior
+        let file = root.join("e1.jpg");
+        let mtime = fs::metadata(&file).unwrap().modified().unwrap();
+        fs::write(&file, b"frame 1m").unwrap();
+        File::options().write(true).open(&file).unwrap().set_modified(mtime).unwrap();
+        assert_eq!(statuses(&f, &sidecar, &root)[0].1, IntegrityStatus::Verified);
+
+        // Conferida há mais de RECHECK_AFTER_HOURS: re-hasheada
+        let old = (chrono::Local::now() - chrono::Duration::hours(RECHECK_AFTER_HOURS + 1)).format(TIMESTAMP_FORMAT).to_string();
+        let conn = sidecar::open(&sidecar, MIGRATIONS).unwrap();
+        conn.execute("UPDATE evidence_checks SET verified_at = ?1", [&old]).unwrap();
+        assert_eq!(statuses(&f, &sidecar, &root)[0].1, IntegrityStatus::Modified);
+        assert!(!expired(&now()));
+        assert!(expired(&old));
+    }
+
+    #[test]
+    fn hash_change_in_the_database_forces_a_recheck() {
+        let (f, sidecar, root) = setup("hash");
+        statuses(&f, &sidecar, &root);
+        f.conn.execute("UPDATE evidence SET file_hash = 'outro' WHERE id = 'e2'", []).unwrap();
+        assert_eq!(statuses(&f, &sidecar, &root)[1].1, IntegrityStatus::Modified);
+    }
+
+    #[test]
+    fn verify_with_no_ids_checks_nothing() {
+        let (f, sidecar, root) = setup("empty");
+        let report = verify(&f.db, &sidecar, Some(&root), Some(&[])).unwrap();
+        assert_eq!(report.last_run.unwrap().checked, 0);
+        assert_eq!(verify(&f.db, &sidecar, Some(&root), None).unwrap().last_run.unwrap().checked, 2);
+    }
+}
@@ -0,0 +1,128 @@
+// Introspecção do schema na abertura do banco. Bancos criados por versões antigas
+// da ingestão não têm algumas colunas/tabelas: as consultas trocam o que falta por
+// NULL, e só as tabelas/colunas obrigatórias impedem o uso do banco.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::backend::{Backend, Dialect};
+use crate::error::{Error, Result};
+use crate::schema::{
+    CRIMES, INDIVIDUAL_IMAGES, LOCATIONS, REQUIRED_COLUMNS, SCHEMA_LEVELS, THREAT_SCORES,
+};
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SchemaInfo {
+    pub version: u32,
+    pub latest:  u32,
+    // Tabelas/colunas opcionais ausentes ("threat_scores", "individuals.height_cm")
+    pub missing: Vec<String>,
+    #[serde(skip)]
+    tables:      BTreeMap<String, BTreeSet<String>>,
+}
+
+const SQLITE_COLUMNS_SQL: &str =
+    "SELECT m.name, p.name FROM sqlite_master m, pragma_table_info(m.name) p
+     WHERE m.type IN ('table', 'view')";
+
+const PG_COLUMNS_SQL: &str =
+    "SELECT CAST(table_name AS TEXT), CAST(column_name AS TEXT) FROM information_schema.columns
+     WHERE table_schema = current_schema()";
+
+impl SchemaInfo {
+    pub fn detect(db: &dyn Backend) -> Result<Self> {
+        let sql = match db.dialect() {
+            Dialect::Sqlite   => SQLITE_COLUMNS_SQL,
+            Dialect::Postgres => PG_COLUMNS_SQL,
+        };
+        let mut tables: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        db.for_each(sql, &[], &mut |r| {
+            tables.entry(r.text(0)?.to_lowercase()).or_default().insert(r.text(1)?.to_lowercase());
+            Ok(())
+        })?;
+
+        let mut info = SchemaInfo { tables, ..Default::default() };
+        info.check_required()?;
+
+        info.latest = SCHEMA_LEVELS.len() as u32;
+        for (level, features) in SCHEMA_LEVELS.iter().enumerate() {
+            let absent: Vec<String> = features.iter().filter(|f| !info.has_feature(f)).map(|f| f.to_string()).collect();
+            if absent.is_empty() && info.missing.is_empty() {
+                info.version = level as u32 + 1;
+            }
+            info.missing.extend(absent);
+        }
+        Ok(info)
+    }
+
+    fn check_required(&self) -> Result<()> {
+        for (table, columns) in REQUIRED_COLUMNS {
+            let Some(present) = self.tables.get(*table) else {
+                return Err(Error::MissingTable(table.to_string()));
+            };
+            if let Some(col) = columns.iter().find(|c| !present.contains(**c)) {
+                return Err(Error::MissingColumn(table.to_string(), col.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    // "tabela" ou "tabela.coluna"
+    fn has_feature(&self, feature: &str) -> bool {
+        match feature.split_once('.') {
+            Some((table, column)) => self.has_column(table, column),
+            None => self.has_table(feature),
+        }
+    }
+
+    pub fn has_table(&self, table: &str) -> bool {
+        self.tables.contains_key(table)
+    }
+
+    pub fn has_column(&self, table: &str, column: &str) -> bool {
+        self.tables.get(table).is_some_and(|cols| cols.contains(column))
+    }
+
+    pub fn require_table(&self, table: &str) -> Result<()> {
+        if self.has_table(table) { Ok(()) } else { Err(Error::MissingTable(table.to_string())) }
+    }
+
+    // ─── Expressões de coluna ─────────────────────────────────────────────────
+    // Coluna presente: `alias.coluna` (com o CAST que iguala os backends);
+    // ausente: NULL já tipado, para o Postgres e o mapper lerem o mesmo tipo.
+
+    pub fn text(&self, table: &str, alias: &str, column: &str) -> String {
+        if self.has_column(table, column) { format!("{alias}.{column}") } else { "CAST(NULL AS TEXT)".into() }
+    }
+
+    pub fn int(&self, table: &str, alias: &str, column: &str) -> String {
+        if self.has_column(table, column) { format!("{alias}.{column}") } else { "CAST(NULL AS BIGINT)".into() }
+    }
+
+    pub fn real(&self, table: &str, alias: &str, column: &str) -> String {
+        let expr = if self.has_column(table, column) { format!("{alias}.{column}") } else { "NULL".into() };
+        format!("CAST({expr} AS DOUBLE PRECISION)")
+    }
+
+    // Timestamps: TEXT no SQLite, TIMESTAMP no Postgres
+    pub fn timestamp(&self, table: &str, alias: &str, column: &str) -> String {
+        let expr = if self.has_column(table, column) { format!("{alias}.{column}") } else { "NULL".into() };
+        format!("CAST({expr} AS TEXT)")
+    }
+
+    pub fn has_crimes(&self) -> bool {
+        self.has_table(CRIMES)
+    }
+
+    pub fn has_locations(&self) -> bool {
+        self.has_table(LOCATIONS)
+    }
+
+    pub fn has_images(&self) -> bool {
+        self.has_table(INDIVIDUAL_IMAGES)
+    }
+
+    pub fn has_threat_scores(&self) -> bool {
+        self.has_column(THREAT_SCORES, "score")
+    }
+}
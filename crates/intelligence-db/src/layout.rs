@@ -0,0 +1,110 @@
+// Layout da pasta de dados compartilhada pelos apps (raiz do projeto ou pasta "achatada").
+
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+use crate::app_error::{AppError, AppResult};
+
+pub const DB_FILE_CANDIDATES:   [&str; 3] = ["intelligence/data/intelligence.db", "data/intelligence.db", "intelligence.db"];
+pub const CAMS_FILE_CANDIDATES: [&str; 2] = ["database/omni_cams.json", "omni_cams.json"];
+// Onde `evidence.file_path` relativo é procurado (os mesmos de verify_integrity.py)
+pub const EVIDENCE_DIR_CANDIDATES: [&str; 3] = ["", "olho_de_deus", "intelligence/data"];
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DataRootCheck {
+    pub root:      PathBuf,
+    pub db_file:   Option<PathBuf>,
+    pub cams_file: Option<PathBuf>,
+    pub valid:     bool,
+}
+
+fn find_in(root: &Path, candidates: &[&str]) -> Option<PathBuf> {
+    candidates.iter().map(|c| root.join(c)).find(|p| p.is_file())
+}
+
+pub fn check_data_root(root: &Path) -> DataRootCheck {
+    let db_file   = find_in(root, &DB_FILE_CANDIDATES);
+    let cams_file = find_in(root, &CAMS_FILE_CANDIDATES);
+    let valid     = db_file.is_some() && cams_file.is_some();
+    DataRootCheck { root: root.to_path_buf(), db_file, cams_file, valid }
+}
+
+// Sem configuração: procura a pasta de dados subindo a partir do diretório atual
+// (equivalente aos antigos caminhos relativos usados em `npm run tauri dev`).
+pub fn discover_data_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors().take(4).find(|p| check_data_root(p).valid).map(Path::to_path_buf)
+}
+
+// Arquivo de imagem (`img_path` relativo, como gravado pela ingestão) dentro da
+// pasta de imagens. Caminhos absolutos ou com `..` são recusados sem tocar no
+// disco; o destino real, com symlinks resolvidos, precisa continuar sob a raiz
+// canônica (PathNotAllowed), e o arquivo precisa existir (FileNotFound).
+pub fn confine(root: &Path, img_path: &str) -> AppResult<PathBuf> {
+    let relative = Path::new(img_path.trim());
+    if relative.as_os_str().is_empty() || relative.components().any(|c| !matches!(c, Component::Normal(_) | Component::CurDir)) {
+        return Err(AppError::PathNotAllowed(relative.to_path_buf()));
+    }
+
+    let root = root.canonicalize().map_err(|e| AppError::io(root, e))?;
+    let joined = root.join(relative);
+    let path = joined.canonicalize().map_err(|e| AppError::io(&joined, e))?;
+    if !path.starts_with(&root) {
+        return Err(AppError::PathNotAllowed(joined));
+    }
+    if !path.is_file() {
+        return Err(AppError::FileNotFound(joined));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pasta de imagens com uma foto, uma subpasta e um arquivo de fora ao lado
+    fn images_dir(name: &str) -> (PathBuf, PathBuf) {
+        let base = std::env::temp_dir().join(format!("layout_test_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let root = base.join("images");
+        std::fs::create_dir_all(root.join("fbi")).unwrap();
+        std::fs::write(root.join("fbi/a.jpg"), b"jpg").unwrap();
+        std::fs::write(base.join("secret.txt"), b"fora").unwrap();
+        (root, base)
+    }
+
+    #[test]
+    fn confine_accepts_files_under_the_root() {
+        let (root, _) = images_dir("ok");
+        let path = confine(&root, " fbi/./a.jpg ").unwrap();
+        assert_eq!(path, root.canonicalize().unwrap().join("fbi/a.jpg"));
+    }
+
+    #[test]
+    fn confine_rejects_parent_and_absolute_paths() {
+        let (root, base) = images_dir("reject");
+        for raw in ["../secret.txt", "fbi/../../secret.txt", "", "  "] {
+            assert!(matches!(confine(&root, raw), Err(AppError::PathNotAllowed(_))), "{raw:?}");
+        }
+        let absolute = base.join("secret.txt");
+        assert!(matches!(confine(&root, &absolute.to_string_lossy()), Err(AppError::PathNotAllowed(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn confine_rejects_symlinks_leaving_the_root() {
+        let (root, base) = images_dir("symlink");
+        std::os::unix::fs::symlink(base.join("secret.txt"), root.join("fbi/link.jpg")).unwrap();
+        std::os::unix::fs::symlink(&base, root.join("up")).unwrap();
+        assert!(matches!(confine(&root, "fbi/link.jpg"), Err(AppError::PathNotAllowed(_))));
+        assert!(matches!(confine(&root, "up/secret.txt"), Err(AppError::PathNotAllowed(_))));
+    }
+
+    #[test]
+    fn confine_reports_missing_files_and_directories() {
+        let (root, _) = images_dir("missing");
+        assert!(matches!(confine(&root, "fbi/b.jpg"), Err(AppError::FileNotFound(_))));
+        assert!(matches!(confine(&root, "fbi"), Err(AppError::FileNotFound(_))));
+        assert!(matches!(confine(&root.join("nada"), "fbi/a.jpg"), Err(AppError::FileNotFound(_))));
+    }
+}
@@ -0,0 +1,42 @@
+// intelligence-db — modelo de dados e repositório do intelligence.db,
+// compartilhado pelo catálogo e pelo dashboard (SQLite local ou Postgres).
+
+pub mod accounts;
+pub mod app_error;
+pub mod audit;
+pub mod audit_log;
+pub mod backend;
+pub mod config;
+pub mod error;
+pub mod integrity;
+pub mod introspect;
+pub mod layout;
+pub mod linkage;
+pub mod model;
+pub mod normalize;
+pub mod operator;
+pub mod pg;
+pub mod pool;
+pub mod query;
+pub mod repo;
+pub mod retention;
+pub mod review;
+pub mod schema;
+pub mod search;
+pub mod search_index;
+pub mod settings;
+pub mod sidecar;
+pub mod sqlite;
+
+#[cfg(test)]
+mod testing;
+
+pub use app_error::{AppError, AppResult};
+pub use backend::{Backend, BackendConfig, BackendSlot, Dialect, Record, Value};
+pub use config::{BackendKind, DatabaseSettings};
+pub use error::{Error, Result};
+pub use introspect::SchemaInfo;
+pub use model::*;
+pub use pool::ReadPool;
+pub use repo::{IngestionStatsFilter, LocationLevel, LocationValuesFilter};
+pub use search::{Facet, SearchFilter, SortKey};
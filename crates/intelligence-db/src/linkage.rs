@@ -0,0 +1,739 @@
+// Ligação de registros entre fontes: a mesma pessoa publicada pelo FBI, pela
+// Interpol e pela OpenSanctions vira linhas separadas em `individuals`. Aqui ficam
+// os candidatos a duplicata (nomes/aliases, nascimento, nacionalidade), a
+// comparação lado a lado e a decisão do analista ("same"/"different"), gravada
+// no sidecar linkage.db. No SQLite o sidecar é anexado às conexões de leitura
+// como `linkage` para a busca agrupar os registros ligados.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::backend::{Backend, Dialect, Value};
+use crate::error::{Error, Result};
+use crate::model::{IndividualDetail, LinkedRecord};
+use crate::normalize::{self, BirthDate};
+use crate::repo;
+use crate::schema::INDIVIDUALS;
+use crate::sidecar;
+
+pub const LINKAGE_FILE:  &str = "linkage.db";
+pub const LINKAGE_ALIAS: &str = "linkage";
+
+const MIGRATIONS: &[&str] = &[
+    // id_a < id_b; link_groups são os componentes das decisões "same" (group_id = menor id)
+    "CREATE TABLE link_decisions (
+         id_a       TEXT NOT NULL,
+         id_b       TEXT NOT NULL,
+         decision   TEXT NOT NULL CHECK (decision IN ('same', 'different')),
+         note       TEXT,
+         decided_at TEXT NOT NULL DEFAULT (datetime('now')),
+         PRIMARY KEY (id_a, id_b)
+     );
+     CREATE TABLE link_groups (id TEXT PRIMARY KEY, group_id TEXT NOT NULL);
+     CREATE INDEX link_groups_group ON link_groups (group_id);",
+];
+
+// Score mínimo (0..1) de um par para entrar num grupo de duplicatas
+pub const DEFAULT_MIN_SCORE: f64 = 0.85;
+pub const DEFAULT_CLUSTER_LIMIT: u32 = 100;
+// Blocos maiores que isso (prefixos muito comuns: "moha", "abdu") não geram pares
+const MAX_BLOCK: usize = 300;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Same,
+    Different,
+}
+
+impl Decision {
+    fn as_str(self) -> &'static str {
+        match self {
+            Decision::Same      => "same",
+            Decision::Different => "different",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "same"      => Some(Decision::Same),
+            "different" => Some(Decision::Different),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LinkDecision {
+    pub id_a:       String,
+    pub id_b:       String,
+    pub decision:   Decision,
+    pub note:       Option<String>,
+    pub decided_at: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Agreement {
+    Exact,
+    Partial,
+    Conflict,
+    Unknown,
+}
+
+// Por que um par parece a mesma pessoa
+#[derive(Serialize, Clone, Debug)]
+pub struct MatchEvidence {
+    pub score:       f64,
+    // Melhor similaridade entre nomes/aliases normalizados; 1.0 com alias idêntico
+    pub name:        f64,
+    pub alias_match: bool,
+    pub birth:       Agreement,
+    pub nationality: Agreement,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CandidatePair {
+    pub id_a:     String,
+    pub id_b:     String,
+    pub evidence: MatchEvidence,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ClusterMember {
+    pub id:             String,
+    pub name:           String,
+    pub source:         String,
+    pub birth_date_iso: Option<String>,
+    pub nationalities:  Vec<String>,
+    pub img_path:       Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DuplicateCluster {
+    pub score:   f64,
+    pub members: Vec<ClusterMember>,
+    pub pairs:   Vec<CandidatePair>,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct DuplicateFilter {
+    pub min_score:       Option<f64>,
+    // Só pares de fontes diferentes (padrão)
+    pub cross_source:    Option<bool>,
+    // Inclui pares que já têm decisão do analista
+    pub include_decided: Option<bool>,
+    pub limit:           Option<u32>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FieldComparison {
+    pub field:     &'static str,
+    pub left:      Option<String>,
+    pub right:     Option<String>,
+    pub agreement: Agreement,
+}
+
+// Dois registros lado a lado, com a evidência e a decisão atual
+#[derive(Serialize, Clone, Debug)]
+pub struct Comparison {
+    pub left:     IndividualDetail,
+    pub right:    IndividualDetail,
+    pub evidence: MatchEvidence,
+    pub fields:   Vec<FieldComparison>,
+    pub decision: Option<LinkDecision>,
+}
+
+// ─── Sidecar ──────────────────────────────────────────────────────────────────
+
+fn ordered<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn map_decision(r: &rusqlite::Row) -> rusqlite::Result<LinkDecision> {
+    let decision: String = r.get(2)?;
+    Ok(LinkDecision {
+        id_a:       r.get(0)?,
+        id_b:       r.get(1)?,
+        decision:   Decision::parse(&decision).unwrap_or(Decision::Different),
+        note:       r.get(3)?,
+        decided_at: r.get(4)?,
+    })
+}
+
+fn all_decisions(conn: &Connection) -> Result<Vec<LinkDecision>> {
+    let mut stmt = conn.prepare("SELECT id_a, id_b, decision, note, decided_at FROM link_decisions")?;
+    let rows = stmt.query_map([], map_decision)?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+fn decision(conn: &Connection, a: &str, b: &str) -> Result<Option<LinkDecision>> {
+    let (a, b) = ordered(a, b);
+    Ok(conn
+        .query_row(
+            "SELECT id_a, id_b, decision, note, decided_at FROM link_decisions WHERE id_a = ?1 AND id_b = ?2",
+            [a, b],
+            map_decision,
+        )
+        .optional()?)
+}
+
+// Recalcula link_groups a partir das decisões "same" (fechamento transitivo)
+fn rebuild_groups(conn: &mut Connection) -> Result<()> {
+    let same: Vec<(String, String)> = {
+        let mut stmt = conn.prepare("SELECT id_a, id_b FROM link_decisions WHERE decision = 'same'")?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    let mut sets = DisjointSets::default();
+    for (a, b) in &same {
+        sets.union(a, b);
+    }
+
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM link_groups", [])?;
+    {
+        let mut insert = tx.prepare("INSERT INTO link_groups (id, group_id) VALUES (?1, ?2)")?;
+        for group in sets.groups() {
+            let group_id = group.iter().min().cloned().unwrap_or_default();
+            for id in &group {
+                insert.execute(params![id, group_id])?;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// Abre (migra) o sidecar e, no SQLite, o anexa às conexões de leitura
+pub fn attach(db: &dyn Backend, path: &Path) -> Result<()> {
+    drop(sidecar::open(path, MIGRATIONS)?);
+    if db.dialect() == Dialect::Sqlite && !db.is_attached(LINKAGE_ALIAS) {
+        db.attach(LINKAGE_ALIAS, path)?;
+    }
+    Ok(())
+}
+
+// Grava (ou apaga, com `decision: None`) a decisão sobre o par
+pub fn decide(
+    db:       &dyn Backend,
+    path:     &Path,
+    a:        &str,
+    b:        &str,
+    decision: Option<Decision>,
+    note:     Option<&str>,
+) -> Result<Option<LinkDecision>> {
+    if a == b {
+        return Err(Error::InvalidInput("id_b".into(), "um registro não pode ser ligado a si mesmo".into()));
+    }
+    for id in [a, b] {
+        if db.count("SELECT COUNT(*) FROM individuals WHERE id = ?", &[id.into()])? == 0 {
+            return Err(Error::NotFound(id.to_string()));
+        }
+    }
+
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    let (id_a, id_b) = ordered(a, b);
+    match decision {
+        Some(d) => {
+            let note = note.map(str::trim).filter(|n| !n.is_empty());
+            conn.execute(
+                "INSERT INTO link_decisions (id_a, id_b, decision, note) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (id_a, id_b) DO UPDATE SET
+                     decision = excluded.decision, note = excluded.note, decided_at = datetime('now')",
+                params![id_a, id_b, d.as_str(), note],
+            )?;
+        }
+        None => {
+            conn.execute("DELETE FROM link_decisions WHERE id_a = ?1 AND id_b = ?2", [id_a, id_b])?;
+        }
+    }
+    rebuild_groups(&mut conn)?;
+    println!("[LINKAGE] {id_a} × {id_b}: {}", decision.map(Decision::as_str).unwrap_or("sem decisão"));
+
+    let current = self::decision(&conn, a, b)?;
+    drop(conn);
+    attach(db, path)?;
+    Ok(current)
+}
+
+// Outros registros do grupo de cada id (só com o sidecar anexado)
+pub fn linked_records(db: &dyn Backend, ids: &[String]) -> Result<HashMap<String, Vec<LinkedRecord>>> {
+    let mut out: HashMap<String, Vec<LinkedRecord>> = HashMap::new();
+    if ids.is_empty() || !db.is_attached(LINKAGE_ALIAS) {
+        return Ok(out);
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let vals: Vec<Value> = ids.iter().map(|id| id.as_str().into()).collect();
+    db.for_each(
+        &format!(
+            "SELECT g.id, o.id, o.name, o.source
+             FROM {LINKAGE_ALIAS}.link_groups g
+             JOIN {LINKAGE_ALIAS}.link_groups h ON h.group_id = g.group_id AND h.id <> g.id
+             JOIN individuals o ON o.id = h.id
+             WHERE g.id IN ({placeholders})
+             ORDER BY g.id, o.source, o.id"
+        ),
+        &vals,
+        &mut |r| {
+            let linked = LinkedRecord { id: r.text(1)?, name: r.text(2)?, source: r.text(3)? };
+            out.entry(r.text(0)?).or_default().push(linked);
+            Ok(())
+        },
+    )?;
+    Ok(out)
+}
+
+// ─── Candidatos ───────────────────────────────────────────────────────────────
+
+struct Candidate {
+    member:        ClusterMember,
+    keys:          Vec<String>,
+    birth:         Option<BirthDate>,
+    nationalities: HashSet<String>,
+}
+
+fn load_candidates(db: &dyn Backend) -> Result<Vec<Candidate>> {
+    let s = db.schema();
+    let t = |c: &str| s.text(INDIVIDUALS, "i", c);
+    let sql = format!(
+        "SELECT i.id, i.name, i.source, {}, {}, {}, {} FROM individuals i",
+        t("aliases"), t("birth_date"), t("nationalities"), t("img_path")
+    );
+    db.query(&sql, &[], |r| {
+        let name = r.text(1)?;
+        let keys = normalize::name_keys(&name, r.opt_text(3)?.as_deref());
+        let birth = r.opt_text(4)?.as_deref().and_then(normalize::birth_date);
+        let nationalities = r.opt_text(5)?.as_deref().map(normalize::json_list).unwrap_or_default();
+        Ok(Candidate {
+            keys,
+            birth,
+            nationalities: nationalities.iter().map(|n| n.to_lowercase()).collect(),
+            member: ClusterMember {
+                id: r.text(0)?,
+                name,
+                source: r.text(2)?,
+                birth_date_iso: birth.map(|b| b.iso()),
+                nationalities,
+                img_path: r.opt_text(6)?,
+            },
+        })
+    })
+}
+
+fn birth_agreement(a: Option<BirthDate>, b: Option<BirthDate>) -> Agreement {
+    match (a, b) {
+        (Some(a), Some(b)) if a.year != b.year => Agreement::Conflict,
+        (Some(a), Some(b)) => match (a.month.zip(a.day), b.month.zip(b.day)) {
+            (Some(x), Some(y)) if x == y => Agreement::Exact,
+            (Some(_), Some(_)) => Agreement::Conflict,
+            _ => Agreement::Partial,
+        },
+        _ => Agreement::Unknown,
+    }
+}
+
+fn evidence(a: &Candidate, b: &Candidate) -> MatchEvidence {
+    let alias_match = a.keys.iter().any(|k| b.keys.contains(k));
+    let name = if alias_match {
+        1.0
+    } else {
+        a.keys
+            .iter()
+            .flat_map(|x| b.keys.iter().map(move |y| normalize::similarity(x, y).min(normalize::similarity(y, x))))
+            .fold(0.0, f64::max)
+    };
+    let birth = birth_agreement(a.birth, b.birth);
+    let nationality = match (a.nationalities.is_empty(), b.nationalities.is_empty()) {
+        (false, false) if a.nationalities.is_disjoint(&b.nationalities) => Agreement::Conflict,
+        (false, false) => Agreement::Exact,
+        _ => Agreement::Unknown,
+    };
+
+    // Nome pesa mais; nascimento divergente derruba o par, igual confirma
+    let mut score = name;
+    score += match birth {
+        Agreement::Exact    => 0.15,
+        Agreement::Partial  => 0.05,
+        Agreement::Conflict => -0.35,
+        Agreement::Unknown  => 0.0,
+    };
+    score += match nationality {
+        Agreement::Exact    => 0.05,
+        Agreement::Conflict => -0.1,
+        _ => 0.0,
+    };
+    let score = (score.clamp(0.0, 1.0) * 1000.0).round() / 1000.0;
+    MatchEvidence { score, name, alias_match, birth, nationality }
+}
+
+// Pares comparados: registros que compartilham o prefixo (4 letras) de algum token do nome
+fn blocks(candidates: &[Candidate]) -> HashSet<(usize, usize)> {
+    let mut by_prefix: HashMap<String, Vec<usize>> = HashMap::new();
+    for (n, c) in candidates.iter().enumerate() {
+        let mut prefixes: Vec<String> = c
+            .keys
+            .iter()
+            .flat_map(|k| k.split(' '))
+            .filter(|t| t.len() >= 3)
+            .map(|t| t.chars().take(4).collect())
+            .collect();
+        prefixes.sort();
+        prefixes.dedup();
+        for p in prefixes {
+            by_prefix.entry(p).or_default().push(n);
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for members in by_prefix.values().filter(|m| m.len() > 1 && m.len() <= MAX_BLOCK) {
+        for (i, &a) in members.iter().enumerate() {
+            for &b in &members[i + 1..] {
+                pairs.insert((a, b));
+            }
+        }
+    }
+    pairs
+}
+
+// Grupos de prováveis duplicatas, do maior score para o menor. Pares com decisão
+// "different" nunca entram; com `include_decided` os já decididos "same" entram.
+pub fn find_duplicates(db: &dyn Backend, path: &Path, f: &DuplicateFilter) -> Result<Vec<DuplicateCluster>> {
+    let min_score = f.min_score.unwrap_or(DEFAULT_MIN_SCORE).clamp(0.0, 1.0);
+    let cross_source = f.cross_source.unwrap_or(true);
+    let include_decided = f.include_decided.unwrap_or(false);
+    let limit = f.limit.unwrap_or(DEFAULT_CLUSTER_LIMIT) as usize;
+
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let decided: HashMap<(String, String), Decision> = all_decisions(&conn)?
+        .into_iter()
+        .map(|d| ((d.id_a, d.id_b), d.decision))
+        .collect();
+    drop(conn);
+
+    let candidates = load_candidates(db)?;
+    let mut pairs = vec![];
+    for (a, b) in blocks(&candidates) {
+        let (ca, cb) = (&candidates[a], &candidates[b]);
+        if cross_source && ca.member.source.eq_ignore_ascii_case(&cb.member.source) {
+            continue;
+        }
+        let (id_a, id_b) = ordered(&ca.member.id, &cb.member.id);
+        match decided.get(&(id_a.to_string(), id_b.to_string())) {
+            Some(Decision::Different) => continue,
+            Some(Decision::Same) if !include_decided => continue,
+            _ => {}
+        }
+        let evidence = evidence(ca, cb);
+        if evidence.score >= min_score {
+            pairs.push((a, b, evidence));
+        }
+    }
+
+    let mut sets = DisjointSets::default();
+    for (a, b, _) in &pairs {
+        sets.union(&candidates[*a].member.id, &candidates[*b].member.id);
+    }
+    let by_id: HashMap<&str, &Candidate> = candidates.iter().map(|c| (c.member.id.as_str(), c)).collect();
+    let mut clusters: Vec<DuplicateCluster> = sets
+        .groups()
+        .into_iter()
+        .map(|ids| {
+            let mut members: Vec<ClusterMember> = ids.iter().filter_map(|id| by_id.get(id.as_str())).map(|c| c.member.clone()).collect();
+            members.sort_by(|a, b| a.source.cmp(&b.source).then_with(|| a.id.cmp(&b.id)));
+            DuplicateCluster { score: 0.0, members, pairs: vec![] }
+        })
+        .collect();
+    let cluster_of: HashMap<String, usize> = clusters
+        .iter()
+        .enumerate()
+        .flat_map(|(n, c)| c.members.iter().map(move |m| (m.id.clone(), n)))
+        .collect();
+    let mut grouped: Vec<Vec<CandidatePair>> = vec![vec![]; clusters.len()];
+    for (a, b, evidence) in pairs {
+        let (id_a, id_b) = ordered(&candidates[a].member.id, &candidates[b].member.id);
+        if let Some(&n) = cluster_of.get(id_a) {
+            grouped[n].push(CandidatePair { id_a: id_a.to_string(), id_b: id_b.to_string(), evidence });
+        }
+    }
+    for (cluster, mut pairs) in clusters.iter_mut().zip(grouped) {
+        pairs.sort_by(|a, b| b.evidence.score.total_cmp(&a.evidence.score));
+        cluster.score = pairs.first().map(|p| p.evidence.score).unwrap_or(0.0);
+        cluster.pairs = pairs;
+    }
+
+    clusters.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| b.members.len().cmp(&a.members.len())));
+    clusters.truncate(limit);
+    Ok(clusters)
+}
+
+// ─── Comparação ───────────────────────────────────────────────────────────────
+
+fn compare_text(field: &'static str, left: Option<String>, right: Option<String>) -> FieldComparison {
+    let agreement = match (&left, &right) {
+        (Some(l), Some(r)) if normalize::fold(l) == normalize::fold(r) => Agreement::Exact,
+        (Some(l), Some(r)) => {
+            let (l, r) = (normalize::fold(l), normalize::fold(r));
+            if !l.is_empty() && !r.is_empty() && (l.contains(&r) || r.contains(&l)) { Agreement::Partial } else { Agreement::Conflict }
+        }
+        _ => Agreement::Unknown,
+    };
+    FieldComparison { field, left, right, agreement }
+}
+
+fn compare_number(field: &'static str, left: Option<f64>, right: Option<f64>, tolerance: f64) -> FieldComparison {
+    let agreement = match (left, right) {
+        (Some(l), Some(r)) if l == r => Agreement::Exact,
+        (Some(l), Some(r)) if (l - r).abs() <= tolerance => Agreement::Partial,
+        (Some(_), Some(_)) => Agreement::Conflict,
+        _ => Agreement::Unknown,
+    };
+    FieldComparison { field, left: left.map(|v| v.to_string()), right: right.map(|v| v.to_string()), agreement }
+}
+
+fn list(raw: &Option<String>) -> Option<String> {
+    let mut values = raw.as_deref().map(normalize::json_list).unwrap_or_default();
+    values.sort();
+    if values.is_empty() { None } else { Some(values.join(", ")) }
+}
+
+pub fn compare(db: &dyn Backend, path: &Path, a: &str, b: &str) -> Result<Comparison> {
+    let left = repo::get_individual(db, a)?;
+    let right = repo::get_individual(db, b)?;
+    let candidate = |d: &IndividualDetail| {
+        let nationalities = d.nationalities.as_deref().map(normalize::json_list).unwrap_or_default();
+        Candidate {
+            keys:          normalize::name_keys(&d.name, d.aliases.as_deref()),
+            birth:         d.birth_date.as_deref().and_then(normalize::birth_date),
+            nationalities: nationalities.iter().map(|n| n.to_lowercase()).collect(),
+            member:        ClusterMember {
+                id:             d.id.clone(),
+                name:           d.name.clone(),
+                source:         d.source.clone(),
+                birth_date_iso: d.birth_date_iso.clone(),
+                nationalities,
+                img_path:       d.img_path.clone(),
+            },
+        }
+    };
+    let evidence = evidence(&candidate(&left), &candidate(&right));
+
+    let fields = vec![
+        compare_text("name", Some(left.name.clone()), Some(right.name.clone())),
+        compare_text("aliases", list(&left.aliases), list(&right.aliases)),
+        FieldComparison {
+            field:     "birth_date",
+            left:      left.birth_date_iso.clone(),
+            right:     right.birth_date_iso.clone(),
+            agreement: evidence.birth,
+        },
+        compare_text("sex", left.sex.clone(), right.sex.clone()),
+        FieldComparison {
+            field:     "nationalities",
+            left:      list(&left.nationalities),
+            right:     list(&right.nationalities),
+            agreement: evidence.nationality,
+        },
+        compare_number("height_cm", left.normalized.height_cm, right.normalized.height_cm, 5.0),
+        compare_number("weight_kg", left.normalized.weight_kg, right.normalized.weight_kg, 5.0),
+        compare_text("eye_color", left.normalized.eye_color.clone(), right.normalized.eye_color.clone()),
+        compare_text("hair_color", left.normalized.hair_color.clone(), right.normalized.hair_color.clone()),
+        compare_text("source", Some(left.source.clone()), Some(right.source.clone())),
+    ];
+
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let decision = decision(&conn, a, b)?;
+    Ok(Comparison { left, right, evidence, fields, decision })
+}
+
+// ─── Union-find ───────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct DisjointSets {
+    parent: HashMap<String, String>,
+}
+
+impl DisjointSets {
+    fn find(&mut self, id: &str) -> String {
+        let parent = self.parent.entry(id.to_string()).or_insert_with(|| id.to_string()).clone();
+        if parent == id {
+            return parent;
+        }
+        let root = self.find(&parent);
+        self.parent.insert(id.to_string(), root.clone());
+        root
+    }
+
+    fn union(&mut self, a: &str, b: &str) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent.insert(ra, rb);
+        }
+    }
+
+    // Conjuntos com dois ou mais ids
+    fn groups(mut self) -> Vec<Vec<String>> {
+        let ids: Vec<String> = self.parent.keys().cloned().collect();
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        for id in ids {
+            let root = self.find(&id);
+            groups.entry(root).or_default().push(id);
+        }
+        let mut out: Vec<Vec<String>> = groups.into_values().filter(|g| g.len() > 1).collect();
+        for g in &mut out {
+            g.sort();
+        }
+        out.sort();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{search_individuals, SearchFilter};
+    use crate::testing::{temp_dir, Fixture};
+    use std::path::PathBuf;
+
+    // a/b/c: a mesma pessoa em três fontes; d: homônimo de outra época
+    const SEED: &str = "
+        INSERT INTO individuals (id, name, aliases, category, source, birth_date, nationalities, sex) VALUES
+            ('a', 'José da Silva',  NULL,             'wanted', 'fbi',           '1975-03-04', '[\"Brazil\"]', 'male'),
+            ('b', 'Jose Silva',     '[\"Zé Silva\"]', 'wanted', 'interpol',      '04/03/1975', '[\"BRAZIL\"]', 'Male'),
+            ('c', 'Ze Silva',       NULL,             'wanted', 'opensanctions', '1975',       NULL,           NULL),
+            ('d', 'Jose Silva',     NULL,             'wanted', 'opensanctions', '1950-01-01', '[\"Portugal\"]', NULL);";
+
+    fn cluster_ids(clusters: &[DuplicateCluster]) -> Vec<Vec<&str>> {
+        let mut out: Vec<Vec<&str>> = clusters
+            .iter()
+            .map(|c| {
+                let mut ids: Vec<&str> = c.members.iter().map(|m| m.id.as_str()).collect();
+                ids.sort();
+                ids
+            })
+            .collect();
+        out.sort();
+        out
+    }
+
+    fn setup(name: &str) -> (Fixture, PathBuf) {
+        let f = Fixture::new(&format!("linkage_{name}"), SEED);
+        (f, temp_dir(&format!("linkage_{name}")).join(LINKAGE_FILE))
+    }
+
+    #[test]
+    fn birth_agreement_uses_the_source_precision() {
+        let b = |s: &str| normalize::birth_date(s);
+        assert_eq!(birth_agreement(b("1975-03-04"), b("1975-03-04")), Agreement::Exact);
+        assert_eq!(birth_agreement(b("1975-03-04"), b("1975")), Agreement::Partial);
+        assert_eq!(birth_agreement(b("1975-03-04"), b("1975-04-03")), Agreement::Conflict);
+        assert_eq!(birth_agreement(b("1975"), b("1950")), Agreement::Conflict);
+        assert_eq!(birth_agreement(b("1975"), None), Agreement::Unknown);
+    }
+
+    #[test]
+    fn duplicates_cross_sources_and_drop_conflicting_births() {
+        let (f, path) = setup("duplicates");
+        let clusters = find_duplicates(&f.db, &path, &DuplicateFilter::default()).unwrap();
+        assert_eq!(cluster_ids(&clusters), [vec!["a", "b", "c"]]);
+        let best = clusters[0].pairs.iter().map(|p| p.evidence.score).fold(0.0, f64::max);
+        assert_eq!(clusters[0].score, best);
+        let ab = clusters[0].pairs.iter().find(|p| (p.id_a.as_str(), p.id_b.as_str()) == ("a", "b")).unwrap();
+        assert_eq!(ab.evidence.birth, Agreement::Exact);
+
+        // Mesmo nome e nascimento na mesma fonte: par só com cross_source desligado
+        f.conn
+            .execute("INSERT INTO individuals (id, name, category, source, birth_date) VALUES ('e', 'José da Silva', 'wanted', 'FBI', '1975-03-04')", [])
+            .unwrap();
+        let pairs = |filter: DuplicateFilter| -> Vec<(String, String)> {
+            let clusters = find_duplicates(&f.db, &path, &filter).unwrap();
+            clusters.into_iter().flat_map(|c| c.pairs).map(|p| (p.id_a, p.id_b)).collect()
+        };
+        let same_source = ("a".to_string(), "e".to_string());
+        assert!(!pairs(DuplicateFilter::default()).contains(&same_source));
+        assert!(pairs(DuplicateFilter { cross_source: Some(false), ..Default::default() }).contains(&same_source));
+        let strict = find_duplicates(&f.db, &path, &DuplicateFilter { min_score: Some(1.0), ..Default::default() }).unwrap();
+        assert!(strict.iter().all(|c| c.pairs.iter().all(|p| p.evidence.score >= 1.0)));
+    }
+
+    #[test]
+    fn decisions_shape_groups_and_candidates() {
+        let (f, path) = setup("decisions");
+        assert!(matches!(decide(&f.db, &path, "a", "a", Some(Decision::Same), None), Err(Error::InvalidInput(..))));
+        assert!(matches!(decide(&f.db, &path, "a", "zz", Some(Decision::Same), None), Err(Error::NotFound(_))));
+
+        let saved = decide(&f.db, &path, "b", "a", Some(Decision::Same), Some("  mesma foto ")).unwrap().unwrap();
+        assert_eq!((saved.id_a.as_str(), saved.id_b.as_str(), saved.note.as_deref()), ("a", "b", Some("mesma foto")));
+        decide(&f.db, &path, "c", "b", Some(Decision::Different), None).unwrap();
+        decide(&f.db, &path, "a", "c", Some(Decision::Different), None).unwrap();
+        assert!(f.db.is_attached(LINKAGE_ALIAS));
+
+        // Par decidido sai dos candidatos; "different" não volta nem com include_decided
+        assert!(find_duplicates(&f.db, &path, &DuplicateFilter::default()).unwrap().is_empty());
+        let decided = find_duplicates(&f.db, &path, &DuplicateFilter { include_decided: Some(true), ..Default::default() }).unwrap();
+        assert_eq!(cluster_ids(&decided), [vec!["a", "b"]]);
+
+        // Grupos são transitivos e somem com a decisão apagada
+        decide(&f.db, &path, "b", "d", Some(Decision::Same), None).unwrap();
+        let linked = linked_records(&f.db, &["a".into(), "c".into()]).unwrap();
+        let ids: Vec<&str> = linked["a"].iter().map(|l| l.id.as_str()).collect();
+        assert_eq!(ids, ["b", "d"]);
+        assert!(!linked.contains_key("c"));
+        assert!(decide(&f.db, &path, "a", "b", None, None).unwrap().is_none());
+        let linked = linked_records(&f.db, &["a".into(), "b".into()]).unwrap();
+        assert!(!linked.contains_key("a"));
+        assert_eq!(linked["b"][0].id, "d");
+    }
+
+    #[test]
+    fn search_groups_linked_records() {
+        let (f, path) = setup("search");
+        decide(&f.db, &path, "a", "b", Some(Decision::Same), None).unwrap();
+        decide(&f.db, &path, "b", "c", Some(Decision::Same), None).unwrap();
+        let filter = SearchFilter { group_linked: Some(true), ..Default::default() };
+        let page = search_individuals(&f.db, &filter).unwrap();
+        let mut ids: Vec<&str> = page.items.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["a", "d"]);
+        assert_eq!(page.total, 2);
+
+        // Fora do filtro, o grupo aparece pelo próximo membro que passa
+        let filter = SearchFilter { source: Some("opensanctions".into()), ..filter };
+        let page = search_individuals(&f.db, &filter).unwrap();
+        let mut ids: Vec<&str> = page.items.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["c", "d"]);
+        let c = page.items.iter().find(|i| i.id == "c").unwrap();
+        assert_eq!(c.linked.iter().map(|l| l.id.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn compare_lines_up_fields_and_decision() {
+        let (f, path) = setup("compare");
+        let cmp = compare(&f.db, &path, "a", "b").unwrap();
+        assert!(cmp.decision.is_none());
+        assert!(!cmp.evidence.alias_match);
+        let agreement = |field: &str| cmp.fields.iter().find(|c| c.field == field).unwrap().agreement;
+        assert_eq!(agreement("birth_date"), Agreement::Exact);
+        assert_eq!(agreement("nationalities"), Agreement::Exact);
+        assert_eq!(agreement("sex"), Agreement::Exact);
+        assert_eq!(agreement("name"), Agreement::Conflict);
+        assert_eq!(agreement("aliases"), Agreement::Unknown);
+        assert_eq!(agreement("source"), Agreement::Conflict);
+
+        // Alias de um é o nome do outro
+        let cmp = compare(&f.db, &path, "b", "c").unwrap();
+        assert!(cmp.evidence.alias_match);
+        assert_eq!(cmp.evidence.name, 1.0);
+        assert_eq!(cmp.evidence.birth, Agreement::Partial);
+
+        decide(&f.db, &path, "a", "d", Some(Decision::Different), None).unwrap();
+        let cmp = compare(&f.db, &path, "d", "a").unwrap();
+        assert_eq!(cmp.decision.unwrap().decision, Decision::Different);
+        assert_eq!(cmp.evidence.birth, Agreement::Conflict);
+        assert!(matches!(compare(&f.db, &path, "a", "zz"), Err(Error::NotFound(_))));
+    }
+}
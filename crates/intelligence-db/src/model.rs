@@ -0,0 +1,291 @@
+// Tipos serializados para os frontends (catálogo e dashboard).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Individual {
+    pub id:              String,
+    pub name:            String,
+    pub category:        String,
+    pub source:          String,
+    pub birth_date:      Option<String>,
+    // Data normalizada com a precisão da fonte ("1980", "1980-03-04") e idade hoje
+    pub birth_date_iso:  Option<String>,
+    pub age:             Option<i64>,
+    pub nationalities:   Option<String>,
+    pub description:     Option<String>,
+    pub reward:          Option<String>,
+    pub img_path:        Option<String>,
+    pub has_embedding:   i32,
+    pub ingested_at:     Option<String>,
+    // Trecho com os termos da busca destacados (só com o índice FTS)
+    pub snippet:         Option<String>,
+    // Similaridade do nome com a busca (0..1, só na busca aproximada)
+    pub match_score:     Option<f64>,
+    // Outros registros da mesma pessoa, ligados pelo analista (linkage.rs)
+    #[serde(default)]
+    pub linked:          Vec<LinkedRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LinkedRecord {
+    pub id:     String,
+    pub name:   String,
+    pub source: String,
+}
+
+// Página de resultados da busca: `total` conta todos os que passam no filtro;
+// `next_cursor` (opaco) pede a página seguinte, None na última
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SearchPage {
+    pub items:       Vec<Individual>,
+    pub total:       i64,
+    pub next_cursor: Option<String>,
+    pub limit:       u32,
+    // Só na primeira página e quando pedidas (`facets: true`)
+    pub facets:      Option<Facets>,
+}
+
+// Contagens por valor, para o filtro atual sem o filtro da própria faceta
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Facets {
+    pub category:         Vec<FacetCount>,
+    pub source:           Vec<FacetCount>,
+    pub nationality:      Vec<FacetCount>,
+    pub crime:            Vec<FacetCount>,
+    pub location_country: Vec<FacetCount>,
+    pub has_images:       Vec<FacetCount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IndividualImage {
+    pub img_url:    Option<String>,
+    pub img_path:   Option<String>,
+    pub caption:    Option<String>,
+    pub is_primary: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IndividualDetail {
+    pub id:              String,
+    pub name:            String,
+    pub category:        String,
+    pub source:          String,
+    pub birth_date:      Option<String>,
+    pub birth_date_iso:  Option<String>,
+    pub age:             Option<i64>,
+    pub nationalities:   Option<String>,
+    pub description:     Option<String>,
+    pub reward:          Option<String>,
+    pub img_path:        Option<String>,
+    pub has_embedding:   i32,
+    pub aliases:         Option<String>,
+    pub sex:             Option<String>,
+    pub url:             Option<String>,
+    pub height_cm:       Option<f64>,
+    pub weight_kg:       Option<f64>,
+    pub eye_color:       Option<String>,
+    pub hair_color:      Option<String>,
+    pub occupation:      Option<String>,
+    // Altura/peso/cores acima como gravados pela fonte; aqui normalizados
+    pub normalized:      Descriptors,
+    pub images:          Vec<IndividualImage>,
+    pub crimes:          Vec<String>,
+    pub locations:       Vec<Location>,
+    pub ingested_at:     Option<String>,
+}
+
+// Descritores físicos em unidades métricas e cores pela chave canônica
+// ("brown", "blond"...), os mesmos valores usados nos filtros da busca
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Descriptors {
+    pub height_cm:  Option<f64>,
+    pub weight_kg:  Option<f64>,
+    pub eye_color:  Option<String>,
+    pub hair_color: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Location {
+    pub loc_type: String,
+    pub country:  Option<String>,
+    pub state:    Option<String>,
+    pub city:     Option<String>,
+    pub details:  Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Stats {
+    pub total:           i64,
+    pub wanted:          i64,
+    pub missing:         i64,
+    pub with_biometrics: i64,
+    pub by_source:       Vec<FacetCount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FacetCount { pub value: String, pub count: i64 }
+
+// Saúde da ingestão: linha do tempo, completude e registros parados por fonte
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IngestionStats {
+    pub days:              u32,
+    pub stale_days:        u32,
+    pub timeline:          Vec<TimelinePoint>,
+    pub completeness:      Completeness,
+    pub sources:           Vec<SourceHealth>,
+    pub top_crimes:        Vec<FacetCount>,
+    pub top_nationalities: Vec<FacetCount>,
+    pub stale:             i64,
+}
+
+// Registros ingeridos num dia (`YYYY-MM-DD`, de `ingested_at`) por uma fonte
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TimelinePoint {
+    pub day:    String,
+    pub source: String,
+    pub count:  i64,
+}
+
+// Percentual (0–100) de registros com o campo preenchido
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Completeness {
+    pub images:      f64,
+    pub birth_date:  f64,
+    pub description: f64,
+    pub locations:   f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SourceHealth {
+    pub source:        String,
+    pub total:         i64,
+    pub stale:         i64,
+    pub last_ingested: Option<String>,
+    pub last_refresh:  Option<String>,
+    pub completeness:  Completeness,
+}
+
+// Avistamento = evidência (frame salvo pelo live_pipeline) + indivíduo + score.
+// Nada é inventado: sem score calculado ou sem dados do match, os campos vêm
+// nulos e a confiança vem "unknown".
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Sighting {
+    pub id:                String,
+    pub individual_id:     String,
+    pub camera_id:         Option<String>,
+    pub captured_at:       String,
+    pub name:              String,
+    pub threat_score:      Option<f64>,
+    // threat_scores.factors_json como veio do ThreatScorer
+    pub threat_factors:    Option<serde_json::Value>,
+    pub file_hash:         Option<String>,
+    pub match_distance:    Option<f64>,
+    pub match_probability: Option<f64>,
+    pub match_confidence:  MatchConfidence,
+    pub model:             Option<String>,
+    pub model_version:     Option<String>,
+    // Preenchidos por integrity::annotate; sem verificação a evidência não é válida
+    #[serde(default)]
+    pub integrity:         IntegrityStatus,
+    #[serde(default)]
+    pub verified_at:       Option<String>,
+    // Preenchido por review::recent_sightings; sem decisão fica "pending"
+    #[serde(default)]
+    pub review:            SightingReview,
+}
+
+// Classe de confiança do match gravada pelo biometric_processor (HIGH/MEDIUM/LOW)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchConfidence {
+    High,
+    Medium,
+    Low,
+    #[default]
+    Unknown,
+}
+
+impl MatchConfidence {
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "HIGH"   => MatchConfidence::High,
+            "MEDIUM" => MatchConfidence::Medium,
+            "LOW"    => MatchConfidence::Low,
+            _ => MatchConfidence::Unknown,
+        }
+    }
+}
+
+// Revisão humana do avistamento (sidecar review.db)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    #[default]
+    Pending,
+    Confirmed,
+    Rejected,
+    Disputed,
+}
+
+impl ReviewStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReviewStatus::Pending   => "pending",
+            ReviewStatus::Confirmed => "confirmed",
+            ReviewStatus::Rejected  => "rejected",
+            ReviewStatus::Disputed  => "disputed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "confirmed" => ReviewStatus::Confirmed,
+            "rejected"  => ReviewStatus::Rejected,
+            "disputed"  => ReviewStatus::Disputed,
+            _ => ReviewStatus::Pending,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SightingReview {
+    pub status:      ReviewStatus,
+    pub reviewer:    Option<String>,
+    pub notes:       Option<String>,
+    pub reviewed_at: Option<String>,
+}
+
+// Resultado da conferência do arquivo de evidência contra evidence.file_hash
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    #[default]
+    Unverified,
+    Verified,
+    Modified,
+    Missing,
+    Unreadable,
+}
+
+impl IntegrityStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IntegrityStatus::Unverified => "unverified",
+            IntegrityStatus::Verified   => "verified",
+            IntegrityStatus::Modified   => "modified",
+            IntegrityStatus::Missing    => "missing",
+            IntegrityStatus::Unreadable => "unreadable",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "verified"   => IntegrityStatus::Verified,
+            "modified"   => IntegrityStatus::Modified,
+            "missing"    => IntegrityStatus::Missing,
+            "unreadable" => IntegrityStatus::Unreadable,
+            _ => IntegrityStatus::Unverified,
+        }
+    }
+}
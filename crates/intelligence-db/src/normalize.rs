@@ -0,0 +1,410 @@
+// Normalização de nomes para busca: fontes em vários alfabetos (Interpol, BNMP,
+// OpenSanctions) e interface em en/pt/ru. `fold` translitera para ASCII
+// (cirílico, árabe, chinês...), remove acentos e pontuação e põe em minúsculas:
+// "José Ramírez" -> "jose ramirez", "Иван Петров" -> "ivan petrov".
+
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::functions::FunctionFlags;
+use rusqlite::Connection;
+use serde::Serialize;
+
+// Score mínimo (0..1) para a busca aproximada considerar dois nomes variantes
+pub const FUZZY_THRESHOLD: f64 = 0.85;
+
+pub fn fold(s: &str) -> String {
+    let ascii = deunicode::deunicode(s).to_lowercase();
+    ascii
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Colunas de lista gravadas como JSON pela ingestão (aliases, nationalities);
+// texto que não é JSON conta como um valor só
+pub fn json_list(raw: &str) -> Vec<String> {
+    let values = match serde_json::from_str::<Vec<Option<String>>>(raw) {
+        Ok(list) => list.into_iter().flatten().collect(),
+        Err(_) => vec![raw.to_string()],
+    };
+    values.into_iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+}
+
+// Nomes e aliases de um registro, já normalizados (aliases vêm em JSON da ingestão)
+pub fn name_keys(name: &str, aliases: Option<&str>) -> Vec<String> {
+    let mut keys = vec![fold(name)];
+    if let Some(raw) = aliases {
+        keys.extend(json_list(raw).iter().map(|a| fold(a)));
+    }
+    keys.retain(|k| !k.is_empty());
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+// Similaridade entre dois nomes já normalizados, tolerante a ordem dos nomes
+// ("petrov ivan") e a busca por parte do nome ("petrov"): o melhor entre
+// Jaro-Winkler do nome inteiro com tokens ordenados e a média, por token da
+// busca, do token mais parecido do nome.
+pub fn similarity(name: &str, query: &str) -> f64 {
+    let name_tokens: Vec<&str> = name.split(' ').filter(|t| !t.is_empty()).collect();
+    let query_tokens: Vec<&str> = query.split(' ').filter(|t| !t.is_empty()).collect();
+    if name_tokens.is_empty() || query_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let sorted = |tokens: &[&str]| {
+        let mut t = tokens.to_vec();
+        t.sort_unstable();
+        t.join(" ")
+    };
+    let whole = strsim::jaro_winkler(&sorted(&name_tokens), &sorted(&query_tokens));
+
+    let per_token = query_tokens
+        .iter()
+        .map(|q| name_tokens.iter().map(|n| strsim::jaro_winkler(n, q)).fold(0.0, f64::max))
+        .sum::<f64>()
+        / query_tokens.len() as f64;
+
+    whole.max(per_token)
+}
+
+// Valor de uma recompensa em texto livre ("Up to $5 million", "R$ 250.000,00",
+// "$10,000"): o primeiro número, com o multiplicador escrito por extenso.
+pub fn reward_amount(s: &str) -> Option<f64> {
+    let lower = s.to_lowercase();
+    let start = lower.find(|c: char| c.is_ascii_digit())?;
+    let rest = &lower[start..];
+    let end = rest.find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.')).unwrap_or(rest.len());
+    let raw = rest[..end].trim_end_matches([',', '.']);
+
+    // Separador decimal: o último ',' ou '.' seguido de 1-2 dígitos; os outros são de milhar
+    let strip = |t: &str| t.replace([',', '.'], "");
+    let number = match raw.rfind([',', '.']) {
+        Some(p) if raw.len() - p - 1 <= 2 => format!("{}.{}", strip(&raw[..p]), &raw[p + 1..]),
+        _ => strip(raw),
+    };
+    let value: f64 = number.parse().ok()?;
+
+    let word = rest[end..].split_whitespace().next().unwrap_or("");
+    let multiplier = if word.starts_with("billion") || word.starts_with("bilh") {
+        1e9
+    } else if word.starts_with("million") || word.starts_with("milh") {
+        1e6
+    } else if word.starts_with("thousand") || word == "mil" {
+        1e3
+    } else {
+        1.0
+    };
+    Some(value * multiplier)
+}
+
+// ─── Data de nascimento ───────────────────────────────────────────────────────
+// Cada fonte grava num formato: "1975-03-12" (OpenSanctions, às vezes só "1975-03"
+// ou "1975"), "March 4, 1980" (FBI), "12/05/1990" (BNMP), "1975/03/12" (Interpol).
+
+// Data possivelmente parcial: ano sempre; mês e dia quando a fonte informa
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BirthDate {
+    pub year:  i32,
+    pub month: Option<u32>,
+    pub day:   Option<u32>,
+}
+
+impl BirthDate {
+    // Forma ISO com a precisão da fonte: "1980", "1980-03", "1980-03-04"
+    pub fn iso(&self) -> String {
+        match (self.month, self.day) {
+            (Some(m), Some(d)) => format!("{:04}-{m:02}-{d:02}", self.year),
+            (Some(m), None) => format!("{:04}-{m:02}", self.year),
+            _ => format!("{:04}", self.year),
+        }
+    }
+
+    // Primeiro dia do período ("1980" -> 1980-01-01): forma comparável usada nos
+    // filtros e na ordenação
+    pub fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+            .unwrap_or(NaiveDate::MIN)
+    }
+
+    // Idade completa em `today`, contada a partir de `start()`
+    pub fn age_on(&self, today: NaiveDate) -> Option<i64> {
+        let start = self.start();
+        let mut age = today.year() - start.year();
+        if (today.month(), today.day()) < (start.month(), start.day()) {
+            age -= 1;
+        }
+        (age >= 0).then_some(age as i64)
+    }
+}
+
+// Prefixos dos meses em en/pt/ru (depois de `fold`: "março" -> "marco", "мая" -> "maia")
+const MONTHS: &[&[&str]] = &[
+    &["jan", "ianv"],
+    &["feb", "fev"],
+    &["mar"],
+    &["apr", "abr"],
+    &["may", "mai", "maia"],
+    &["jun", "iiun"],
+    &["jul", "iiul"],
+    &["aug", "ago", "avg"],
+    &["sep", "set", "sen"],
+    &["oct", "out", "okt"],
+    &["nov", "noia"],
+    &["dec", "dez", "dek"],
+];
+
+fn month_name(word: &str) -> Option<u32> {
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|prefixes| prefixes.iter().any(|p| word.starts_with(p)))
+        .map(|n| n as u32 + 1)
+}
+
+// Data em texto livre. Números sem nome de mês: ano primeiro é ano-mês-dia; ano
+// no fim é dia/mês/ano, salvo quando o segundo número passa de 12 (mês/dia/ano).
+// Mês ou dia inválido é descartado, ficando a data parcial.
+pub fn birth_date(s: &str) -> Option<BirthDate> {
+    let folded = fold(s);
+    let numbers: Vec<&str> = s.split(|c: char| !c.is_ascii_digit()).filter(|n| !n.is_empty()).collect();
+    let year_at = numbers
+        .iter()
+        .position(|n| n.len() == 4 && n.parse::<i32>().is_ok_and(|y| (1900..=2100).contains(&y)))?;
+    let year: i32 = numbers[year_at].parse().ok()?;
+    let num = |n: Option<&&str>| n.filter(|n| n.len() <= 2).and_then(|n| n.parse::<u32>().ok());
+
+    let (month, day) = match folded.split(' ').find_map(month_name) {
+        Some(month) => {
+            let day = numbers.iter().enumerate().find(|(n, _)| *n != year_at).and_then(|(_, d)| num(Some(d)));
+            (Some(month), day)
+        }
+        None if year_at == 0 => (num(numbers.get(1)), num(numbers.get(2))),
+        None => match (year_at.checked_sub(2).map(|n| numbers.get(n)), num(numbers.get(year_at - 1))) {
+            (Some(first), Some(second)) => match num(first) {
+                Some(first) if second > 12 && first <= 12 => (Some(first), Some(second)),
+                first => (Some(second), first),
+            },
+            (None, month) => (month, None),
+            (Some(_), None) => (None, None),
+        },
+    };
+
+    let month = month.filter(|m| (1..=12).contains(m));
+    let day = day.filter(|d| month.is_some_and(|m| NaiveDate::from_ymd_opt(year, m, *d).is_some()));
+    Some(BirthDate { year, month, day })
+}
+
+// Ano de nascimento em texto livre (ver `birth_date`)
+pub fn birth_year(s: &str) -> Option<i64> {
+    birth_date(s).map(|d| d.year as i64)
+}
+
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+// Data mais recente de nascimento para ter `age` anos em `today` (filtro de idade)
+pub fn born_by(today: NaiveDate, age: u32) -> NaiveDate {
+    today.checked_sub_months(Months::new(age.saturating_mul(12))).unwrap_or(NaiveDate::MIN)
+}
+
+// ─── Descritores físicos ──────────────────────────────────────────────────────
+// `height_cm`/`weight_kg` vêm como número solto: fontes americanas informam
+// polegadas (ou pés+polegadas colados, 5'10" -> 510) e libras; as outras,
+// centímetros (ou metros) e quilos.
+
+pub const IMPERIAL_SOURCES: &[&str] = &["FBI", "US Marshals", "Phoenix Open Data"];
+
+fn imperial(source: &str) -> bool {
+    IMPERIAL_SOURCES.iter().any(|s| s.eq_ignore_ascii_case(source))
+}
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+pub fn height_cm(value: f64, source: &str) -> Option<f64> {
+    let cm = if value <= 0.0 {
+        return None;
+    } else if value < 3.0 {
+        value * 100.0
+    } else if (300.0..900.0).contains(&value) && value % 100.0 < 12.0 {
+        (value / 100.0).floor() * 30.48 + (value % 100.0) * 2.54
+    } else if imperial(source) && value < 100.0 {
+        value * 2.54
+    } else {
+        value
+    };
+    (40.0..=250.0).contains(&cm).then(|| round1(cm))
+}
+
+pub fn weight_kg(value: f64, source: &str) -> Option<f64> {
+    let kg = if imperial(source) { value * 0.453_592_37 } else { value };
+    (2.0..=400.0).contains(&kg).then(|| round1(kg))
+}
+
+// Cores de olhos/cabelo: chave canônica em inglês a partir de texto em en/pt/ru
+// ou dos códigos da Interpol ("BRO", "BLA"). Primeiro casamento vence, então
+// "gray" vem antes de "green" e "salt and pepper" conta como grisalho.
+const COLORS: &[(&str, &[&str], &[&str])] = &[
+    // (chave, prefixos de palavra, códigos exatos)
+    ("gray",   &["gray", "grey", "gris", "cinz", "salt", "sed", "ser"], &["gry"]),
+    ("black",  &["black", "pret", "negr", "chern"],                    &["bla"]),
+    ("brown",  &["brown", "castanh", "marr", "kar", "korichn"],        &["bro"]),
+    ("blue",   &["blue", "azu", "golub", "sin"],                       &["blu"]),
+    ("green",  &["green", "verd", "zelen"],                            &["gre"]),
+    ("hazel",  &["hazel", "mel", "orekh"],                             &["haz"]),
+    ("blond",  &["blond", "loir", "lour", "sandy", "svetl"],           &["blo", "yel"]),
+    ("red",    &["red", "ruiv", "auburn", "ryzh"],                     &["red", "aub"]),
+    ("white",  &["white", "branc", "bel"],                             &["whi"]),
+    ("bald",   &["bald", "careca", "lys"],                             &["bal"]),
+];
+
+pub fn color(s: &str) -> Option<&'static str> {
+    let folded = fold(s);
+    let words: Vec<&str> = folded.split(' ').filter(|w| !w.is_empty()).collect();
+    words.iter().find_map(|w| {
+        COLORS
+            .iter()
+            .find(|(_, prefixes, codes)| codes.contains(w) || prefixes.iter().any(|p| w.starts_with(p)))
+            .map(|(key, _, _)| *key)
+    })
+}
+
+// Regex (Postgres, `~*`) com as variantes de uma chave de `color`; sem
+// transliteração, casa só o texto em alfabeto latino
+pub fn color_pattern(key: &str) -> Option<String> {
+    let (_, prefixes, codes) = COLORS.iter().find(|(k, _, _)| *k == key)?;
+    let prefixes = prefixes.iter().map(|p| p.to_string());
+    let codes = codes.iter().map(|c| format!("{c}\\M"));
+    Some(format!("\\m({})", prefixes.chain(codes).collect::<Vec<_>>().join("|")))
+}
+
+// Funções SQL para as conexões de leitura: `fold_name(texto)`,
+// `name_similarity(nome_normalizado, busca_normalizada)`, `reward_amount(texto)`,
+// `birth_year(texto)`, `birth_date(texto)` (AAAA-MM-DD, ver `BirthDate::start`),
+// `height_cm(valor, fonte)`, `weight_kg(valor, fonte)` e `color_key(texto)`
+pub fn register_sql_functions(conn: &Connection) -> rusqlite::Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    conn.create_scalar_function("fold_name", 1, flags, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.map(|s| fold(&s)))
+    })?;
+    conn.create_scalar_function("name_similarity", 2, flags, |ctx| {
+        let name: Option<String> = ctx.get(0)?;
+        let query: Option<String> = ctx.get(1)?;
+        Ok(match (name, query) {
+            (Some(n), Some(q)) => similarity(&n, &q),
+            _ => 0.0,
+        })
+    })?;
+    conn.create_scalar_function("reward_amount", 1, flags, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.and_then(|s| reward_amount(&s)))
+    })?;
+    conn.create_scalar_function("birth_year", 1, flags, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.and_then(|s| birth_year(&s)))
+    })?;
+    conn.create_scalar_function("birth_date", 1, flags, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.and_then(|s| birth_date(&s)).map(|d| d.start().to_string()))
+    })?;
+    conn.create_scalar_function("height_cm", 2, flags, |ctx| {
+        let source: Option<String> = ctx.get(1)?;
+        Ok(ctx.get::<Option<f64>>(0)?.and_then(|v| height_cm(v, source.as_deref().unwrap_or_default())))
+    })?;
+    conn.create_scalar_function("weight_kg", 2, flags, |ctx| {
+        let source: Option<String> = ctx.get(1)?;
+        Ok(ctx.get::<Option<f64>>(0)?.and_then(|v| weight_kg(v, source.as_deref().unwrap_or_default())))
+    })?;
+    conn.create_scalar_function("color_key", 1, flags, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.and_then(|s| color(&s)))
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+        birth_date(s).map(|d| (d.year, d.month, d.day))
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn birth_date_formats_of_each_source() {
+        assert_eq!(date("1975-03-12"), Some((1975, Some(3), Some(12))));
+        assert_eq!(date("1975-03"), Some((1975, Some(3), None)));
+        assert_eq!(date("1975"), Some((1975, None, None)));
+        assert_eq!(date("March 4, 1980"), Some((1980, Some(3), Some(4))));
+        assert_eq!(date("12 de março de 1980"), Some((1980, Some(3), Some(12))));
+        assert_eq!(date("1975/03/12"), Some((1975, Some(3), Some(12))));
+        assert_eq!(date("n/a"), None);
+        assert_eq!(date("1850"), None);
+    }
+
+    #[test]
+    fn ambiguous_day_month_year() {
+        // Ano no fim: dia/mês/ano, salvo quando o segundo número passa de 12
+        assert_eq!(date("12/05/1990"), Some((1990, Some(5), Some(12))));
+        assert_eq!(date("05/13/1990"), Some((1990, Some(5), Some(13))));
+        // Mês ou dia inválido fica de fora, a data parcial continua
+        assert_eq!(date("31/02/1980"), Some((1980, Some(2), None)));
+        assert_eq!(date("13/13/1990"), Some((1990, None, None)));
+    }
+
+    #[test]
+    fn partial_dates_compare_from_the_start_of_the_period() {
+        let d = birth_date("1980").unwrap();
+        assert_eq!((d.iso(), d.start()), ("1980".to_string(), ymd(1980, 1, 1)));
+        assert_eq!(birth_date("1980-03").unwrap().iso(), "1980-03");
+        assert_eq!(birth_date("1980-03-04").unwrap().age_on(ymd(2020, 3, 3)), Some(39));
+        assert_eq!(birth_date("1980-03-04").unwrap().age_on(ymd(2020, 3, 4)), Some(40));
+        assert_eq!(born_by(ymd(2024, 2, 29), 1), ymd(2023, 2, 28));
+    }
+
+    #[test]
+    fn height_units() {
+        assert_eq!(height_cm(180.0, "Interpol"), Some(180.0));
+        assert_eq!(height_cm(1.8, "BNMP"), Some(180.0));
+        // 5'10" gravado como 510, ou polegadas nas fontes americanas
+        assert_eq!(height_cm(510.0, "FBI"), Some(177.8));
+        assert_eq!(height_cm(70.0, "FBI"), Some(177.8));
+        assert_eq!(height_cm(70.0, "Interpol"), Some(70.0));
+        assert_eq!(height_cm(0.0, "FBI"), None);
+        assert_eq!(height_cm(999.0, "Interpol"), None);
+    }
+
+    #[test]
+    fn weight_units() {
+        assert_eq!(weight_kg(180.0, "FBI"), Some(81.6));
+        assert_eq!(weight_kg(80.0, "Interpol"), Some(80.0));
+        assert_eq!(weight_kg(1000.0, "Interpol"), None);
+        assert_eq!(weight_kg(1.0, "Interpol"), None);
+    }
+
+    #[test]
+    fn colors_from_text_and_codes() {
+        assert_eq!(color("Brown"), Some("brown"));
+        assert_eq!(color("BRO"), Some("brown"));
+        assert_eq!(color("castanho escuro"), Some("brown"));
+        assert_eq!(color("Loiro"), Some("blond"));
+        assert_eq!(color("salt and pepper"), Some("gray"));
+        assert_eq!(color("grey-green"), Some("gray"));
+        assert_eq!(color("unknown"), None);
+        assert_eq!(color(""), None);
+    }
+
+    #[test]
+    fn reward_amounts() {
+        assert_eq!(reward_amount("Up to $5 million"), Some(5e6));
+        assert_eq!(reward_amount("R$ 250.000,00"), Some(250_000.0));
+        assert_eq!(reward_amount("$10,000"), Some(10_000.0));
+        assert_eq!(reward_amount("none"), None);
+    }
+}
@@ -0,0 +1,397 @@
+// Login, tela de bloqueio, contas dos operadores e supervisão do log de auditoria,
+// iguais no catálogo e no dashboard: o SettingsStore comum (settings.rs) implementa
+// `OperatorStore` e `auth_commands!` gera os comandos Tauri de cada app. O perfil é
+// conferido dentro de cada comando (`require`), não só na interface. Entradas,
+// saídas, tentativas falhas e mudanças de conta vão para o log de auditoria.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::accounts::{self, Account, AccountUpdate, Role, Sessions};
+use crate::app_error::{AppError, AppResult};
+use crate::audit::ExportFormat;
+use crate::audit_log::{self, AuditEvent, AuditFilter, ChainReport};
+use crate::error::Error;
+
+// Onde cada app guarda contas e log, e o tempo de bloqueio configurado
+pub trait OperatorStore {
+    // Contas dos operadores; com OSS_ACCOUNTS_DB os dois apps usam as mesmas contas
+    fn accounts_file(&self) -> PathBuf;
+
+    // Log de auditoria; com OSS_AUDIT_DB os dois apps podem gravar na mesma cadeia
+    fn audit_file(&self) -> PathBuf;
+
+    fn idle_lock(&self) -> Duration;
+
+    // Conta logada com pelo menos `role` (cada comando chama antes de tudo)
+    fn require(&self, sessions: &Sessions, role: Role) -> AppResult<Account> {
+        Ok(sessions.require(&self.accounts_file(), role, self.idle_lock())?)
+    }
+
+    // `actor`: conta logada (ou o nome digitado, num login que falhou)
+    fn record(&self, actor: &str, action: &str, target: Option<&str>, detail: &serde_json::Value) -> AppResult<()> {
+        audit_log::record(&self.audit_file(), actor, action, target, detail)?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionStatus {
+    // Nenhuma conta ainda: a interface pede o primeiro admin
+    pub setup_required:    bool,
+    pub account:           Option<Account>,
+    pub locked_user:       Option<String>,
+    pub idle_lock_minutes: u64,
+}
+
+pub fn status(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<SessionStatus> {
+    Ok(SessionStatus {
+        setup_required:    accounts::is_empty(&store.accounts_file())?,
+        account:           sessions.current(store.idle_lock()),
+        locked_user:       sessions.locked_user(),
+        idle_lock_minutes: store.idle_lock().as_secs() / 60,
+    })
+}
+
+// ─── Sessão ───────────────────────────────────────────────────────────────────
+
+pub fn setup_admin(store: &impl OperatorStore, sessions: &Sessions, username: &str, password: &str) -> AppResult<SessionStatus> {
+    let account = accounts::create_first_admin(&store.accounts_file(), username, password)?;
+    store.record(&account.username, "account_setup", Some(&account.username), &serde_json::json!({}))?;
+    sessions.start(account);
+    status(store, sessions)
+}
+
+// Falhas seguidas do mesmo usuário impõem uma espera crescente (`Sessions::login_failed`);
+// durante ela a senha nem é conferida
+pub fn login(store: &impl OperatorStore, sessions: &Sessions, username: &str, password: &str) -> AppResult<SessionStatus> {
+    let result = match sessions.login_wait(username) {
+        Some(wait) => Err(Error::TooManyAttempts(wait.as_secs_f64().ceil() as u64)),
+        None => accounts::authenticate(&store.accounts_file(), username, password),
+    };
+    match result {
+        Ok(account) => {
+            store.record(&account.username, "login", None, &serde_json::json!({}))?;
+            sessions.start(account);
+            status(store, sessions)
+        }
+        Err(e) => {
+            if matches!(e, Error::InvalidCredentials) {
+                sessions.login_failed(username);
+            }
+            // O operador recebe o erro do login mesmo se o log falhar
+            let detail = serde_json::json!({ "error": e.to_string() });
+            if let Err(audit) = store.record(username.trim(), "login_failed", None, &detail) {
+                println!("[ACCOUNTS] login_failed de {} não registrado: {}", username.trim(), audit);
+            }
+            Err(e.into())
+        }
+    }
+}
+
+pub fn logout(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<SessionStatus> {
+    if let Some(account) = sessions.end() {
+        store.record(&account.username, "logout", None, &serde_json::json!({}))?;
+    }
+    status(store, sessions)
+}
+
+pub fn lock_session(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<SessionStatus> {
+    if let Some(account) = sessions.lock() {
+        store.record(&account.username, "lock", None, &serde_json::json!({}))?;
+    }
+    status(store, sessions)
+}
+
+// Atividade do operador: adia o bloqueio por inatividade
+pub fn touch_session(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<Account> {
+    Ok(sessions.touch(store.idle_lock())?)
+}
+
+// A senha atual passa pela mesma espera do login: a sessão aberta não serve para
+// testar senhas sem limite
+pub fn change_password(store: &impl OperatorStore, sessions: &Sessions, current: &str, new: &str) -> AppResult<()> {
+    let account = store.require(sessions, Role::Viewer)?;
+    if let Some(wait) = sessions.login_wait(&account.username) {
+        return Err(Error::TooManyAttempts(wait.as_secs_f64().ceil() as u64).into());
+    }
+    match accounts::change_password(&store.accounts_file(), &account.username, current, new) {
+        Err(Error::InvalidCredentials) => {
+            sessions.login_failed(&account.username);
+            return Err(AppError::InvalidCredentials);
+        }
+        other => other?,
+    }
+    store.record(&account.username, "change_password", Some(&account.username), &serde_json::json!({}))?;
+    Ok(())
+}
+
+// ─── Contas (admin) ───────────────────────────────────────────────────────────
+
+pub fn list_accounts(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<Vec<Account>> {
+    store.require(sessions, Role::Admin)?;
+    Ok(accounts::list(&store.accounts_file())?)
+}
+
+pub fn create_account(
+    store:    &impl OperatorStore,
+    sessions: &Sessions,
+    username: &str,
+    password: &str,
+    role:     Role,
+) -> AppResult<Account> {
+    let admin = store.require(sessions, Role::Admin)?;
+    let account = accounts::create(&store.accounts_file(), username, password, role)?;
+    let detail = serde_json::json!({ "role": role });
+    store.record(&admin.username, "create_account", Some(&account.username), &detail)?;
+    Ok(account)
+}
+
+// A senha nova (reset) nunca vai para o log, só o fato de ter sido trocada
+pub fn update_account(
+    store:    &impl OperatorStore,
+    sessions: &Sessions,
+    username: &str,
+    change:   &AccountUpdate,
+) -> AppResult<Account> {
+    let admin = store.require(sessions, Role::Admin)?;
+    if change.role.is_none() && change.disabled.is_none() && change.password.is_none() {
+        return Err(AppError::invalid_input("change", "nada a alterar"));
+    }
+    let account = accounts::update(&store.accounts_file(), username, change)?;
+    sessions.refresh(&account);
+    let detail = serde_json::json!({
+        "role": change.role,
+        "disabled": change.disabled,
+        "password_reset": change.password.is_some(),
+    });
+    store.record(&admin.username, "update_account", Some(&account.username), &detail)?;
+    Ok(account)
+}
+
+// ─── Log de auditoria (admin) ─────────────────────────────────────────────────
+
+pub fn verify_audit_log(store: &impl OperatorStore, sessions: &Sessions) -> AppResult<ChainReport> {
+    store.require(sessions, Role::Admin)?;
+    Ok(audit_log::verify(&store.audit_file())?)
+}
+
+pub fn get_audit_log(store: &impl OperatorStore, sessions: &Sessions, filter: &AuditFilter) -> AppResult<Vec<AuditEvent>> {
+    store.require(sessions, Role::Admin)?;
+    Ok(audit_log::list(&store.audit_file(), filter)?)
+}
+
+// Eventos do filtro + estado da cadeia (JSON) ou só os eventos (CSV) em `path`,
+// escolhido pelo `admin` já conferido pelo comando (antes de abrir o diálogo)
+pub fn export_audit_log(
+    store:  &impl OperatorStore,
+    admin:  &Account,
+    filter: &AuditFilter,
+    format: ExportFormat,
+    path:   &Path,
+) -> AppResult<()> {
+    // Registrada antes: a própria exportação sai no arquivo
+    let detail = serde_json::json!({ "format": format.extension(), "path": path, "filter": filter });
+    store.record(&admin.username, "export_audit_log", None, &detail)?;
+    let content = audit_log::export(&store.audit_file(), filter, format)?;
+    std::fs::write(path, content).map_err(|e| AppError::io(path, e))
+}
+
+// ─── Comandos Tauri ───────────────────────────────────────────────────────────
+
+// Os comandos de sessão e contas, iguais nos dois apps, gerados no módulo auth de
+// cada um sobre o seu SettingsStore (`$store` implementa `OperatorStore`)
+#[macro_export]
+macro_rules! auth_commands {
+    ($store:ty) => {
+        #[tauri::command]
+        pub fn get_session(
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::operator::SessionStatus> {
+            $crate::operator::status(&*store, &sessions)
+        }
+
+        #[tauri::command]
+        pub fn setup_admin(
+            username: String,
+            password: String,
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::operator::SessionStatus> {
+            $crate::operator::setup_admin(&*store, &sessions, &username, &password)
+        }
+
+        #[tauri::command]
+        pub fn login(
+            username: String,
+            password: String,
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::operator::SessionStatus> {
+            $crate::operator::login(&*store, &sessions, &username, &password)
+        }
+
+        #[tauri::command]
+        pub fn logout(
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::operator::SessionStatus> {
+            $crate::operator::logout(&*store, &sessions)
+        }
+
+        #[tauri::command]
+        pub fn lock_session(
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::operator::SessionStatus> {
+            $crate::operator::lock_session(&*store, &sessions)
+        }
+
+        #[tauri::command]
+        pub fn touch_session(
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::accounts::Account> {
+            $crate::operator::touch_session(&*store, &sessions)
+        }
+
+        #[tauri::command]
+        pub fn change_password(
+            current:  String,
+            new:      String,
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<()> {
+            $crate::operator::change_password(&*store, &sessions, &current, &new)
+        }
+
+        #[tauri::command]
+        pub fn list_accounts(
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<Vec<$crate::accounts::Account>> {
+            $crate::operator::list_accounts(&*store, &sessions)
+        }
+
+        #[tauri::command]
+        pub fn create_account(
+            username: String,
+            password: String,
+            role:     $crate::accounts::Role,
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::accounts::Account> {
+            $crate::operator::create_account(&*store, &sessions, &username, &password, role)
+        }
+
+        #[tauri::command]
+        pub fn update_account(
+            username: String,
+            change:   $crate::accounts::AccountUpdate,
+            store:    ::tauri::State<'_, $store>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::accounts::Account> {
+            $crate::operator::update_account(&*store, &sessions, &username, &change)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestStore {
+        dir: PathBuf,
+    }
+
+    impl TestStore {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("operator_test_{}_{name}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            TestStore { dir }
+        }
+    }
+
+    impl OperatorStore for TestStore {
+        fn accounts_file(&self) -> PathBuf {
+            self.dir.join(accounts::ACCOUNTS_FILE)
+        }
+
+        // Um diretório no lugar do arquivo: toda gravação no log falha
+        fn audit_file(&self) -> PathBuf {
+            self.dir.clone()
+        }
+
+        fn idle_lock(&self) -> Duration {
+            Duration::from_secs(600)
+        }
+    }
+
+    #[test]
+    fn failed_login_keeps_credential_error_when_audit_fails() {
+        let store = TestStore::new("audit");
+        accounts::create_first_admin(&store.accounts_file(), "ana", "senha-forte").unwrap();
+        let err = login(&store, &Sessions::default(), "ana", "errada").err().unwrap();
+        assert!(matches!(err, AppError::InvalidCredentials), "{err:?}");
+    }
+
+    #[test]
+    fn repeated_failures_throttle_the_username() {
+        let store = TestStore::new("throttle");
+        accounts::create_first_admin(&store.accounts_file(), "ana", "senha-forte").unwrap();
+        let sessions = Sessions::default();
+        for _ in 0..3 {
+            assert!(matches!(login(&store, &sessions, "ana", "errada"), Err(AppError::InvalidCredentials)));
+        }
+        assert_eq!(sessions.login_wait("ana"), None);
+        assert!(matches!(login(&store, &sessions, "ana", "errada"), Err(AppError::InvalidCredentials)));
+        // Na espera nem a senha certa entra; outro usuário não é afetado
+        let wait = sessions.login_wait(" ANA ").unwrap();
+        assert!(wait <= Duration::from_secs(2));
+        assert!(matches!(login(&store, &sessions, "ana", "senha-forte"), Err(AppError::TooManyAttempts { retry_after: 2 })));
+        assert_eq!(sessions.login_wait("bia"), None);
+        assert_eq!(sessions.login_failed("ana"), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn wrong_current_password_shares_the_login_throttle() {
+        let store = TestStore::new("password");
+        let file = store.accounts_file();
+        accounts::create_first_admin(&file, "ana", "senha-forte").unwrap();
+        let sessions = Sessions::default();
+        sessions.start(accounts::authenticate(&file, "ana", "senha-forte").unwrap());
+        for _ in 0..4 {
+            let err = change_password(&store, &sessions, "errada", "outra-senha").err().unwrap();
+            assert!(matches!(err, AppError::InvalidCredentials), "{err:?}");
+        }
+        // Na espera nem a senha atual certa passa, e o login do mesmo usuário também espera
+        let err = change_password(&store, &sessions, "senha-forte", "outra-senha").err().unwrap();
+        assert!(matches!(err, AppError::TooManyAttempts { .. }), "{err:?}");
+        assert!(sessions.login_wait("ana").is_some());
+        assert!(accounts::authenticate(&file, "ana", "senha-forte").is_ok());
+    }
+
+    #[test]
+    fn require_sees_changes_made_by_the_other_app() {
+        let store = TestStore::new("require");
+        let file = store.accounts_file();
+        accounts::create_first_admin(&file, "ana", "senha-forte").unwrap();
+        accounts::create(&file, "bia", "senha-forte", Role::Admin).unwrap();
+        let sessions = Sessions::default();
+        sessions.start(accounts::authenticate(&file, "bia", "senha-forte").unwrap());
+
+        // O outro app rebaixa a conta logada direto no accounts.db compartilhado
+        let change = AccountUpdate { role: Some(Role::Reviewer), ..Default::default() };
+        accounts::update(&file, "bia", &change).unwrap();
+        assert!(matches!(store.require(&sessions, Role::Admin), Err(AppError::Forbidden { .. })));
+        assert_eq!(store.require(&sessions, Role::Reviewer).unwrap().role, Role::Reviewer);
+
+        // Desativada: nem consultar (viewer) passa mais
+        accounts::update(&file, "bia", &AccountUpdate { disabled: Some(true), ..Default::default() }).unwrap();
+        assert!(matches!(store.require(&sessions, Role::Viewer), Err(AppError::NotAuthenticated)));
+        assert!(sessions.current(store.idle_lock()).is_none());
+    }
+}
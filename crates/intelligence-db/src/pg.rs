@@ -0,0 +1,202 @@
+// Backend PostgreSQL (docker-compose: ankane/pgvector).
+// Mesmo SQL do SQLite: os placeholders `?` são traduzidos para `$n` com cast
+// explícito (o Postgres infere o tipo do parâmetro e não converte int4/int8 sozinho)
+// e LIKE vira ILIKE para manter a busca case-insensitive do SQLite.
+// Os statements preparados ficam em cache pelo SQL já traduzido (os casts dependem
+// do tipo dos parâmetros), limitado a `STATEMENT_CACHE_SIZE` com descarte do menos
+// usado: SQL dinâmico (listas IN, variações de filtro) não acumula no servidor.
+//
+// O cliente síncrono cria seu próprio runtime tokio: chamar apenas a partir de
+// comandos Tauri síncronos (ou de spawn_blocking), nunca de dentro de um `async fn`.
+
+use postgres::config::Host;
+use postgres::types::{ToSql, Type};
+use postgres::{Client, NoTls, Row, Statement};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+
+use crate::backend::{Backend, Dialect, Record, Value};
+use crate::error::Result;
+use crate::introspect::SchemaInfo;
+
+const STATEMENT_CACHE_SIZE: usize = 64;
+
+pub struct PgBackend {
+    describe: String,
+    schema:   SchemaInfo,
+    conn:     Mutex<PgConn>,
+}
+
+struct PgConn {
+    client:     Client,
+    // SQL traduzido → (statement, último uso)
+    statements: HashMap<String, (Statement, u64)>,
+    uses:       u64,
+}
+
+impl PgConn {
+    fn prepare(&mut self, sql: &str) -> Result<Statement> {
+        self.uses += 1;
+        if let Some((stmt, used)) = self.statements.get_mut(sql) {
+            *used = self.uses;
+            return Ok(stmt.clone());
+        }
+        if self.statements.len() >= STATEMENT_CACHE_SIZE {
+            // Descartar o Statement fecha o prepared statement no servidor
+            let oldest = self.statements.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.statements.remove(&oldest);
+            }
+        }
+        let stmt = self.client.prepare(sql)?;
+        self.statements.insert(sql.to_string(), (stmt.clone(), self.uses));
+        Ok(stmt)
+    }
+}
+
+impl PgBackend {
+    pub fn connect(url: &str) -> Result<Self> {
+        let config: postgres::Config = url.parse()?;
+        let host = match config.get_hosts().first() {
+            Some(Host::Tcp(h)) => h.clone(),
+            _ => "local".to_string(),
+        };
+        let describe = format!("postgres:{}/{}", host, config.get_dbname().unwrap_or_default());
+        let client = config.connect(NoTls)?;
+        let mut backend = PgBackend {
+            describe,
+            schema: SchemaInfo::default(),
+            conn: Mutex::new(PgConn { client, statements: HashMap::new(), uses: 0 }),
+        };
+        backend.schema = SchemaInfo::detect(&backend)?;
+        Ok(backend)
+    }
+}
+
+fn pg_type(v: &Value) -> &'static str {
+    match v {
+        Value::Int(_)  => "BIGINT",
+        Value::Real(_) => "DOUBLE PRECISION",
+        Value::Null | Value::Text(_) => "TEXT",
+    }
+}
+
+// Equivalente ao `DB.translate_query` do intelligence_db.py; nada muda dentro de literais
+pub fn translate_query(sql: &str, params: &[Value]) -> String {
+    let mut out = String::with_capacity(sql.len() + params.len() * 8);
+    let mut in_literal = false;
+    let mut n = 0;
+    for ch in sql.chars() {
+        match ch {
+            '\'' => {
+                in_literal = !in_literal;
+                out.push(ch);
+            }
+            '?' if !in_literal => {
+                let cast = params.get(n).map(pg_type).unwrap_or("TEXT");
+                n += 1;
+                let _ = write!(out, "${n}::{cast}");
+            }
+            ' ' if !in_literal && out.ends_with(" LIKE") => {
+                out.truncate(out.len() - "LIKE".len());
+                out.push_str("ILIKE ");
+            }
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn to_pg(v: &Value) -> Box<dyn ToSql + Sync> {
+    match v {
+        Value::Null    => Box::new(None::<String>),
+        Value::Int(i)  => Box::new(*i),
+        Value::Real(f) => Box::new(*f),
+        Value::Text(s) => Box::new(s.clone()),
+    }
+}
+
+impl Record for Row {
+    fn opt_text(&self, idx: usize) -> Result<Option<String>> {
+        Ok(self.try_get(idx)?)
+    }
+
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>> {
+        let ty = self.columns()[idx].type_();
+        Ok(if *ty == Type::INT4 {
+            self.try_get::<_, Option<i32>>(idx)?.map(i64::from)
+        } else if *ty == Type::INT2 {
+            self.try_get::<_, Option<i16>>(idx)?.map(i64::from)
+        } else {
+            self.try_get(idx)?
+        })
+    }
+
+    fn opt_real(&self, idx: usize) -> Result<Option<f64>> {
+        let ty = self.columns()[idx].type_();
+        Ok(if *ty == Type::FLOAT4 {
+            self.try_get::<_, Option<f32>>(idx)?.map(f64::from)
+        } else if *ty == Type::INT4 || *ty == Type::INT8 {
+            self.opt_int(idx)?.map(|v| v as f64)
+        } else {
+            self.try_get(idx)?
+        })
+    }
+}
+
+impl Backend for PgBackend {
+    fn dialect(&self) -> Dialect {
+        Dialect::Postgres
+    }
+
+    fn describe(&self) -> String {
+        self.describe.clone()
+    }
+
+    fn schema(&self) -> &SchemaInfo {
+        &self.schema
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize> {
+        let mut guard = self.conn.lock().unwrap();
+        let boxed: Vec<Box<dyn ToSql + Sync>> = params.iter().map(to_pg).collect();
+        let refs: Vec<&(dyn ToSql + Sync)> = boxed.iter().map(|b| b.as_ref()).collect();
+        Ok(guard.client.execute(&translate_query(sql, params), &refs)? as usize)
+    }
+
+    fn for_each(
+        &self,
+        sql:    &str,
+        params: &[Value],
+        f:      &mut dyn FnMut(&dyn Record) -> Result<()>,
+    ) -> Result<()> {
+        let mut guard = self.conn.lock().unwrap();
+        let conn = &mut *guard;
+        let stmt = conn.prepare(&translate_query(sql, params))?;
+
+        let boxed: Vec<Box<dyn ToSql + Sync>> = params.iter().map(to_pg).collect();
+        let refs: Vec<&(dyn ToSql + Sync)> = boxed.iter().map(|b| b.as_ref()).collect();
+        for row in conn.client.query(&stmt, &refs)? {
+            f(&row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_get_casts_from_param_types() {
+        let sql = translate_query("SELECT * FROM t WHERE a = ? AND b = ? AND c = ?", &[Value::Int(1), Value::Real(0.5), Value::Null]);
+        assert_eq!(sql, "SELECT * FROM t WHERE a = $1::BIGINT AND b = $2::DOUBLE PRECISION AND c = $3::TEXT");
+    }
+
+    #[test]
+    fn literals_are_left_untouched() {
+        let sql = translate_query("SELECT ' LIKE ?' FROM t WHERE a LIKE ? AND b NOT LIKE 'x'", &["%a%".into()]);
+        assert_eq!(sql, "SELECT ' LIKE ?' FROM t WHERE a ILIKE $1::TEXT AND b NOT ILIKE 'x'");
+    }
+}
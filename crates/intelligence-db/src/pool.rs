@@ -0,0 +1,182 @@
+// Pool de conexões somente-leitura ao intelligence.db.
+// A ingestão Python continua escrevendo no mesmo arquivo: os leitores nunca
+// alteram journal_mode (WAL é decidido pelo escritor), usam busy_timeout para
+// esperar checkpoints/locks e guardam os prepared statements em cache.
+// Sidecars do app (ex.: índice de busca) podem ser anexados com ATTACH, também
+// somente-leitura; anexar invalida as conexões abertas antes (geração do pool).
+
+use rusqlite::{Connection, OpenFlags, Result};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use crate::normalize;
+
+pub const DEFAULT_POOL_SIZE: usize = 4;
+const BUSY_TIMEOUT:    Duration = Duration::from_secs(5);
+const STATEMENT_CACHE: usize = 32;
+
+pub struct ReadPool {
+    path:         PathBuf,
+    size:         usize,
+    journal_mode: String,
+    state:        Mutex<PoolState>,
+    available:    Condvar,
+}
+
+struct PoolState {
+    idle:       Vec<Connection>,
+    open:       usize,
+    generation: u64,
+    attached:   Vec<(String, PathBuf)>,
+}
+
+pub struct PooledConnection<'a> {
+    pool:       &'a ReadPool,
+    conn:       Option<Connection>,
+    generation: u64,
+}
+
+fn open_read_only(path: &Path, attached: &[(String, PathBuf)]) -> Result<Connection> {
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY
+        | OpenFlags::SQLITE_OPEN_URI
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let conn = Connection::open_with_flags(path, flags)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    normalize::register_sql_functions(&conn)?;
+    for (alias, file) in attached {
+        conn.execute(&format!("ATTACH DATABASE ?1 AS {alias}"), [file.to_string_lossy()])?;
+    }
+    conn.execute_batch("PRAGMA query_only = ON")?;
+    conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE);
+    Ok(conn)
+}
+
+impl ReadPool {
+    // Abre a primeira conexão já na criação: caminho inválido falha aqui, não no primeiro comando.
+    pub fn open(path: &Path, size: usize) -> Result<Self> {
+        let first = open_read_only(path, &[])?;
+        let journal_mode: String = first.query_row("PRAGMA journal_mode", [], |r| r.get(0))?;
+        Ok(ReadPool {
+            path: path.to_path_buf(),
+            size: size.max(1),
+            journal_mode,
+            state: Mutex::new(PoolState { idle: vec![first], open: 1, generation: 0, attached: vec![] }),
+            available: Condvar::new(),
+        })
+    }
+
+    // Conexão de escrita avulsa, fora do pool (ver Backend::execute)
+    pub fn open_writer(&self) -> Result<Connection> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let conn = Connection::open_with_flags(&self.path, flags)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn journal_mode(&self) -> &str {
+        &self.journal_mode
+    }
+
+    pub fn is_attached(&self, alias: &str) -> bool {
+        self.state.lock().unwrap().attached.iter().any(|(a, _)| a == alias)
+    }
+
+    // Anexa `path` como `alias` em todas as conexões (novas ou devolvidas depois disto).
+    // Valida abrindo uma conexão já com o anexo antes de trocar a geração. A trava fica
+    // presa durante a abertura: dois attach simultâneos não perdem o alias um do outro.
+    pub fn attach(&self, alias: &str, path: &Path) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let mut attached = state.attached.clone();
+        attached.retain(|(a, _)| a != alias);
+        attached.push((alias.to_string(), path.to_path_buf()));
+        let conn = open_read_only(&self.path, &attached)?;
+
+        state.open -= state.idle.len();
+        state.idle.clear();
+        state.idle.push(conn);
+        state.open += 1;
+        state.generation += 1;
+        state.attached = attached;
+        Ok(())
+    }
+
+    pub fn get(&self) -> Result<PooledConnection<'_>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let generation = state.generation;
+            if let Some(conn) = state.idle.pop() {
+                return Ok(PooledConnection { pool: self, conn: Some(conn), generation });
+            }
+            if state.open < self.size {
+                state.open += 1;
+                let attached = state.attached.clone();
+                drop(state);
+                return match open_read_only(&self.path, &attached) {
+                    Ok(conn) => Ok(PooledConnection { pool: self, conn: Some(conn), generation }),
+                    Err(e) => {
+                        self.state.lock().unwrap().open -= 1;
+                        Err(e)
+                    }
+                };
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("conexão já devolvida ao pool")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            let mut state = self.pool.state.lock().unwrap();
+            if state.generation == self.generation {
+                state.idle.push(conn);
+            } else {
+                state.open -= 1; // anterior ao último ATTACH: descarta
+            }
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("pool_test_{}_{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Connection::open(&path).unwrap().execute_batch("CREATE TABLE t (x INTEGER)").unwrap();
+        path
+    }
+
+    #[test]
+    fn concurrent_attaches_keep_every_alias() {
+        let pool = ReadPool::open(&db_file("main"), 2).unwrap();
+        let sidecars: Vec<PathBuf> = (0..8).map(|i| db_file(&format!("side{i}"))).collect();
+        std::thread::scope(|s| {
+            for (i, file) in sidecars.iter().enumerate() {
+                let pool = &pool;
+                s.spawn(move || pool.attach(&format!("side{i}"), file).unwrap());
+            }
+        });
+        let conn = pool.get().unwrap();
+        for i in 0..sidecars.len() {
+            assert!(pool.is_attached(&format!("side{i}")));
+            conn.query_row(&format!("SELECT count(*) FROM side{i}.t"), [], |r| r.get::<_, i64>(0)).unwrap();
+        }
+    }
+}
@@ -0,0 +1,481 @@
+// Sintaxe de consulta da caixa de busca do catálogo:
+//
+//   source:interpol crime:fraud nationality:BR born:<1980 has:images
+//   "jose silva" OR ivan -source:fbi (crime:fraud OR crime:"money laundering")
+//
+// Termos lado a lado são AND; OR e NOT (ou `-termo`) em maiúsculas; parênteses
+// agrupam; aspas fazem frase. Aqui só se analisa o texto — o SQL parametrizado
+// sai de search.rs. Erros trazem a posição (em caracteres) do trecho inválido.
+
+use std::fmt;
+
+// Parênteses/NOT aninhados além disso viram erro (a análise é recursiva)
+pub const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Source,
+    Category,
+    Crime,
+    Nationality,
+    Location,
+    Born,
+    Has,
+}
+
+impl Field {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "name" | "nome"                 => Field::Name,
+            "source" | "fonte"              => Field::Source,
+            "category" | "categoria"        => Field::Category,
+            "crime"                         => Field::Crime,
+            "nationality" | "nacionalidade" => Field::Nationality,
+            "location" | "local"            => Field::Location,
+            "born" | "nascido"              => Field::Born,
+            "has" | "tem"                   => Field::Has,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    pub fn sql(self) -> &'static str {
+        match self {
+            Op::Eq => "=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+// Valores aceitos em `has:`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Has {
+    Images,
+    Biometrics,
+    Crimes,
+    Locations,
+    Reward,
+}
+
+impl Has {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "images" | "image" | "imagens" | "photo" | "foto" => Has::Images,
+            "biometrics" | "embedding" | "biometria" | "face" => Has::Biometrics,
+            "crimes" | "crime"                                => Has::Crimes,
+            "locations" | "location" | "locais"               => Has::Locations,
+            "reward" | "recompensa"                           => Has::Reward,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Term {
+    pub field:  Option<Field>, // None: texto livre
+    pub op:     Op,
+    pub value:  String,
+    pub phrase: bool,
+    pub pos:    usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Not(Box<Node>),
+    Term(Term),
+}
+
+// ─── Erros ────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnclosedQuote,
+    UnclosedParen,
+    UnexpectedParen,
+    MissingTerm,
+    MissingValue,
+    UnknownField,
+    InvalidOperator,
+    InvalidYear,
+    UnknownHas,
+    TooDeep,
+}
+
+impl ParseErrorKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            ParseErrorKind::UnclosedQuote   => "unclosed_quote",
+            ParseErrorKind::UnclosedParen   => "unclosed_paren",
+            ParseErrorKind::UnexpectedParen => "unexpected_paren",
+            ParseErrorKind::MissingTerm     => "missing_term",
+            ParseErrorKind::MissingValue    => "missing_value",
+            ParseErrorKind::UnknownField    => "unknown_field",
+            ParseErrorKind::InvalidOperator => "invalid_operator",
+            ParseErrorKind::InvalidYear     => "invalid_year",
+            ParseErrorKind::UnknownHas      => "unknown_has",
+            ParseErrorKind::TooDeep         => "too_deep",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind:     ParseErrorKind,
+    pub position: usize, // em caracteres, a partir de 0
+    pub token:    String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.token;
+        match self.kind {
+            ParseErrorKind::UnclosedQuote   => write!(f, "aspas sem fechamento"),
+            ParseErrorKind::UnclosedParen   => write!(f, "parêntese sem fechamento"),
+            ParseErrorKind::UnexpectedParen => write!(f, "`)` sem `(` correspondente"),
+            ParseErrorKind::MissingTerm     => write!(f, "faltou um termo depois de `{t}`"),
+            ParseErrorKind::MissingValue    => write!(f, "faltou o valor de `{t}`"),
+            ParseErrorKind::UnknownField    => write!(f, "campo desconhecido `{t}`"),
+            ParseErrorKind::InvalidOperator => write!(f, "`{t}` não aceita comparação (só born:)"),
+            ParseErrorKind::InvalidYear     => write!(f, "ano inválido `{t}` (use born:1980, born:<1980)"),
+            ParseErrorKind::UnknownHas      => write!(f, "has:{t} desconhecido (images, biometrics, crimes, locations, reward)"),
+            ParseErrorKind::TooDeep         => write!(f, "mais de {MAX_DEPTH} níveis de `{t}` aninhados"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn err(kind: ParseErrorKind, position: usize, token: impl Into<String>) -> ParseError {
+    ParseError { kind, position, token: token.into() }
+}
+
+// ─── Léxico ───────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+struct Token {
+    tok: Tok,
+    pos: usize,
+    text: String,
+}
+
+fn is_break(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')' || c == '"'
+}
+
+// Frase entre aspas a partir de `chars[start] == '"'`; devolve o texto e o fim
+fn quoted(chars: &[char], start: usize) -> Result<(String, usize), ParseError> {
+    match chars[start + 1..].iter().position(|&c| c == '"') {
+        Some(len) => Ok((chars[start + 1..start + 1 + len].iter().collect(), start + len + 2)),
+        None => Err(err(ParseErrorKind::UnclosedQuote, start, "\"")),
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let push = |tokens: &mut Vec<Token>, tok, end: usize| {
+            tokens.push(Token { tok, pos: start, text: chars[start..end].iter().collect() });
+        };
+        match c {
+            '(' => { push(&mut tokens, Tok::LParen, i + 1); i += 1; }
+            ')' => { push(&mut tokens, Tok::RParen, i + 1); i += 1; }
+            '-' if chars.get(i + 1).is_some_and(|n| !n.is_whitespace()) => { push(&mut tokens, Tok::Not, i + 1); i += 1; }
+            '"' => {
+                let (value, end) = quoted(&chars, i)?;
+                let term = Term { field: None, op: Op::Eq, value, phrase: true, pos: start };
+                push(&mut tokens, Tok::Term(term), end);
+                i = end;
+            }
+            _ => {
+                let mut end = i;
+                while end < chars.len() && !is_break(chars[end]) {
+                    end += 1;
+                }
+                let word: String = chars[i..end].iter().collect();
+                let tok = match word.as_str() {
+                    "AND" | "&&" => Tok::And,
+                    "OR" | "||"  => Tok::Or,
+                    "NOT"        => Tok::Not,
+                    _ => match word.split_once(':').filter(|(f, _)| !f.is_empty() && f.chars().all(char::is_alphabetic)) {
+                        Some((field, rest)) => {
+                            let (term, term_end) = field_term(&chars, start, field, rest, end)?;
+                            end = term_end;
+                            Tok::Term(term)
+                        }
+                        None => Tok::Term(Term { field: None, op: Op::Eq, value: word, phrase: false, pos: start }),
+                    },
+                };
+                push(&mut tokens, tok, end);
+                i = end;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// `campo:[op]valor` ou `campo:[op]"frase"`; `end` é o fim da palavra já lida
+fn field_term(chars: &[char], start: usize, name: &str, rest: &str, end: usize) -> Result<(Term, usize), ParseError> {
+    let field = Field::parse(&name.to_lowercase()).ok_or_else(|| err(ParseErrorKind::UnknownField, start, name))?;
+    let (op, value) = [("<=", Op::Le), (">=", Op::Ge), ("<", Op::Lt), (">", Op::Gt), ("=", Op::Eq)]
+        .iter()
+        .find_map(|(prefix, op)| rest.strip_prefix(prefix).map(|v| (*op, v)))
+        .unwrap_or((Op::Eq, rest));
+
+    let (value, phrase, end) = if value.is_empty() && chars.get(end) == Some(&'"') {
+        let (value, quoted_end) = quoted(chars, end)?;
+        (value, true, quoted_end)
+    } else {
+        (value.to_string(), false, end)
+    };
+    let token = format!("{name}:");
+    if value.trim().is_empty() {
+        return Err(err(ParseErrorKind::MissingValue, start, token));
+    }
+    let value_pos = start + name.chars().count() + 1;
+    if op != Op::Eq && field != Field::Born {
+        return Err(err(ParseErrorKind::InvalidOperator, value_pos, token));
+    }
+    match field {
+        Field::Born if !is_year(&value) => return Err(err(ParseErrorKind::InvalidYear, value_pos, value)),
+        Field::Has if Has::parse(&value.to_lowercase()).is_none() => {
+            return Err(err(ParseErrorKind::UnknownHas, value_pos, value));
+        }
+        _ => {}
+    }
+    Ok((Term { field: Some(field), op, value, phrase, pos: start }, end))
+}
+
+fn is_year(s: &str) -> bool {
+    s.len() == 4 && s.chars().all(|c| c.is_ascii_digit())
+}
+
+// ─── Sintaxe ──────────────────────────────────────────────────────────────────
+//   or    := and ("OR" and)*
+//   and   := unary (["AND"] unary)*
+//   unary := ("NOT" | "-") unary | "(" or ")" | termo
+
+struct Parser {
+    tokens: Vec<Token>,
+    next:   usize,
+    len:    usize, // tamanho da entrada, para erros no fim
+    depth:  usize, // NOT e `(` abertos
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.next).map(|t| &t.tok)
+    }
+
+    fn advance(&mut self) -> &Token {
+        self.next += 1;
+        &self.tokens[self.next - 1]
+    }
+
+    // Erro de termo ausente logo depois do token anterior
+    fn missing_term(&self) -> ParseError {
+        let prev = self.next.checked_sub(1).unwrap_or(self.next);
+        match self.tokens.get(prev) {
+            Some(t) => err(ParseErrorKind::MissingTerm, t.pos, t.text.clone()),
+            None => err(ParseErrorKind::MissingTerm, self.len, ""),
+        }
+    }
+
+    fn or(&mut self) -> Result<Node, ParseError> {
+        let mut nodes = vec![self.and()?];
+        while self.peek() == Some(&Tok::Or) {
+            self.advance();
+            nodes.push(self.and()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::Or(nodes) })
+    }
+
+    fn and(&mut self) -> Result<Node, ParseError> {
+        let mut nodes = vec![self.unary()?];
+        loop {
+            match self.peek() {
+                Some(Tok::And) => {
+                    self.advance();
+                    nodes.push(self.unary()?);
+                }
+                Some(Tok::Or) | Some(Tok::RParen) | None => break,
+                Some(_) => nodes.push(self.unary()?),
+            }
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::And(nodes) })
+    }
+
+    fn unary(&mut self) -> Result<Node, ParseError> {
+        if matches!(self.peek(), Some(Tok::Not) | Some(Tok::LParen)) {
+            if self.depth == MAX_DEPTH {
+                let t = &self.tokens[self.next];
+                return Err(err(ParseErrorKind::TooDeep, t.pos, t.text.clone()));
+            }
+            self.depth += 1;
+            let node = self.nested();
+            self.depth -= 1;
+            return node;
+        }
+        match self.peek() {
+            Some(Tok::Term(_)) => match &self.advance().tok {
+                Tok::Term(term) => Ok(Node::Term(term.clone())),
+                _ => unreachable!(),
+            },
+            Some(Tok::RParen) => {
+                let t = &self.tokens[self.next];
+                Err(err(ParseErrorKind::UnexpectedParen, t.pos, ")"))
+            }
+            _ => Err(self.missing_term()),
+        }
+    }
+
+    // NOT/`-` ou grupo entre parênteses
+    fn nested(&mut self) -> Result<Node, ParseError> {
+        match self.peek() {
+            Some(Tok::Not) => {
+                self.advance();
+                Ok(Node::Not(Box::new(self.unary()?)))
+            }
+            Some(Tok::LParen) => {
+                let open = self.advance().pos;
+                if self.peek() == Some(&Tok::RParen) {
+                    return Err(self.missing_term());
+                }
+                let node = self.or()?;
+                match self.peek() {
+                    Some(Tok::RParen) => {
+                        self.advance();
+                        Ok(node)
+                    }
+                    _ => Err(err(ParseErrorKind::UnclosedParen, open, "(")),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Consulta vazia (só espaços): None
+pub fn parse(input: &str) -> Result<Option<Node>, ParseError> {
+    let tokens = lex(input)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+    let mut parser = Parser { tokens, next: 0, len: input.chars().count(), depth: 0 };
+    let node = parser.or()?;
+    if let Some(t) = parser.tokens.get(parser.next) {
+        // Sobra só pode ser `)` sem par (o resto é consumido pelo AND implícito)
+        return Err(err(ParseErrorKind::UnexpectedParen, t.pos, t.text.clone()));
+    }
+    Ok(Some(node))
+}
+
+impl Term {
+    pub fn has(&self) -> Option<Has> {
+        Has::parse(&self.value.to_lowercase())
+    }
+
+    pub fn year(&self) -> Option<i64> {
+        self.value.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(field: Option<Field>, value: &str, pos: usize) -> Node {
+        Node::Term(Term { field, op: Op::Eq, value: value.into(), phrase: false, pos })
+    }
+
+    fn error(input: &str) -> (ParseErrorKind, usize, String) {
+        let e = parse(input).unwrap_err();
+        (e.kind, e.position, e.token)
+    }
+
+    #[test]
+    fn empty_query_is_none() {
+        assert_eq!(parse("").unwrap(), None);
+        assert_eq!(parse("   ").unwrap(), None);
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let node = parse("a OR b c").unwrap().unwrap();
+        assert_eq!(node, Node::Or(vec![
+            term(None, "a", 0),
+            Node::And(vec![term(None, "b", 5), term(None, "c", 7)]),
+        ]));
+    }
+
+    #[test]
+    fn negation_groups_and_fields() {
+        let node = parse("-source:fbi NOT (a OR b)").unwrap().unwrap();
+        assert_eq!(node, Node::And(vec![
+            Node::Not(Box::new(term(Some(Field::Source), "fbi", 1))),
+            Node::Not(Box::new(Node::Or(vec![term(None, "a", 17), term(None, "b", 22)]))),
+        ]));
+    }
+
+    #[test]
+    fn phrases_and_comparisons() {
+        let Some(Node::Term(t)) = parse("name:\"jose silva\"").unwrap() else { panic!() };
+        assert_eq!((t.field, t.value.as_str(), t.phrase), (Some(Field::Name), "jose silva", true));
+        let Some(Node::Term(t)) = parse("born:<=1980").unwrap() else { panic!() };
+        assert_eq!((t.op, t.year()), (Op::Le, Some(1980)));
+    }
+
+    #[test]
+    fn errors_carry_character_positions() {
+        assert_eq!(error("\"abc"), (ParseErrorKind::UnclosedQuote, 0, "\"".into()));
+        assert_eq!(error("x (a OR b"), (ParseErrorKind::UnclosedParen, 2, "(".into()));
+        assert_eq!(error("josé )"), (ParseErrorKind::UnexpectedParen, 5, ")".into()));
+        assert_eq!(error("a OR"), (ParseErrorKind::MissingTerm, 2, "OR".into()));
+        assert_eq!(error("()"), (ParseErrorKind::MissingTerm, 0, "(".into()));
+        assert_eq!(error("source:"), (ParseErrorKind::MissingValue, 0, "source:".into()));
+        assert_eq!(error("a foo:bar"), (ParseErrorKind::UnknownField, 2, "foo".into()));
+        assert_eq!(error("crime:>x"), (ParseErrorKind::InvalidOperator, 6, "crime:".into()));
+        assert_eq!(error("born:19x0"), (ParseErrorKind::InvalidYear, 5, "19x0".into()));
+        assert_eq!(error("has:wings"), (ParseErrorKind::UnknownHas, 4, "wings".into()));
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error_not_a_crash() {
+        let ok = "(".repeat(MAX_DEPTH) + "a" + &")".repeat(MAX_DEPTH);
+        assert!(parse(&ok).is_ok());
+        assert_eq!(error(&("(".repeat(5000) + "a")), (ParseErrorKind::TooDeep, MAX_DEPTH, "(".into()));
+        assert_eq!(error(&("-".repeat(5000) + "a")), (ParseErrorKind::TooDeep, MAX_DEPTH, "-".into()));
+    }
+}
@@ -0,0 +1,584 @@
+// Repositório: consultas tipadas sobre o intelligence.db + mappers de linha.
+
+use chrono::Days;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{Backend, Record, Value};
+use crate::error::{Error, Result};
+use crate::introspect::SchemaInfo;
+use crate::model::{
+    Completeness, Descriptors, FacetCount, Individual, IndividualDetail, IndividualImage, IngestionStats,
+    IntegrityStatus, Location, MatchConfidence, Sighting, SightingReview, SourceHealth, Stats, TimelinePoint,
+};
+use crate::normalize;
+use crate::schema::{
+    image_columns, individual_detail_columns, location_columns, sighting_columns, CATEGORY_MISSING,
+    CATEGORY_WANTED, EVIDENCE, INDIVIDUALS, INDIVIDUAL_IMAGES, LOCATIONS,
+};
+use crate::search::{self, Facet, SearchFilter};
+
+// ─── Mappers ──────────────────────────────────────────────────────────────────
+
+// Data normalizada e idade a partir do texto da fonte
+fn birth(raw: Option<&str>) -> (Option<String>, Option<i64>) {
+    match raw.and_then(normalize::birth_date) {
+        Some(date) => (Some(date.iso()), date.age_on(normalize::today())),
+        None => (None, None),
+    }
+}
+
+pub fn map_individual(r: &dyn Record) -> Result<Individual> {
+    let birth_date = r.opt_text(4)?;
+    let (birth_date_iso, age) = birth(birth_date.as_deref());
+    Ok(Individual {
+        id:             r.text(0)?,
+        name:           r.text(1)?,
+        category:       r.text(2)?,
+        source:         r.text(3)?,
+        birth_date,
+        birth_date_iso,
+        age,
+        nationalities:  r.opt_text(5)?,
+        description:    r.opt_text(6)?,
+        reward:         r.opt_text(7)?,
+        img_path:       r.opt_text(8)?,
+        has_embedding:  r.opt_int(9)?.unwrap_or(0) as i32,
+        ingested_at:    r.opt_text(10)?,
+        snippet:        r.opt_text(11)?,
+        match_score:    r.opt_real(12)?,
+        linked:         vec![],
+    })
+}
+
+pub fn map_individual_detail(r: &dyn Record) -> Result<IndividualDetail> {
+    let source = r.text(3)?;
+    let birth_date = r.opt_text(4)?;
+    let (birth_date_iso, age) = birth(birth_date.as_deref());
+    let (height_cm, weight_kg) = (r.opt_real(14)?, r.opt_real(15)?);
+    let (eye_color, hair_color) = (r.opt_text(16)?, r.opt_text(17)?);
+    let normalized = Descriptors {
+        height_cm:  height_cm.and_then(|v| normalize::height_cm(v, &source)),
+        weight_kg:  weight_kg.and_then(|v| normalize::weight_kg(v, &source)),
+        eye_color:  eye_color.as_deref().and_then(normalize::color).map(str::to_string),
+        hair_color: hair_color.as_deref().and_then(normalize::color).map(str::to_string),
+    };
+    Ok(IndividualDetail {
+        id:             r.text(0)?,
+        name:           r.text(1)?,
+        category:       r.text(2)?,
+        source,
+        birth_date,
+        birth_date_iso,
+        age,
+        nationalities:  r.opt_text(5)?,
+        description:    r.opt_text(6)?,
+        reward:         r.opt_text(7)?,
+        img_path:       r.opt_text(8)?,
+        has_embedding:  r.opt_int(9)?.unwrap_or(0) as i32,
+        aliases:        r.opt_text(10)?,
+        sex:            r.opt_text(11)?,
+        url:            r.opt_text(12)?,
+        ingested_at:    r.opt_text(13)?,
+        height_cm,
+        weight_kg,
+        eye_color,
+        hair_color,
+        occupation:     r.opt_text(18)?,
+        normalized,
+        images:         vec![], // Preenchido depois
+        crimes:         vec![],
+        locations:      vec![],
+    })
+}
+
+pub fn map_location(r: &dyn Record) -> Result<Location> {
+    Ok(Location {
+        loc_type: r.text(0)?,
+        country:  r.opt_text(1)?,
+        state:    r.opt_text(2)?,
+        city:     r.opt_text(3)?,
+        details:  r.opt_text(4)?,
+    })
+}
+
+pub fn map_image(r: &dyn Record) -> Result<IndividualImage> {
+    Ok(IndividualImage {
+        img_url:    r.opt_text(0)?,
+        img_path:   r.opt_text(1)?,
+        caption:    r.opt_text(2)?,
+        is_primary: r.opt_int(3)?.unwrap_or(0) as i32,
+    })
+}
+
+pub fn map_sighting(r: &dyn Record) -> Result<Sighting> {
+    Ok(Sighting {
+        id:                r.text(0)?,
+        individual_id:     r.text(1)?,
+        camera_id:         r.opt_text(2)?,
+        captured_at:       r.text(3)?,
+        name:              r.text(4)?,
+        threat_score:      r.opt_real(5)?,
+        threat_factors:    r.opt_text(6)?.and_then(|raw| serde_json::from_str(&raw).ok()),
+        file_hash:         r.opt_text(7)?,
+        match_distance:    r.opt_real(8)?,
+        match_probability: r.opt_real(9)?,
+        match_confidence:  r.opt_text(10)?.map_or(MatchConfidence::Unknown, |c| MatchConfidence::parse(&c)),
+        model:             r.opt_text(11)?,
+        model_version:     r.opt_text(12)?,
+        integrity:         IntegrityStatus::Unverified,
+        verified_at:       None,
+        review:            SightingReview::default(),
+    })
+}
+
+// ─── Consultas ────────────────────────────────────────────────────────────────
+// SQL portável: nada específico de SQLite/Postgres; o backend traduz placeholders.
+// Colunas opcionais vêm do schema detectado (db.schema()): ausentes viram NULL.
+
+pub fn get_individual(db: &dyn Backend, id: &str) -> Result<IndividualDetail> {
+    let s = db.schema();
+    let key = [Value::from(id)];
+    let row = db
+        .query_opt(&format!("SELECT {} FROM individuals i WHERE i.id=?", individual_detail_columns(s)), &key, map_individual_detail)?
+        .ok_or_else(|| Error::NotFound(id.to_string()))?;
+
+    let mut crimes    = vec![];
+    let mut locations = vec![];
+    let mut images    = vec![];
+    if s.has_crimes() {
+        crimes = db.query("SELECT crime FROM crimes WHERE individual_id=?", &key, |r| r.text(0))?;
+    }
+    if s.has_locations() {
+        locations = db.query(&format!("SELECT {} FROM locations l WHERE l.individual_id=?", location_columns(s)), &key, map_location)?;
+    }
+    if s.has_images() {
+        images = db.query(
+            &format!("SELECT {} FROM individual_images m WHERE m.individual_id=? {}", image_columns(s), gallery_order(s)),
+            &key,
+            map_image,
+        )?;
+    }
+
+    Ok(IndividualDetail { crimes, locations, images, ..row })
+}
+
+// Ordem estável da galeria: o índice `n` do protocolo catalog://image/<id>/<n> aponta para `images[n - 1]`
+fn gallery_order(s: &SchemaInfo) -> String {
+    format!(
+        "ORDER BY {} DESC, {}",
+        s.int(INDIVIDUAL_IMAGES, "m", "is_primary"),
+        s.text(INDIVIDUAL_IMAGES, "m", "img_path"),
+    )
+}
+
+// Caminho relativo da imagem `n` de um indivíduo: 0 é a principal (`img_path`), 1.. a galeria
+pub fn image_path(db: &dyn Backend, id: &str, n: usize) -> Result<Option<String>> {
+    let s = db.schema();
+    let key = [Value::from(id)];
+    let path = if n == 0 {
+        let sql = format!("SELECT {} FROM individuals i WHERE i.id=?", s.text(INDIVIDUALS, "i", "img_path"));
+        db.query_opt(&sql, &key, |r| r.opt_text(0))?.ok_or_else(|| Error::NotFound(id.to_string()))?
+    } else if s.has_images() {
+        db.query(
+            &format!(
+                "SELECT {} FROM individual_images m WHERE m.individual_id=? {}",
+                s.text(INDIVIDUAL_IMAGES, "m", "img_path"),
+                gallery_order(s),
+            ),
+            &key,
+            |r| r.opt_text(0),
+        )?
+        .into_iter()
+        .nth(n - 1)
+        .flatten()
+    } else {
+        None
+    };
+    Ok(path.filter(|p| !p.trim().is_empty()))
+}
+
+pub fn get_stats(db: &dyn Backend) -> Result<Stats> {
+    let total           = db.count("SELECT COUNT(*) FROM individuals", &[])?;
+    let wanted          = db.count("SELECT COUNT(*) FROM individuals WHERE category=?", &[CATEGORY_WANTED.into()])?;
+    let missing         = db.count("SELECT COUNT(*) FROM individuals WHERE category=?", &[CATEGORY_MISSING.into()])?;
+    let with_biometrics = db.count(
+        &format!("SELECT COUNT(*) FROM individuals i WHERE {}=1", db.schema().int(INDIVIDUALS, "i", "has_embedding")),
+        &[],
+    )?;
+
+    let mut by_source = search::facet(db, &SearchFilter::default(), Facet::Source)?;
+    by_source.truncate(10);
+
+    Ok(Stats { total, wanted, missing, with_biometrics, by_source })
+}
+
+// ─── Saúde da ingestão ────────────────────────────────────────────────────────
+
+// Janela da linha do tempo e dias sem atualização para um registro contar como parado
+pub const TIMELINE_DAYS: u32 = 90;
+pub const STALE_DAYS:    u32 = 30;
+pub const TOP_LIMIT:     u32 = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct IngestionStatsFilter {
+    pub days:       Option<u32>,
+    pub stale_days: Option<u32>,
+    pub top:        Option<u32>,
+}
+
+// Contagens de campos preenchidos, convertidas em percentual no fim
+#[derive(Default)]
+struct Filled {
+    total:       i64,
+    images:      i64,
+    birth_date:  i64,
+    description: i64,
+    locations:   i64,
+}
+
+impl Filled {
+    fn add(&mut self, other: &Filled) {
+        self.total       += other.total;
+        self.images      += other.images;
+        self.birth_date  += other.birth_date;
+        self.description += other.description;
+        self.locations   += other.locations;
+    }
+
+    fn percent(&self) -> Completeness {
+        let pct = |n: i64| if self.total == 0 { 0.0 } else { (n as f64 * 1000.0 / self.total as f64).round() / 10.0 };
+        Completeness {
+            images:      pct(self.images),
+            birth_date:  pct(self.birth_date),
+            description: pct(self.description),
+            locations:   pct(self.locations),
+        }
+    }
+}
+
+// O upsert da ingestão não toca em `ingested_at` (só na inserção) mas regrava
+// `last_seen`: o último refresh é o `last_seen` e, sem ele, a data de ingestão.
+pub fn ingestion_stats(db: &dyn Backend, f: &IngestionStatsFilter) -> Result<IngestionStats> {
+    let s = db.schema();
+    let days       = f.days.unwrap_or(TIMELINE_DAYS);
+    let stale_days = f.stale_days.unwrap_or(STALE_DAYS);
+    let top        = f.top.unwrap_or(TOP_LIMIT) as usize;
+
+    let today = normalize::today();
+    let since = |n: u32| today.checked_sub_days(Days::new(n.into())).unwrap_or(today).format("%Y-%m-%d").to_string();
+
+    let ingested_at = s.timestamp(INDIVIDUALS, "i", "ingested_at");
+    let refresh     = format!("substr(COALESCE(NULLIF({}, ''), {ingested_at}), 1, 10)", s.text(INDIVIDUALS, "i", "last_seen"));
+    let filled      = |c: &str| format!("SUM(CASE WHEN NULLIF(TRIM({}), '') IS NOT NULL THEN 1 ELSE 0 END)", s.text(INDIVIDUALS, "i", c));
+    let images      = format!(
+        "SUM(CASE WHEN NULLIF(TRIM({}), '') IS NOT NULL OR {} THEN 1 ELSE 0 END)",
+        s.text(INDIVIDUALS, "i", "img_path"),
+        search::has_images_expr(db),
+    );
+    let locations = if s.has_locations() {
+        "SUM(CASE WHEN EXISTS (SELECT 1 FROM locations l WHERE l.individual_id = i.id) THEN 1 ELSE 0 END)"
+    } else {
+        "0"
+    };
+
+    let timeline = db.query(
+        &format!(
+            "SELECT substr({ingested_at}, 1, 10), i.source, COUNT(*) FROM individuals i
+             WHERE {ingested_at} >= ? GROUP BY 1, 2 ORDER BY 1, 2"
+        ),
+        &[since(days).into()],
+        |r| Ok(TimelinePoint { day: r.text(0)?, source: r.text(1)?, count: r.int(2)? }),
+    )?;
+
+    let mut overall = Filled::default();
+    let mut stale = 0;
+    let sources = db.query(
+        &format!(
+            "SELECT i.source, COUNT(*), {images}, {}, {}, {locations},
+                    SUM(CASE WHEN {refresh} < ? THEN 1 ELSE 0 END), MAX({ingested_at}), MAX({refresh})
+             FROM individuals i GROUP BY i.source ORDER BY 2 DESC, 1",
+            filled("birth_date"),
+            filled("description"),
+        ),
+        &[since(stale_days).into()],
+        |r| {
+            let counts = Filled {
+                total:       r.int(1)?,
+                images:      r.int(2)?,
+                birth_date:  r.int(3)?,
+                description: r.int(4)?,
+                locations:   r.int(5)?,
+            };
+            overall.add(&counts);
+            let source_stale = r.int(6)?;
+            stale += source_stale;
+            Ok(SourceHealth {
+                source:        r.text(0)?,
+                total:         counts.total,
+                stale:         source_stale,
+                last_ingested: r.opt_text(7)?,
+                last_refresh:  r.opt_text(8)?,
+                completeness:  counts.percent(),
+            })
+        },
+    )?;
+
+    let mut top_crimes = search::facet(db, &SearchFilter::default(), Facet::Crime)?;
+    top_crimes.truncate(top);
+    let mut top_nationalities = search::facet(db, &SearchFilter::default(), Facet::Nationality)?;
+    top_nationalities.truncate(top);
+
+    Ok(IngestionStats {
+        days,
+        stale_days,
+        timeline,
+        completeness: overall.percent(),
+        sources,
+        top_crimes,
+        top_nationalities,
+        stale,
+    })
+}
+
+// Nível da tabela `locations` listado no autocomplete
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationLevel {
+    Type,
+    #[default]
+    Country,
+    State,
+    City,
+}
+
+impl LocationLevel {
+    fn column(self) -> &'static str {
+        match self {
+            LocationLevel::Type    => "type",
+            LocationLevel::Country => "country",
+            LocationLevel::State   => "state",
+            LocationLevel::City    => "city",
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct LocationValuesFilter {
+    pub level:    LocationLevel,
+    // Começo do valor digitado (autocomplete)
+    pub prefix:   Option<String>,
+    // Níveis acima já escolhidos: estados de um país, cidades de um estado
+    pub loc_type: Option<String>,
+    pub country:  Option<String>,
+    pub state:    Option<String>,
+    pub limit:    Option<u32>,
+}
+
+pub const LOCATION_VALUES_LIMIT: u32 = 50;
+
+// Valores distintos de um nível de `locations`, com o número de indivíduos
+pub fn location_values(db: &dyn Backend, f: &LocationValuesFilter) -> Result<Vec<FacetCount>> {
+    let s = db.schema();
+    s.require_table(LOCATIONS)?;
+    let value = s.text(LOCATIONS, "l", f.level.column());
+    let mut conds = vec![format!("{value} IS NOT NULL"), format!("{value} <> ''")];
+    let mut vals: Vec<Value> = vec![];
+
+    for (column, v) in [("type", &f.loc_type), ("country", &f.country), ("state", &f.state)] {
+        if let Some(v) = v.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            conds.push(format!("LOWER({}) = LOWER(?)", s.text(LOCATIONS, "l", column)));
+            vals.push(v.into());
+        }
+    }
+    if let Some(prefix) = f.prefix.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        conds.push(format!("{value} LIKE ?"));
+        vals.push(format!("{prefix}%").into());
+    }
+    vals.push(Value::Int(f.limit.unwrap_or(LOCATION_VALUES_LIMIT).min(500) as i64));
+
+    db.query(
+        &format!(
+            "SELECT {value}, COUNT(DISTINCT l.individual_id) FROM locations l
+             WHERE {} GROUP BY 1 ORDER BY 2 DESC, 1 LIMIT ?",
+            conds.join(" AND ")
+        ),
+        &vals,
+        |r| Ok(FacetCount { value: r.text(0)?, count: r.int(1)? }),
+    )
+}
+
+// Avistamentos mais recentes primeiro. `cond` restringe `e` com SQL fixo (ex.: a
+// revisão, com o sidecar anexado); `offset` serve à leitura em lotes.
+pub fn recent_sightings(db: &dyn Backend, limit: u32, offset: u32, cond: Option<&str>) -> Result<Vec<Sighting>> {
+    let s = db.schema();
+    s.require_table(EVIDENCE)?;
+    let scores = if s.has_threat_scores() {
+        "LEFT JOIN threat_scores t ON e.individual_id = t.individual_id"
+    } else {
+        ""
+    };
+    let where_sql = cond.map(|c| format!("WHERE {c}")).unwrap_or_default();
+    let vals = [Value::Int(limit as i64), Value::Int(offset as i64)];
+
+    db.query(
+        &format!(
+            "SELECT {columns}
+             FROM evidence e
+             JOIN individuals i ON e.individual_id = i.id
+             {scores}
+             {where_sql}
+             ORDER BY {captured_at} DESC, e.id
+             LIMIT ? OFFSET ?",
+            columns     = sighting_columns(s),
+            captured_at = s.text(EVIDENCE, "e", "captured_at"),
+        ),
+        &vals,
+        map_sighting,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Fixture;
+
+    // `a` (FBI, medidas imperiais) com galeria; `b` parado há 60 dias; `c` desaparecida
+    const SEED: &str = "
+        INSERT INTO individuals (id, name, category, source, birth_date, height_cm, weight_kg, eye_color, description, img_path, has_embedding, nationalities, ingested_at, last_seen) VALUES
+            ('a', 'José Silva',  'wanted',  'fbi',      '1975-03-04', 511, 180, 'BRO', 'fraude', 'images/a.jpg', 1, '[\"Brazil\"]', datetime('now', '-2 days'), NULL),
+            ('b', 'Ivan Petrov', 'wanted',  'interpol', 'c. 1982',    180, 80,  NULL,  NULL,     '  ',           0, '[\"Russia\", \"Brazil\"]', datetime('now', '-200 days'), datetime('now', '-60 days')),
+            ('c', 'Maria Souza', 'missing', 'interpol', NULL,         NULL, NULL, NULL, 'vista em Recife', NULL, 0, NULL, datetime('now', '-1 days'), NULL);
+        INSERT INTO crimes (individual_id, crime) VALUES ('a', 'fraud'), ('a', 'smuggling'), ('b', 'fraud');
+        INSERT INTO locations (individual_id, type, country, state, city) VALUES
+            ('a', 'last_seen', 'Brazil', 'SP', 'São Paulo'),
+            ('a', 'residence', 'Brazil', 'RJ', 'Rio de Janeiro'),
+            ('c', 'last_seen', 'brazil', 'PE', 'Recife'),
+            ('b', 'residence', 'Russia', NULL, 'Moscow');
+        INSERT INTO individual_images (individual_id, img_path, caption, is_primary) VALUES
+            ('a', 'images/a_2.jpg', 'perfil', 0),
+            ('a', 'images/a_1.jpg', 'frente', 1),
+            ('a', 'images/a_0.jpg', NULL, 0);";
+
+    #[test]
+    fn individual_detail_with_gallery_and_normalized_descriptors() {
+        let f = Fixture::new("repo_detail", SEED);
+        let a = get_individual(&f.db, "a").unwrap();
+        assert_eq!(a.birth_date_iso.as_deref(), Some("1975-03-04"));
+        assert!(a.age.unwrap() >= 51);
+        assert_eq!(a.crimes, ["fraud", "smuggling"]);
+        assert_eq!(a.locations.len(), 2);
+        let gallery: Vec<_> = a.images.iter().map(|i| i.img_path.as_deref().unwrap()).collect();
+        assert_eq!(gallery, ["images/a_1.jpg", "images/a_0.jpg", "images/a_2.jpg"]);
+        // 5'11" e libras (fonte americana); cor pela chave canônica
+        assert_eq!(a.normalized.height_cm.map(f64::round), Some(180.0));
+        assert_eq!(a.normalized.weight_kg.map(f64::round), Some(82.0));
+        assert_eq!(a.normalized.eye_color.as_deref(), Some("brown"));
+        assert!(matches!(get_individual(&f.db, "zz"), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn image_path_indexes_the_gallery_order() {
+        let f = Fixture::new("repo_image_path", SEED);
+        let path = |id: &str, n| image_path(&f.db, id, n).unwrap();
+        assert_eq!(path("a", 0).as_deref(), Some("images/a.jpg"));
+        assert_eq!(path("a", 1).as_deref(), Some("images/a_1.jpg"));
+        assert_eq!(path("a", 3).as_deref(), Some("images/a_2.jpg"));
+        assert_eq!(path("a", 4), None);
+        // Caminho em branco conta como ausente
+        assert_eq!(path("b", 0), None);
+        assert!(matches!(image_path(&f.db, "zz", 0), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn older_schemas_without_optional_tables() {
+        let seed = format!("{SEED} DROP TABLE individual_images; DROP TABLE locations; DROP TABLE crimes; DROP TABLE evidence;");
+        let f = Fixture::new("repo_older_schema", &seed);
+        let a = get_individual(&f.db, "a").unwrap();
+        assert!(a.images.is_empty() && a.crimes.is_empty() && a.locations.is_empty());
+        assert_eq!(image_path(&f.db, "a", 1).unwrap(), None);
+        assert!(matches!(location_values(&f.db, &Default::default()), Err(Error::MissingTable(_))));
+        assert!(matches!(recent_sightings(&f.db, 10, 0, None), Err(Error::MissingTable(_))));
+        assert_eq!(ingestion_stats(&f.db, &Default::default()).unwrap().completeness.locations, 0.0);
+    }
+
+    #[test]
+    fn stats_count_categories_and_sources() {
+        let f = Fixture::new("repo_stats", SEED);
+        let stats = get_stats(&f.db).unwrap();
+        assert_eq!((stats.total, stats.wanted, stats.missing, stats.with_biometrics), (3, 2, 1, 1));
+        let by_source: Vec<_> = stats.by_source.iter().map(|c| (c.value.as_str(), c.count)).collect();
+        assert_eq!(by_source, [("interpol", 2), ("fbi", 1)]);
+    }
+
+    #[test]
+    fn ingestion_stats_timeline_staleness_and_completeness() {
+        let f = Fixture::new("repo_ingestion", SEED);
+        let stats = ingestion_stats(&f.db, &IngestionStatsFilter::default()).unwrap();
+        // `b` foi ingerido fora da janela de 90 dias
+        let timeline: Vec<_> = stats.timeline.iter().map(|p| (p.source.as_str(), p.count)).collect();
+        assert_eq!(timeline, [("fbi", 1), ("interpol", 1)]);
+        // Refresh (last_seen) há 60 dias: parado com o limite de 30, não com o de 90
+        assert_eq!(stats.stale, 1);
+        let interpol = stats.sources.iter().find(|s| s.source == "interpol").unwrap();
+        assert_eq!((interpol.total, interpol.stale), (2, 1));
+        assert_eq!(interpol.completeness.images, 0.0);
+        assert_eq!(interpol.completeness.description, 50.0);
+        assert_eq!(stats.completeness.images, 33.3);
+        assert_eq!(stats.completeness.locations, 100.0);
+        assert_eq!(stats.top_crimes[0].value, "fraud");
+        assert_eq!(stats.top_nationalities[0].value, "Brazil");
+
+        let relaxed = ingestion_stats(&f.db, &IngestionStatsFilter { days: Some(365), stale_days: Some(90), top: Some(1) }).unwrap();
+        assert_eq!(relaxed.timeline.len(), 3);
+        assert_eq!(relaxed.stale, 0);
+        assert_eq!(relaxed.top_crimes.len(), 1);
+    }
+
+    #[test]
+    fn location_values_narrow_by_level_and_prefix() {
+        let f = Fixture::new("repo_locations", SEED);
+        let values = |filter: LocationValuesFilter| -> Vec<(String, i64)> {
+            location_values(&f.db, &filter).unwrap().into_iter().map(|c| (c.value, c.count)).collect()
+        };
+        assert_eq!(values(Default::default()), [("Brazil".into(), 1), ("Russia".into(), 1), ("brazil".into(), 1)]);
+        let states = values(LocationValuesFilter { level: LocationLevel::State, country: Some("BRAZIL".into()), ..Default::default() });
+        assert_eq!(states.len(), 3);
+        let cities = values(LocationValuesFilter {
+            level:    LocationLevel::City,
+            loc_type: Some("last_seen".into()),
+            prefix:   Some("Re".into()),
+            ..Default::default()
+        });
+        assert_eq!(cities, [("Recife".into(), 1)]);
+        let types = values(LocationValuesFilter { level: LocationLevel::Type, limit: Some(1), ..Default::default() });
+        assert_eq!(types, [("last_seen".into(), 2)]);
+    }
+
+    #[test]
+    fn recent_sightings_order_page_and_scores() {
+        let seed = format!(
+            "{SEED}
+             INSERT INTO evidence (id, individual_id, camera_id, file_hash, file_path, captured_at, match_confidence) VALUES
+                 ('e1', 'a', 'cam1', 'h1', 'e1.jpg', '2026-01-01 10:00:00', 'high'),
+                 ('e2', 'b', 'cam2', 'h2', 'e2.jpg', '2026-01-03 10:00:00', NULL),
+                 ('e3', 'a', 'cam1', 'h3', 'e3.jpg', '2026-01-02 10:00:00', 'weird');
+             INSERT INTO threat_scores (individual_id, score, factors_json) VALUES ('a', 7.5, '{{\"crimes\": 2}}');"
+        );
+        let f = Fixture::new("repo_sightings", &seed);
+        let ids = |s: &[Sighting]| s.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        let all = recent_sightings(&f.db, 10, 0, None).unwrap();
+        assert_eq!(ids(&all), ["e2", "e3", "e1"]);
+        assert_eq!(ids(&recent_sightings(&f.db, 1, 1, None).unwrap()), ["e3"]);
+        assert_eq!(ids(&recent_sightings(&f.db, 10, 0, Some("e.camera_id = 'cam1'")).unwrap()), ["e3", "e1"]);
+
+        let e1 = &all[2];
+        assert_eq!(e1.name, "José Silva");
+        assert_eq!(e1.threat_score, Some(7.5));
+        assert_eq!(e1.threat_factors.as_ref().unwrap()["crimes"], 2);
+        assert_eq!(e1.match_confidence, MatchConfidence::High);
+        assert_eq!(all[1].match_confidence, MatchConfidence::Unknown);
+        assert_eq!(all[0].threat_score, None);
+        assert_eq!(e1.integrity, IntegrityStatus::Unverified);
+    }
+}
@@ -0,0 +1,439 @@
+// Política de retenção das evidências: cada categoria de indivíduo (wanted,
+// missing, ...) tem um prazo em dias contado a partir de `evidence.captured_at`;
+// categorias sem prazo próprio usam o padrão e, sem padrão, nada vence. O expurgo
+// apaga o arquivo de cada evidência vencida (sobrescrito antes de removido) e só
+// então a linha de `evidence` — se o arquivo não puder ser apagado, a linha fica e
+// a próxima rodada tenta de novo. Arquivos ainda citados por evidências dentro do
+// prazo nunca são apagados. Linha cujo arquivo não foi encontrado só sai se a
+// ausência for confirmada (a pasta onde ele estaria existe); sem pasta de dados ou
+// com layout que não bate, a linha fica. É a única escrita do app no banco principal.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::backend::{Backend, Value};
+use crate::error::{Error, Result};
+use crate::integrity;
+use crate::layout::{check_data_root, EVIDENCE_DIR_CANDIDATES};
+use crate::schema::EVIDENCE;
+
+pub const DEFAULT_INTERVAL_HOURS: u32 = 24;
+
+// Linhas por DELETE (limite de parâmetros do SQLite)
+const DELETE_CHUNK: usize = 500;
+const WIPE_BLOCK:   usize = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    // Expurgo automático; a prévia funciona mesmo desligado
+    pub enabled:        bool,
+    pub default_days:   Option<u32>,
+    pub categories:     BTreeMap<String, u32>,
+    pub interval_hours: Option<u32>,
+}
+
+impl RetentionPolicy {
+    pub fn days_for(&self, category: &str) -> Option<u32> {
+        self.categories.get(category).copied().or(self.default_days)
+    }
+
+    pub fn interval(&self) -> Duration {
+        let hours = self.interval_hours.unwrap_or(DEFAULT_INTERVAL_HOURS).max(1);
+        Duration::from_secs(u64::from(hours) * 3600)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.default_days == Some(0) {
+            return Err(Error::InvalidInput("default_days".into(), "prazo deve ser de pelo menos 1 dia".into()));
+        }
+        if let Some((category, _)) = self.categories.iter().find(|(_, days)| **days == 0) {
+            return Err(Error::InvalidInput(format!("categories.{category}"), "prazo deve ser de pelo menos 1 dia".into()));
+        }
+        if self.categories.keys().any(|c| c.trim().is_empty()) {
+            return Err(Error::InvalidInput("categories".into(), "categoria sem nome".into()));
+        }
+        Ok(())
+    }
+}
+
+// O que aconteceu (ou, na prévia, o que vai acontecer) com o arquivo da evidência
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileOutcome {
+    // Prévia: o arquivo existe e será apagado
+    Present,
+    Removed,
+    Missing,
+    // Também citado por evidência dentro do prazo: fica no disco
+    Shared,
+    Failed,
+    // Caminho relativo sem pasta de dados, ou pasta que não existe: a linha fica
+    Unresolved,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ExpiredEvidence {
+    pub evidence_id:   String,
+    pub individual_id: Option<String>,
+    pub category:      String,
+    pub captured_at:   String,
+    pub file_path:     String,
+    pub resolved_path: Option<PathBuf>,
+    pub file:          FileOutcome,
+    pub row_deleted:   bool,
+    pub detail:        Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct PurgeReport {
+    pub dry_run:       bool,
+    pub started_at:    String,
+    pub finished_at:   String,
+    pub policy:        RetentionPolicy,
+    pub expired:       usize,
+    pub by_category:   BTreeMap<String, usize>,
+    pub rows_deleted:  usize,
+    pub files_removed: usize,
+    pub files_missing: usize,
+    pub files_shared:  usize,
+    pub files_unresolved: usize,
+    pub failures:      usize,
+    pub items:         Vec<ExpiredEvidence>,
+}
+
+impl PurgeReport {
+    pub fn deleted_ids(&self) -> Vec<String> {
+        self.items.iter().filter(|i| i.row_deleted).map(|i| i.evidence_id.clone()).collect()
+    }
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+// `captured_at` é gravado em UTC (CURRENT_TIMESTAMP), no formato do SQLite
+fn cutoff(days: u32) -> String {
+    (chrono::Utc::now() - chrono::Duration::days(i64::from(days))).format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+// Evidências vencidas, mais antigas primeiro. `captured_at` nulo nunca vence.
+fn expired(db: &dyn Backend, policy: &RetentionPolicy, root: Option<&Path>) -> Result<Vec<ExpiredEvidence>> {
+    let s = db.schema();
+    s.require_table(EVIDENCE)?;
+    let category = "COALESCE(i.category, '')";
+    let captured = s.timestamp(EVIDENCE, "e", "captured_at");
+
+    let mut conditions = vec![];
+    let mut vals: Vec<Value> = vec![];
+    for (name, days) in &policy.categories {
+        conditions.push(format!("({category} = ? AND {captured} < ?)"));
+        vals.push(name.as_str().into());
+        vals.push(cutoff(*days).into());
+    }
+    if let Some(days) = policy.default_days {
+        let others = if policy.categories.is_empty() {
+            String::new()
+        } else {
+            vals.extend(policy.categories.keys().map(|c| Value::from(c.as_str())));
+            format!("{category} NOT IN ({}) AND ", vec!["?"; policy.categories.len()].join(", "))
+        };
+        conditions.push(format!("({others}{captured} < ?)"));
+        vals.push(cutoff(days).into());
+    }
+    if conditions.is_empty() {
+        return Ok(vec![]);
+    }
+
+    db.query(
+        &format!(
+            "SELECT e.id, i.id, {category}, {captured}, {path} FROM evidence e
+             LEFT JOIN individuals i ON i.id = {individual}
+             WHERE {captured} IS NOT NULL AND ({conditions})
+             ORDER BY {captured}, e.id",
+            path = s.text(EVIDENCE, "e", "file_path"),
+            individual = s.text(EVIDENCE, "e", "individual_id"),
+            conditions = conditions.join(" OR "),
+        ),
+        &vals,
+        |r| {
+            let file_path = r.opt_text(4)?.unwrap_or_default();
+            Ok(ExpiredEvidence {
+                evidence_id:   r.text(0)?,
+                individual_id: r.opt_text(1)?,
+                category:      r.text(2)?,
+                captured_at:   r.opt_text(3)?.unwrap_or_default(),
+                resolved_path: integrity::resolve(root, &file_path),
+                file_path,
+                file:          FileOutcome::Missing,
+                row_deleted:   false,
+                detail:        None,
+            })
+        },
+    )
+}
+
+// Arquivo não encontrado: a ausência só é confirmada se a pasta onde ele estaria
+// existe — e, para caminho relativo, sob uma pasta de dados válida (disco
+// desmontado, pasta não configurada ou com outro layout não contam). Caminho vazio
+// não aponta para arquivo nenhum.
+fn confirmed_absent(root: Option<&Path>, file_path: &str) -> bool {
+    let path = Path::new(file_path.trim());
+    if path.as_os_str().is_empty() {
+        return true;
+    }
+    let parent_exists = |p: &Path| p.parent().is_some_and(Path::is_dir);
+    if path.is_absolute() {
+        return parent_exists(path);
+    }
+    root.filter(|root| check_data_root(root).valid)
+        .is_some_and(|root| EVIDENCE_DIR_CANDIDATES.iter().any(|dir| parent_exists(&root.join(dir).join(path))))
+}
+
+// O arquivo também é citado por alguma evidência fora de `expired`? Candidatas pelo
+// nome exato do arquivo (`_` e `%` do nome escapados no LIKE; `foto.jpg` não casa
+// com `xfoto.jpg`); a comparação final é pelo caminho resolvido (relativo ou absoluto).
+fn is_shared(db: &dyn Backend, root: Option<&Path>, path: &Path, expired: &HashSet<&str>) -> Result<bool> {
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+        return Ok(false);
+    };
+    let column = db.schema().text(EVIDENCE, "e", "file_path");
+    let pattern = name.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    let others = db.query(
+        &format!("SELECT e.id, {column} FROM evidence e WHERE {column} LIKE ? ESCAPE '\\'"),
+        &[format!("%{pattern}").into()],
+        |r| Ok((r.text(0)?, r.opt_text(1)?.unwrap_or_default())),
+    )?;
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    Ok(others.iter().any(|(id, file_path)| {
+        !expired.contains(id.as_str())
+            && file_path.rsplit(['/', '\\']).next() == Some(name.as_str())
+            && integrity::resolve(root, file_path).and_then(|p| fs::canonicalize(p).ok()).is_some_and(|p| p == target)
+    }))
+}
+
+// Sobrescreve com zeros, trunca e remove. Em SSD, sistemas copy-on-write ou com
+// snapshots os blocos antigos podem sobreviver; para esses casos vale a
+// criptografia do disco.
+fn wipe(path: &Path) -> std::io::Result<()> {
+    let mut file = File::options().write(true).open(path)?;
+    let len = file.metadata()?.len();
+    let zeros = vec![0u8; WIPE_BLOCK];
+    let mut left = len;
+    while left > 0 {
+        let n = left.min(WIPE_BLOCK as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        left -= n as u64;
+    }
+    file.sync_all()?;
+    file.set_len(0)?;
+    file.sync_all()?;
+    drop(file);
+    fs::remove_file(path)
+}
+
+fn delete_rows(db: &dyn Backend, ids: &[String]) -> Result<usize> {
+    let mut deleted = 0;
+    for chunk in ids.chunks(DELETE_CHUNK) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let vals: Vec<Value> = chunk.iter().map(|id| id.as_str().into()).collect();
+        deleted += db.execute(&format!("DELETE FROM evidence WHERE id IN ({placeholders})"), &vals)?;
+    }
+    Ok(deleted)
+}
+
+// ─── Expurgo ──────────────────────────────────────────────────────────────────
+
+// Com `dry_run` só lista o que venceu e o que seria feito com cada arquivo
+pub fn run(db: &dyn Backend, policy: &RetentionPolicy, root: Option<&Path>, dry_run: bool) -> Result<PurgeReport> {
+    policy.validate()?;
+    let started_at = now();
+    let mut items = expired(db, policy, root)?;
+    let expired_ids: HashSet<&str> = items.iter().map(|i| i.evidence_id.as_str()).collect();
+
+    // Um destino por arquivo: evidências vencidas podem apontar para o mesmo arquivo
+    let mut outcomes: BTreeMap<PathBuf, (FileOutcome, Option<String>)> = BTreeMap::new();
+    for item in &items {
+        let Some(path) = &item.resolved_path else { continue };
+        if outcomes.contains_key(path) {
+            continue;
+        }
+        let outcome = if is_shared(db, root, path, &expired_ids)? {
+            (FileOutcome::Shared, None)
+        } else if dry_run {
+            (FileOutcome::Present, None)
+        } else {
+            match wipe(path) {
+                Ok(()) => (FileOutcome::Removed, None),
+                Err(e) => {
+                    println!("[RETENTION] {}: {}", path.display(), e);
+                    (FileOutcome::Failed, Some(e.to_string()))
+                }
+            }
+        };
+        outcomes.insert(path.clone(), outcome);
+    }
+
+    let mut report = PurgeReport { dry_run, started_at, policy: policy.clone(), ..Default::default() };
+    let mut seen = HashSet::new();
+    for item in &mut items {
+        if let Some(path) = &item.resolved_path {
+            let (outcome, detail) = outcomes[path].clone();
+            item.file = outcome;
+            item.detail = detail;
+            if seen.insert(path.clone()) {
+                match outcome {
+                    FileOutcome::Removed => report.files_removed += 1,
+                    FileOutcome::Shared  => report.files_shared += 1,
+                    FileOutcome::Failed  => report.failures += 1,
+                    FileOutcome::Present | FileOutcome::Missing | FileOutcome::Unresolved => {}
+                }
+            }
+        } else if confirmed_absent(root, &item.file_path) {
+            report.files_missing += 1;
+        } else {
+            item.file = FileOutcome::Unresolved;
+            item.detail = Some("arquivo não localizado: confira a pasta de dados".into());
+            report.files_unresolved += 1;
+        }
+        *report.by_category.entry(item.category.clone()).or_default() += 1;
+    }
+    report.expired = items.len();
+
+    if !dry_run {
+        // Só sai a linha cujo arquivo foi apagado, ficou (compartilhado) ou comprovadamente não existe
+        let deletable = |i: &ExpiredEvidence| matches!(i.file, FileOutcome::Removed | FileOutcome::Shared | FileOutcome::Missing);
+        let ids: Vec<String> = items.iter().filter(|i| deletable(i)).map(|i| i.evidence_id.clone()).collect();
+        report.rows_deleted = delete_rows(db, &ids)?;
+        for item in items.iter_mut().filter(|i| deletable(i)) {
+            item.row_deleted = true;
+        }
+        println!(
+            "[RETENTION] {} evidências vencidas: {} linhas apagadas, {} arquivos removidos, {} ausentes, {} compartilhados, {} não localizados, {} falhas",
+            report.expired, report.rows_deleted, report.files_removed, report.files_missing, report.files_shared,
+            report.files_unresolved, report.failures
+        );
+    }
+    report.items = items;
+    report.finished_at = now();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, Fixture};
+
+    fn policy() -> RetentionPolicy {
+        RetentionPolicy {
+            default_days: Some(30),
+            categories: BTreeMap::from([("missing".to_string(), 365)]),
+            ..Default::default()
+        }
+    }
+
+    // `evidence` = (id, indivíduo, dias atrás, file_path)
+    fn fixture(name: &str, evidence: &[(&str, &str, u32, String)]) -> Fixture {
+        let mut seed = String::from(
+            "INSERT INTO individuals (id, name, category, source) VALUES
+                 ('w', 'Procurado', 'wanted', 'fbi'), ('m', 'Desaparecido', 'missing', 'fbi');",
+        );
+        for (id, individual, days, path) in evidence {
+            seed += &format!(
+                "INSERT INTO evidence (id, individual_id, file_hash, file_path, captured_at)
+                 VALUES ('{id}', '{individual}', '', '{path}', datetime('now', '-{days} days'));"
+            );
+        }
+        Fixture::new(&format!("retention_{name}"), &seed)
+    }
+
+    fn item<'a>(report: &'a PurgeReport, id: &str) -> &'a ExpiredEvidence {
+        report.items.iter().find(|i| i.evidence_id == id).unwrap()
+    }
+
+    fn remaining(f: &Fixture) -> Vec<String> {
+        let mut stmt = f.conn.prepare("SELECT id FROM evidence ORDER BY id").unwrap();
+        stmt.query_map([], |r| r.get(0)).unwrap().collect::<rusqlite::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn category_deadline_overrides_the_default() {
+        let f = fixture("cutoffs", &[
+            ("w_old", "w", 60, String::new()),
+            ("w_new", "w", 10, String::new()),
+            ("m_old", "m", 400, String::new()),
+            ("m_mid", "m", 60, String::new()),
+        ]);
+        let report = run(&f.db, &policy(), None, true).unwrap();
+        let ids: Vec<&str> = report.items.iter().map(|i| i.evidence_id.as_str()).collect();
+        assert_eq!(ids, ["m_old", "w_old"]);
+        assert_eq!(report.by_category, BTreeMap::from([("missing".to_string(), 1), ("wanted".to_string(), 1)]));
+
+        // Sem prazo padrão só vencem as categorias com prazo próprio
+        let only_missing = RetentionPolicy { default_days: None, ..policy() };
+        assert_eq!(run(&f.db, &only_missing, None, true).unwrap().expired, 1);
+        assert_eq!(run(&f.db, &RetentionPolicy::default(), None, true).unwrap().expired, 0);
+    }
+
+    #[test]
+    fn dry_run_changes_nothing() {
+        let dir = temp_dir("retention_dry");
+        std::fs::write(dir.join("a.jpg"), b"frame").unwrap();
+        let f = fixture("dry", &[("e1", "w", 60, dir.join("a.jpg").display().to_string())]);
+        let report = run(&f.db, &policy(), None, true).unwrap();
+        assert_eq!(item(&report, "e1").file, FileOutcome::Present);
+        assert_eq!((report.rows_deleted, report.files_removed), (0, 0));
+        assert!(dir.join("a.jpg").is_file());
+        assert_eq!(remaining(&f), ["e1"]);
+    }
+
+    #[test]
+    fn files_still_cited_by_live_evidence_are_kept() {
+        let dir = temp_dir("retention_shared");
+        for sub in ["a", "b"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+            std::fs::write(dir.join(sub).join("foto.jpg"), b"frame").unwrap();
+        }
+        std::fs::write(dir.join("a/xfoto.jpg"), b"frame").unwrap();
+        let path = |p: &str| dir.join(p).display().to_string();
+        let f = fixture("shared", &[
+            // Mesmo arquivo citado por uma evidência dentro do prazo (caminho relativo à pasta de dados)
+            ("old_a", "w", 60, path("a/foto.jpg")),
+            ("new_a", "w", 1, "a/foto.jpg".into()),
+            // Mesmo nome em outra pasta e nome com sufixo igual não são o mesmo arquivo
+            ("old_b", "w", 60, path("b/foto.jpg")),
+            ("new_x", "w", 1, path("a/xfoto.jpg")),
+        ]);
+        let report = run(&f.db, &policy(), Some(&dir), false).unwrap();
+        assert_eq!(item(&report, "old_a").file, FileOutcome::Shared);
+        assert_eq!(item(&report, "old_b").file, FileOutcome::Removed);
+        assert!(dir.join("a/foto.jpg").is_file());
+        assert!(!dir.join("b/foto.jpg").exists());
+        assert_eq!(remaining(&f), ["new_a", "new_x"]);
+        assert_eq!((report.files_shared, report.files_removed, report.rows_deleted), (1, 1, 2));
+    }
+
+    #[test]
+    fn rows_whose_file_cannot_be_located_stay() {
+        let dir = temp_dir("retention_unresolved");
+        let f = fixture("unresolved", &[
+            // Relativo sem pasta de dados: pode ser só o disco desmontado
+            ("relative", "w", 60, "frames/a.jpg".into()),
+            // Absoluto numa pasta que não existe
+            ("gone_dir", "w", 60, dir.join("nada/a.jpg").display().to_string()),
+            // Absoluto, pasta existe e o arquivo não: ausência confirmada
+            ("absent", "w", 60, dir.join("a.jpg").display().to_string()),
+        ]);
+        let report = run(&f.db, &policy(), None, false).unwrap();
+        assert_eq!(item(&report, "relative").file, FileOutcome::Unresolved);
+        assert_eq!(item(&report, "gone_dir").file, FileOutcome::Unresolved);
+        assert_eq!(item(&report, "absent").file, FileOutcome::Missing);
+        assert_eq!((report.files_unresolved, report.files_missing, report.rows_deleted), (2, 1, 1));
+        assert_eq!(remaining(&f), ["gone_dir", "relative"]);
+        assert_eq!(report.deleted_ids(), ["absent"]);
+    }
+}
@@ -0,0 +1,379 @@
+// Revisão humana dos avistamentos: cada evidência casada com um indivíduo começa
+// "pending" e o analista confirma, rejeita (falso positivo) ou marca como disputada.
+// A decisão atual e o histórico ficam no sidecar review.db — o intelligence.db não
+// é tocado. Avistamentos rejeitados nunca saem de `recent_sightings` daqui; o
+// dossiê da ingestão Python lê o mesmo arquivo para excluí-los — por padrão
+// `review.db` ao lado do intelligence.db (ou onde OSS_REVIEW_DB apontar, nos dois).
+// No SQLite o sidecar é anexado às conexões de leitura e o escopo vira um
+// EXISTS/NOT EXISTS na própria consulta; no Postgres as evidências são lidas em
+// lotes e filtradas aqui.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::backend::{Backend, Dialect};
+use crate::error::{Error, Result};
+use crate::model::{ReviewStatus, Sighting, SightingReview};
+use crate::repo;
+use crate::schema::EVIDENCE;
+use crate::sidecar;
+
+pub const REVIEW_FILE: &str = "review.db";
+pub const REVIEW_ALIAS: &str = "review";
+
+// Evidências por lote quando o sidecar não pode ser anexado (Postgres)
+const SCAN_BATCH: u32 = 200;
+
+const MIGRATIONS: &[&str] = &[
+    // "pending" não é gravado em sighting_reviews (sem linha = pendente), só no histórico
+    "CREATE TABLE sighting_reviews (
+         evidence_id TEXT PRIMARY KEY,
+         status      TEXT NOT NULL CHECK (status IN ('confirmed', 'rejected', 'disputed')),
+         reviewer    TEXT NOT NULL,
+         notes       TEXT,
+         reviewed_at TEXT NOT NULL DEFAULT (datetime('now'))
+     );
+     CREATE INDEX sighting_reviews_status ON sighting_reviews (status);
+     CREATE TABLE review_history (
+         id          INTEGER PRIMARY KEY AUTOINCREMENT,
+         evidence_id TEXT NOT NULL,
+         status      TEXT NOT NULL,
+         reviewer    TEXT NOT NULL,
+         notes       TEXT,
+         reviewed_at TEXT NOT NULL DEFAULT (datetime('now'))
+     );
+     CREATE INDEX review_history_evidence ON review_history (evidence_id);",
+];
+
+// Quais avistamentos listar; rejeitados ficam de fora em todos
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewScope {
+    // Confirmados e disputados
+    #[default]
+    Reviewed,
+    Pending,
+    All,
+}
+
+impl ReviewScope {
+    fn includes(self, status: ReviewStatus) -> bool {
+        match self {
+            ReviewScope::Reviewed => matches!(status, ReviewStatus::Confirmed | ReviewStatus::Disputed),
+            ReviewScope::Pending  => status == ReviewStatus::Pending,
+            ReviewScope::All      => status != ReviewStatus::Rejected,
+        }
+    }
+
+    // A mesma regra sobre `e`, com o sidecar anexado
+    fn sql(self) -> String {
+        let review = format!("SELECT 1 FROM {REVIEW_ALIAS}.sighting_reviews r WHERE r.evidence_id = e.id");
+        match self {
+            ReviewScope::Reviewed => format!("EXISTS ({review} AND r.status IN ('confirmed', 'disputed'))"),
+            ReviewScope::Pending  => format!("NOT EXISTS ({review})"),
+            ReviewScope::All      => format!("NOT EXISTS ({review} AND r.status = 'rejected')"),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ReviewEvent {
+    pub evidence_id: String,
+    pub status:      ReviewStatus,
+    pub reviewer:    String,
+    pub notes:       Option<String>,
+    pub reviewed_at: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ReviewCounts {
+    pub pending:   i64,
+    pub confirmed: i64,
+    pub rejected:  i64,
+    pub disputed:  i64,
+}
+
+fn map_review(r: &rusqlite::Row) -> rusqlite::Result<(String, SightingReview)> {
+    let status: String = r.get(1)?;
+    let review = SightingReview {
+        status:      ReviewStatus::parse(&status),
+        reviewer:    r.get(2)?,
+        notes:       r.get(3)?,
+        reviewed_at: r.get(4)?,
+    };
+    Ok((r.get(0)?, review))
+}
+
+// Decisões atuais das evidências de uma página (ou lote)
+fn reviews(conn: &Connection, sightings: &[Sighting]) -> Result<HashMap<String, SightingReview>> {
+    let mut out = HashMap::new();
+    for chunk in sightings.chunks(SCAN_BATCH as usize) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT evidence_id, status, reviewer, notes, reviewed_at FROM sighting_reviews WHERE evidence_id IN ({placeholders})"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(chunk.iter().map(|s| &s.id)), map_review)?;
+        for row in rows {
+            let (id, review) = row?;
+            out.insert(id, review);
+        }
+    }
+    Ok(out)
+}
+
+fn review(conn: &Connection, evidence_id: &str) -> Result<Option<SightingReview>> {
+    Ok(conn
+        .query_row(
+            "SELECT evidence_id, status, reviewer, notes, reviewed_at FROM sighting_reviews WHERE evidence_id = ?1",
+            [evidence_id],
+            map_review,
+        )
+        .optional()?
+        .map(|(_, review)| review))
+}
+
+// Abre (migra) o sidecar e, no SQLite, o anexa às conexões de leitura
+pub fn attach(db: &dyn Backend, path: &Path) -> Result<()> {
+    drop(sidecar::open(path, MIGRATIONS)?);
+    if db.dialect() == Dialect::Sqlite && !db.is_attached(REVIEW_ALIAS) {
+        db.attach(REVIEW_ALIAS, path)?;
+    }
+    Ok(())
+}
+
+// Avistamentos mais recentes do escopo, já com a revisão de cada um
+pub fn recent_sightings(db: &dyn Backend, path: &Path, scope: ReviewScope, limit: u32) -> Result<Vec<Sighting>> {
+    attach(db, path)?;
+    let conn = sidecar::open(path, MIGRATIONS)?;
+
+    if db.is_attached(REVIEW_ALIAS) {
+        let mut sightings = repo::recent_sightings(db, limit, 0, Some(&scope.sql()))?;
+        let mut reviews = reviews(&conn, &sightings)?;
+        for s in &mut sightings {
+            s.review = reviews.remove(&s.id).unwrap_or_default();
+        }
+        return Ok(sightings);
+    }
+
+    let mut out = Vec::with_capacity(limit as usize);
+    let mut offset = 0;
+    while out.len() < limit as usize {
+        let batch = repo::recent_sightings(db, SCAN_BATCH, offset, None)?;
+        let done = batch.len() < SCAN_BATCH as usize;
+        let mut reviews = reviews(&conn, &batch)?;
+        for mut s in batch {
+            s.review = reviews.remove(&s.id).unwrap_or_default();
+            if scope.includes(s.review.status) && out.len() < limit as usize {
+                out.push(s);
+            }
+        }
+        if done {
+            break;
+        }
+        offset += SCAN_BATCH;
+    }
+    Ok(out)
+}
+
+// Grava a decisão (ou volta para pendente) e registra no histórico
+pub fn set_review(
+    db:          &dyn Backend,
+    path:        &Path,
+    evidence_id: &str,
+    status:      ReviewStatus,
+    reviewer:    &str,
+    notes:       Option<&str>,
+) -> Result<SightingReview> {
+    let reviewer = reviewer.trim();
+    if reviewer.is_empty() {
+        return Err(Error::InvalidInput("reviewer".into(), "identificação do revisor obrigatória".into()));
+    }
+    db.schema().require_table(EVIDENCE)?;
+    if db.count("SELECT COUNT(*) FROM evidence WHERE id = ?", &[evidence_id.into()])? == 0 {
+        return Err(Error::NotFound(evidence_id.to_string()));
+    }
+    let notes = notes.map(str::trim).filter(|n| !n.is_empty());
+
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    let tx = conn.transaction()?;
+    match status {
+        ReviewStatus::Pending => {
+            tx.execute("DELETE FROM sighting_reviews WHERE evidence_id = ?1", [evidence_id])?;
+        }
+        _ => {
+            tx.execute(
+                "INSERT INTO sighting_reviews (evidence_id, status, reviewer, notes) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (evidence_id) DO UPDATE SET
+                     status = excluded.status, reviewer = excluded.reviewer, notes = excluded.notes,
+                     reviewed_at = datetime('now')",
+                params![evidence_id, status.as_str(), reviewer, notes],
+            )?;
+        }
+    }
+    tx.execute(
+        "INSERT INTO review_history (evidence_id, status, reviewer, notes) VALUES (?1, ?2, ?3, ?4)",
+        params![evidence_id, status.as_str(), reviewer, notes],
+    )?;
+    tx.commit()?;
+    println!("[REVIEW] {evidence_id}: {} por {reviewer}", status.as_str());
+
+    Ok(review(&conn, evidence_id)?.unwrap_or_default())
+}
+
+pub fn history(path: &Path, evidence_id: &str) -> Result<Vec<ReviewEvent>> {
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let mut stmt = conn.prepare(
+        "SELECT evidence_id, status, reviewer, notes, reviewed_at FROM review_history
+         WHERE evidence_id = ?1 ORDER BY id DESC",
+    )?;
+    let rows = stmt.query_map([evidence_id], |r| {
+        let status: String = r.get(1)?;
+        Ok(ReviewEvent {
+            evidence_id: r.get(0)?,
+            status:      ReviewStatus::parse(&status),
+            reviewer:    r.get(2)?,
+            notes:       r.get(3)?,
+            reviewed_at: r.get(4)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+// Pendentes = evidências sem decisão no sidecar
+pub fn counts(db: &dyn Backend, path: &Path) -> Result<ReviewCounts> {
+    db.schema().require_table(EVIDENCE)?;
+    let total = db.count("SELECT COUNT(*) FROM evidence", &[])?;
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let mut out = ReviewCounts::default();
+    let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM sighting_reviews GROUP BY status")?;
+    let rows = stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))?;
+    for row in rows {
+        let (status, count) = row?;
+        match ReviewStatus::parse(&status) {
+            ReviewStatus::Confirmed => out.confirmed = count,
+            ReviewStatus::Rejected  => out.rejected = count,
+            ReviewStatus::Disputed  => out.disputed = count,
+            ReviewStatus::Pending   => {}
+        }
+    }
+    out.pending = (total - out.confirmed - out.rejected - out.disputed).max(0);
+    Ok(out)
+}
+
+// Decisões atuais de evidências que saíram do banco (ex.: expurgo da retenção);
+// o histórico fica
+pub fn forget(path: &Path, ids: &[String]) -> Result<usize> {
+    let conn = sidecar::open(path, MIGRATIONS)?;
+    let mut removed = 0;
+    for id in ids {
+        removed += conn.execute("DELETE FROM sighting_reviews WHERE evidence_id = ?1", [id])?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, Detached, Fixture};
+    use std::path::PathBuf;
+
+    // e000..e449, o maior número é o mais recente: mais de dois lotes de SCAN_BATCH
+    const SEED: &str = "
+        INSERT INTO individuals (id, name, category, source) VALUES ('i1', 'Fulano', 'wanted', 'fbi');
+        WITH RECURSIVE n(v) AS (SELECT 0 UNION ALL SELECT v + 1 FROM n WHERE v < 449)
+        INSERT INTO evidence (id, individual_id, file_hash, file_path, captured_at)
+        SELECT printf('e%03d', v), 'i1', 'h', 'f.jpg', datetime('2026-01-01', '+' || v || ' minutes') FROM n;";
+
+    fn setup(name: &str) -> (Fixture, PathBuf) {
+        let f = Fixture::new(&format!("review_{name}"), SEED);
+        (f, temp_dir(&format!("review_{name}")).join(REVIEW_FILE))
+    }
+
+    fn ids(sightings: &[Sighting]) -> Vec<String> {
+        sightings.iter().map(|s| s.id.clone()).collect()
+    }
+
+    #[test]
+    fn set_review_validates_reviewer_and_evidence() {
+        let (f, path) = setup("validation");
+        assert!(matches!(set_review(&f.db, &path, "e001", ReviewStatus::Confirmed, "  ", None), Err(Error::InvalidInput(..))));
+        assert!(matches!(set_review(&f.db, &path, "zz", ReviewStatus::Confirmed, "ana", None), Err(Error::NotFound(_))));
+
+        let g = Fixture::new("review_no_evidence", "DROP TABLE evidence;");
+        assert!(matches!(set_review(&g.db, &path, "e001", ReviewStatus::Confirmed, "ana", None), Err(Error::MissingTable(_))));
+        assert!(matches!(counts(&g.db, &path), Err(Error::MissingTable(_))));
+    }
+
+    #[test]
+    fn transitions_keep_the_full_history() {
+        let (f, path) = setup("transitions");
+        let confirmed = set_review(&f.db, &path, "e001", ReviewStatus::Confirmed, " ana ", Some("  ")).unwrap();
+        assert_eq!((confirmed.status, confirmed.reviewer.as_deref(), confirmed.notes), (ReviewStatus::Confirmed, Some("ana"), None));
+        let rejected = set_review(&f.db, &path, "e001", ReviewStatus::Rejected, "bia", Some(" outra pessoa ")).unwrap();
+        assert_eq!((rejected.status, rejected.notes.as_deref()), (ReviewStatus::Rejected, Some("outra pessoa")));
+
+        // Voltar para pendente apaga a decisão atual, não o histórico
+        let pending = set_review(&f.db, &path, "e001", ReviewStatus::Pending, "ana", None).unwrap();
+        assert_eq!(pending.status, ReviewStatus::Pending);
+        assert!(pending.reviewer.is_none());
+        let events: Vec<_> = history(&path, "e001").unwrap().into_iter().map(|e| (e.status, e.reviewer)).collect();
+        assert_eq!(
+            events,
+            [(ReviewStatus::Pending, "ana".into()), (ReviewStatus::Rejected, "bia".into()), (ReviewStatus::Confirmed, "ana".into())]
+        );
+        assert!(history(&path, "e002").unwrap().is_empty());
+    }
+
+    #[test]
+    fn counts_and_forget() {
+        let (f, path) = setup("counts");
+        set_review(&f.db, &path, "e001", ReviewStatus::Confirmed, "ana", None).unwrap();
+        set_review(&f.db, &path, "e002", ReviewStatus::Rejected, "ana", None).unwrap();
+        set_review(&f.db, &path, "e003", ReviewStatus::Disputed, "ana", None).unwrap();
+        let c = counts(&f.db, &path).unwrap();
+        assert_eq!((c.pending, c.confirmed, c.rejected, c.disputed), (447, 1, 1, 1));
+
+        assert_eq!(forget(&path, &["e001".into(), "e002".into(), "e404".into()]).unwrap(), 2);
+        let c = counts(&f.db, &path).unwrap();
+        assert_eq!((c.pending, c.confirmed, c.rejected), (449, 0, 0));
+        assert_eq!(history(&path, "e001").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn scopes_match_with_and_without_attach() {
+        let (f, path) = setup("scopes");
+        // Os 250 mais recentes rejeitados: o primeiro lote inteiro fica de fora
+        let conn = sidecar::open(&path, MIGRATIONS).unwrap();
+        conn.execute_batch(
+            "WITH RECURSIVE n(v) AS (SELECT 200 UNION ALL SELECT v + 1 FROM n WHERE v < 449)
+             INSERT INTO sighting_reviews (evidence_id, status, reviewer) SELECT printf('e%03d', v), 'rejected', 'ana' FROM n;",
+        )
+        .unwrap();
+        drop(conn);
+        set_review(&f.db, &path, "e005", ReviewStatus::Confirmed, "ana", Some("confere")).unwrap();
+        set_review(&f.db, &path, "e003", ReviewStatus::Disputed, "bia", None).unwrap();
+
+        let detached = Detached(&f.db);
+        for scope in [ReviewScope::Reviewed, ReviewScope::Pending, ReviewScope::All] {
+            let attached = recent_sightings(&f.db, &path, scope, 10).unwrap();
+            let batched = recent_sightings(&detached, &path, scope, 10).unwrap();
+            assert_eq!(ids(&attached), ids(&batched), "{scope:?}");
+            let statuses = |s: &[Sighting]| s.iter().map(|s| s.review.status).collect::<Vec<_>>();
+            assert_eq!(statuses(&attached), statuses(&batched), "{scope:?}");
+        }
+        assert!(f.db.is_attached(REVIEW_ALIAS));
+        assert!(!detached.is_attached(REVIEW_ALIAS));
+
+        let reviewed = recent_sightings(&detached, &path, ReviewScope::Reviewed, 10).unwrap();
+        assert_eq!(ids(&reviewed), ["e005", "e003"]);
+        assert_eq!(reviewed[0].review.notes.as_deref(), Some("confere"));
+        let pending = recent_sightings(&detached, &path, ReviewScope::Pending, 3).unwrap();
+        assert_eq!(ids(&pending), ["e199", "e198", "e197"]);
+        // Mais do que um lote pedido: atravessa até o fim sem repetir
+        let all = recent_sightings(&detached, &path, ReviewScope::All, 500).unwrap();
+        assert_eq!(all.len(), 200);
+        assert_eq!(all.last().unwrap().id, "e000");
+    }
+}
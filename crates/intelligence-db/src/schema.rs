@@ -0,0 +1,94 @@
+// Constantes do schema do intelligence.db (ver intelligence/intelligence_db.py, SCHEMA_SQL).
+// Qualquer mudança de schema na ingestão Python deve ser refletida aqui.
+
+use crate::introspect::SchemaInfo;
+
+pub const INDIVIDUALS:       &str = "individuals";
+pub const CRIMES:            &str = "crimes";
+pub const LOCATIONS:         &str = "locations";
+pub const INDIVIDUAL_IMAGES: &str = "individual_images";
+pub const FACE_EMBEDDINGS:   &str = "face_embeddings";
+pub const EVIDENCE:          &str = "evidence";
+pub const THREAT_SCORES:     &str = "threat_scores";
+
+pub const CATEGORY_WANTED:  &str = "wanted";
+pub const CATEGORY_MISSING: &str = "missing";
+
+// Tabelas/colunas sem as quais o banco é recusado (MissingTable / MissingColumn)
+pub const REQUIRED_COLUMNS: &[(&str, &[&str])] = &[
+    (INDIVIDUALS, &["id", "name", "category", "source"]),
+];
+
+// Versões do schema detectadas por introspecção (o intelligence_db.py não grava versão):
+// a versão é o último nível com todos os recursos presentes, cumulativamente.
+pub const SCHEMA_LEVELS: &[&[&str]] = &[
+    // 1 — catálogo básico
+    &[CRIMES, LOCATIONS, INDIVIDUAL_IMAGES, "individuals.has_embedding", "individuals.ingested_at"],
+    // 2 — atributos físicos
+    &["individuals.height_cm", "individuals.weight_kg", "individuals.eye_color",
+      "individuals.hair_color", "individuals.occupation"],
+    // 3 — biometria e evidências
+    &[FACE_EMBEDDINGS, EVIDENCE, THREAT_SCORES],
+    // 4 — câmera de origem das evidências (Fase 13)
+    &["evidence.camera_id"],
+    // 5 — proveniência do match (distância, probabilidade, confiança, modelo)
+    &["evidence.match_distance", "evidence.match_probability", "evidence.match_confidence",
+      "evidence.model", "evidence.model_version"],
+];
+
+// Listas de colunas na ordem esperada pelos mappers. Colunas ausentes no banco
+// viram NULL tipado (ver SchemaInfo); os CASTs deixam os tipos iguais nos dois
+// backends: no Postgres `ingested_at` é TIMESTAMP e `height_cm`/`weight_kg` são REAL.
+
+// `individuals` (alias `i`)
+pub fn individual_summary_columns(s: &SchemaInfo) -> String {
+    let t = |c: &str| s.text(INDIVIDUALS, "i", c);
+    [
+        "i.id".into(), "i.name".into(), "i.category".into(), "i.source".into(),
+        t("birth_date"), t("nationalities"), t("description"), t("reward"), t("img_path"),
+        s.int(INDIVIDUALS, "i", "has_embedding"), s.timestamp(INDIVIDUALS, "i", "ingested_at"),
+    ]
+    .join(", ")
+}
+
+pub fn individual_detail_columns(s: &SchemaInfo) -> String {
+    let t = |c: &str| s.text(INDIVIDUALS, "i", c);
+    [
+        "i.id".into(), "i.name".into(), "i.category".into(), "i.source".into(),
+        t("birth_date"), t("nationalities"), t("description"), t("reward"), t("img_path"),
+        s.int(INDIVIDUALS, "i", "has_embedding"), t("aliases"), t("sex"), t("url"),
+        s.timestamp(INDIVIDUALS, "i", "ingested_at"),
+        s.real(INDIVIDUALS, "i", "height_cm"), s.real(INDIVIDUALS, "i", "weight_kg"),
+        t("eye_color"), t("hair_color"), t("occupation"),
+    ]
+    .join(", ")
+}
+
+pub fn location_columns(s: &SchemaInfo) -> String {
+    let t = |c: &str| s.text(LOCATIONS, "l", c);
+    ["l.type".into(), t("country"), t("state"), t("city"), t("details")].join(", ")
+}
+
+pub fn image_columns(s: &SchemaInfo) -> String {
+    let t = |c: &str| s.text(INDIVIDUAL_IMAGES, "m", c);
+    [t("img_url"), t("img_path"), t("caption"), s.int(INDIVIDUAL_IMAGES, "m", "is_primary")].join(", ")
+}
+
+// Avistamentos: evidence `e` + individuals `i` (+ threat_scores `t`, quando existir).
+// Sem threat_scores (ou sem score para o indivíduo) o score vem NULL, nunca um padrão.
+pub fn sighting_columns(s: &SchemaInfo) -> String {
+    let e = |c: &str| s.text(EVIDENCE, "e", c);
+    // `t` só entra no JOIN com threat_scores.score presente
+    let (score, factors) = if s.has_threat_scores() {
+        (s.real(THREAT_SCORES, "t", "score"), s.text(THREAT_SCORES, "t", "factors_json"))
+    } else {
+        ("CAST(NULL AS DOUBLE PRECISION)".into(), "CAST(NULL AS TEXT)".into())
+    };
+    [
+        "e.id".into(), "e.individual_id".into(), e("camera_id"),
+        s.timestamp(EVIDENCE, "e", "captured_at"), "i.name".into(), score, factors,
+        e("file_hash"), s.real(EVIDENCE, "e", "match_distance"), s.real(EVIDENCE, "e", "match_probability"),
+        e("match_confidence"), e("model"), e("model_version"),
+    ]
+    .join(", ")
+}
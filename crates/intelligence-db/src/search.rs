@@ -0,0 +1,993 @@
+// Busca do catálogo: filtros, texto livre (FTS5 / similaridade / LIKE), ordenação
+// e paginação por cursor (keyset). O cursor guarda os valores das chaves de
+// ordenação da última linha da página; a próxima página começa depois deles,
+// sem OFFSET (páginas profundas custam o mesmo que a primeira).
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{Backend, Dialect, Record, Value};
+use crate::error::{Error, Result};
+use crate::model::{FacetCount, Facets, Individual, SearchPage};
+use crate::normalize::{self, FUZZY_THRESHOLD};
+use crate::query::{self, Field, Has, Node, Term};
+use crate::repo::map_individual;
+use crate::schema::{individual_summary_columns, INDIVIDUALS, LOCATIONS};
+use std::collections::HashMap;
+use crate::linkage::{self, LINKAGE_ALIAS};
+use crate::search_index::{self, INDEX_ALIAS, RANK_FUNCTION};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 40;
+pub const MAX_PAGE_SIZE:     u32 = 200;
+// Valores por faceta (crimes e nacionalidades têm cauda longa)
+pub const FACET_LIMIT:       i64 = 50;
+
+// Colunas lidas por map_individual (resumo + trecho + score); as chaves de ordenação vêm depois
+const KEY_COLUMN_OFFSET: usize = 13;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    // Rank do FTS / similaridade, depois biometria e ingestão mais recente
+    #[default]
+    Relevance,
+    Name,
+    IngestedAt,
+    Source,
+    Reward,
+    BirthDate,
+}
+
+impl SortKey {
+    // Direção quando o chamador não informa `descending`
+    fn default_descending(self) -> bool {
+        matches!(self, SortKey::IngestedAt | SortKey::Reward)
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct SearchFilter {
+    pub name:          Option<String>,
+    // Sintaxe de consulta (query.rs): `source:interpol crime:fraud born:<1980 "jose silva"`
+    pub query:         Option<String>,
+    pub category:      Option<String>,
+    pub country:       Option<String>,
+    pub crime:         Option<String>,
+    pub has_embedding: Option<bool>,
+    pub source:        Option<String>,
+    // Local em `locations` (valores exatos, sem diferenciar maiúsculas), todos na
+    // mesma linha; `location_type` restringe ao tipo (última vez visto, residência...)
+    pub location_type:    Option<String>,
+    pub location_country: Option<String>,
+    pub location_state:   Option<String>,
+    pub location_city:    Option<String>,
+    pub has_images:    Option<bool>,
+    // Faixas sobre os valores normalizados (normalize.rs): idade em anos completos,
+    // altura em cm, peso em kg; cores pela chave canônica ou qualquer variante
+    // reconhecida ("castanhos" = "brown")
+    pub age_min:       Option<u32>,
+    pub age_max:       Option<u32>,
+    pub height_min:    Option<f64>,
+    pub height_max:    Option<f64>,
+    pub weight_min:    Option<f64>,
+    pub weight_max:    Option<f64>,
+    pub eye_color:     Option<String>,
+    pub hair_color:    Option<String>,
+    // Registros ligados pelo analista (linkage.rs) aparecem uma vez só: fica o
+    // primeiro (menor id) do grupo que passa nos filtros
+    pub group_linked:  Option<bool>,
+    // Busca aproximada por nome (variantes de grafia/transliteração), ordenada por similaridade
+    pub fuzzy:         Option<bool>,
+    pub sort:          Option<SortKey>,
+    pub descending:    Option<bool>,
+    // `next_cursor` da página anterior; sem cursor, `page` pula direto (OFFSET)
+    pub cursor:        Option<String>,
+    pub page:          Option<u32>,
+    pub limit:         Option<u32>,
+    // Calcula as facetas junto com a página (ignorado com cursor: são as mesmas da primeira)
+    pub facets:        Option<bool>,
+}
+
+// Dimensões com contagem na barra lateral do catálogo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Facet {
+    Category,
+    Source,
+    Nationality,
+    Crime,
+    LocationCountry,
+    HasImages,
+}
+
+// ─── Texto livre ──────────────────────────────────────────────────────────────
+
+// Como o texto livre (`name`) entra na consulta: índice FTS5, similaridade de
+// nomes (ambos no sidecar anexado) ou LIKE quando não há índice (ex.: Postgres)
+struct TextMatch {
+    join:        &'static str,
+    cond:        Option<Cond>,
+    columns:     String, // trecho destacado, score de similaridade
+    column_vals: Vec<Value>,
+    rank:        Option<OrderKey>,
+}
+
+fn text_match(db: &dyn Backend, text: &str, fuzzy: bool) -> TextMatch {
+    let mut m = TextMatch {
+        join:        "",
+        cond:        None,
+        columns:     "CAST(NULL AS TEXT), CAST(NULL AS DOUBLE PRECISION)".into(),
+        column_vals: vec![],
+        rank:        None,
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return m;
+    }
+
+    if db.is_attached(INDEX_ALIAS) {
+        let expr = search_index::match_expr(text);
+        let folded = normalize::fold(text);
+        if fuzzy && !folded.is_empty() {
+            let mut by_name = "SELECT id FROM name_keys WHERE name_similarity(folded, ?) >= ?".to_string();
+            let mut vals: Vec<Value> = vec![];
+            if let Some(expr) = expr {
+                by_name = format!("SELECT id FROM individuals_fts WHERE individuals_fts MATCH ? UNION {by_name}");
+                vals.push(expr.into());
+            }
+            vals.push(folded.clone().into());
+            vals.push(FUZZY_THRESHOLD.into());
+            m.cond = Some(Cond::new(None, format!("i.id IN ({by_name})"), vals));
+
+            let score = "COALESCE((SELECT MAX(name_similarity(k.folded, ?)) FROM name_keys k WHERE k.id = i.id), 0.0)";
+            m.columns = format!("CAST(NULL AS TEXT), {score}");
+            m.column_vals.push(folded.clone().into());
+            m.rank = Some(OrderKey { vals: vec![folded.into()], ..OrderKey::new(score, KeyKind::Real, true) });
+            return m;
+        }
+        if let Some(expr) = expr {
+            // `rank` do FTS5 com os pesos por coluna: menor = mais relevante
+            m.join = "JOIN individuals_fts ON individuals_fts.id = i.id";
+            m.cond = Some(Cond::new(
+                None,
+                "individuals_fts MATCH ? AND individuals_fts.rank MATCH ?",
+                vec![expr.into(), RANK_FUNCTION.into()],
+            ));
+            m.columns = format!("{}, CAST(NULL AS DOUBLE PRECISION)", search_index::snippet_expr());
+            m.rank = Some(OrderKey::new("individuals_fts.rank", KeyKind::Real, false));
+            return m;
+        }
+    }
+
+    let description = db.schema().text(INDIVIDUALS, "i", "description");
+    m.cond = Some(Cond::new(
+        None,
+        format!("(i.name LIKE ? OR {description} LIKE ?)"),
+        vec![format!("%{text}%").into(), format!("%{text}%").into()],
+    ));
+    m
+}
+
+// ─── Filtros ──────────────────────────────────────────────────────────────────
+// Cada condição do WHERE sabe a que faceta pertence: a contagem de uma faceta usa
+// todos os filtros menos os dela, para a barra lateral continuar mostrando as
+// alternativas ("FBI (312) / Interpol (1.204)" mesmo com FBI selecionado).
+
+struct Cond {
+    facet: Option<Facet>,
+    sql:   String,
+    vals:  Vec<Value>,
+}
+
+impl Cond {
+    fn new(facet: Option<Facet>, sql: impl Into<String>, vals: Vec<Value>) -> Self {
+        Cond { facet, sql: sql.into(), vals }
+    }
+}
+
+struct Filters {
+    join:           &'static str,
+    conds:          Vec<Cond>,
+    // Tipo/estado/cidade escolhidos: a faceta de país conta só os locais que os satisfazem
+    location_scope: Vec<(&'static str, String)>,
+    // `group_linked` com o linkage.db anexado: resultados e facetas contam um por grupo
+    group_linked:   bool,
+}
+
+impl Filters {
+    // WHERE (sem a palavra) e parâmetros, sem as condições da faceta `except`
+    fn clause(&self, except: Option<Facet>) -> (String, Vec<Value>) {
+        let mut sql = vec!["1=1"];
+        let mut vals = vec![];
+        for cond in self.conds.iter().filter(|c| except.is_none() || c.facet != except) {
+            sql.push(&cond.sql);
+            vals.extend(cond.vals.iter().cloned());
+        }
+        let where_clause = sql.join(" AND ");
+        if !self.group_linked {
+            return (where_clause, vals);
+        }
+        // Subconsulta com o mesmo filtro (o `i` interno esconde o externo): outro
+        // membro do grupo com id menor também passa, então este fica de fora
+        let grouped = format!(
+            "{where_clause} AND NOT EXISTS (
+                 SELECT 1 FROM {LINKAGE_ALIAS}.link_groups g
+                 JOIN {LINKAGE_ALIAS}.link_groups h ON h.group_id = g.group_id
+                 WHERE g.id = i.id AND h.id < i.id
+                   AND h.id IN (SELECT i.id FROM individuals i {join} WHERE {where_clause}))",
+            join = self.join
+        );
+        vals.extend(vals.clone());
+        (grouped, vals)
+    }
+}
+
+pub(crate) fn has_images_expr(db: &dyn Backend) -> &'static str {
+    if db.schema().has_images() {
+        "EXISTS (SELECT 1 FROM individual_images m WHERE m.individual_id = i.id)"
+    } else {
+        "1=0"
+    }
+}
+
+fn filters(db: &dyn Backend, f: &SearchFilter, text: &mut TextMatch) -> Result<Filters> {
+    let s = db.schema();
+    let non_empty = |v: &Option<String>| v.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+    let mut conds = vec![];
+
+    conds.extend(text.cond.take());
+    if let Some(c) = non_empty(&f.category) {
+        conds.push(Cond::new(Some(Facet::Category), "i.category = ?", vec![c.into()]));
+    }
+    if let Some(co) = non_empty(&f.country) {
+        let nationalities = s.text(INDIVIDUALS, "i", "nationalities");
+        conds.push(Cond::new(Some(Facet::Nationality), format!("{nationalities} LIKE ?"), vec![format!("%{co}%").into()]));
+    }
+    if let Some(src) = non_empty(&f.source) {
+        conds.push(Cond::new(Some(Facet::Source), "i.source LIKE ?", vec![format!("%{src}%").into()]));
+    }
+    if let Some(has_bio) = f.has_embedding {
+        let has_embedding = s.int(INDIVIDUALS, "i", "has_embedding");
+        conds.push(Cond::new(None, format!("COALESCE({has_embedding}, 0) = ?"), vec![Value::Int(has_bio as i64)]));
+    }
+    if let Some(cr) = non_empty(&f.crime) {
+        conds.push(if s.has_crimes() {
+            Cond::new(
+                Some(Facet::Crime),
+                "EXISTS (SELECT 1 FROM crimes c WHERE c.individual_id = i.id AND c.crime LIKE ?)",
+                vec![format!("%{cr}%").into()],
+            )
+        } else {
+            Cond::new(Some(Facet::Crime), "1=0", vec![])
+        });
+    }
+    let location: Vec<(&'static str, String)> = [
+        ("type", non_empty(&f.location_type)),
+        ("country", non_empty(&f.location_country)),
+        ("state", non_empty(&f.location_state)),
+        ("city", non_empty(&f.location_city)),
+    ]
+    .into_iter()
+    .filter_map(|(column, value)| value.map(|v| (column, v)))
+    .collect();
+    if !location.is_empty() {
+        conds.push(if s.has_locations() {
+            let terms: Vec<String> = location
+                .iter()
+                .map(|(column, _)| format!("LOWER({}) = LOWER(?)", s.text(LOCATIONS, "l", column)))
+                .collect();
+            Cond::new(
+                Some(Facet::LocationCountry),
+                format!("EXISTS (SELECT 1 FROM locations l WHERE l.individual_id = i.id AND {})", terms.join(" AND ")),
+                location.iter().map(|(_, v)| v.as_str().into()).collect(),
+            )
+        } else {
+            Cond::new(Some(Facet::LocationCountry), "1=0", vec![])
+        });
+    }
+    if let Some(has_images) = f.has_images {
+        let expr = has_images_expr(db);
+        let sql = if has_images { expr.to_string() } else { format!("NOT {expr}") };
+        conds.push(Cond::new(Some(Facet::HasImages), sql, vec![]));
+    }
+    descriptor_filters(db, f, &mut conds)?;
+    let location_scope = location.into_iter().filter(|(column, _)| *column != "country").collect();
+    let group_linked = f.group_linked.unwrap_or(false) && db.is_attached(LINKAGE_ALIAS);
+    Ok(Filters { join: text.join, conds, location_scope, group_linked })
+}
+
+// Idade (pela data normalizada: "1980" conta como 1980-01-01), altura, peso e cores
+fn descriptor_filters(db: &dyn Backend, f: &SearchFilter, conds: &mut Vec<Cond>) -> Result<()> {
+    let today = normalize::today();
+    let birth_date = birth_date_expr(db);
+    // idade >= n: nasceu até hoje - n anos; idade <= n: depois de hoje - (n + 1) anos
+    if let Some(min) = f.age_min {
+        conds.push(Cond::new(None, format!("{birth_date} <= ?"), vec![normalize::born_by(today, min).to_string().into()]));
+    }
+    if let Some(max) = f.age_max {
+        let after = normalize::born_by(today, max.saturating_add(1));
+        conds.push(Cond::new(None, format!("{birth_date} > ?"), vec![after.to_string().into()]));
+    }
+
+    for (expr, min, max) in [
+        (height_expr(db), f.height_min, f.height_max),
+        (weight_expr(db), f.weight_min, f.weight_max),
+    ] {
+        if let Some(min) = min {
+            conds.push(Cond::new(None, format!("{expr} >= ?"), vec![min.into()]));
+        }
+        if let Some(max) = max {
+            conds.push(Cond::new(None, format!("{expr} <= ?"), vec![max.into()]));
+        }
+    }
+
+    for (field, value) in [("eye_color", &f.eye_color), ("hair_color", &f.hair_color)] {
+        let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        let key = normalize::color(value)
+            .ok_or_else(|| Error::InvalidInput(field.into(), format!("cor não reconhecida: {value}")))?;
+        let column = db.schema().text(INDIVIDUALS, "i", field);
+        conds.push(match db.dialect() {
+            Dialect::Sqlite => Cond::new(None, format!("color_key({column}) = ?"), vec![key.into()]),
+            Dialect::Postgres => {
+                let pattern = normalize::color_pattern(key).unwrap_or_default();
+                Cond::new(None, format!("{column} ~* ?"), vec![pattern.into()])
+            }
+        });
+    }
+    Ok(())
+}
+
+// Texto livre + filtros por parâmetro + consulta estruturada (`query`)
+fn prepare(db: &dyn Backend, f: &SearchFilter) -> Result<(TextMatch, Filters)> {
+    let parsed = match f.query.as_deref() {
+        Some(q) => query::parse(q)?,
+        None => None,
+    };
+    let (words, rest) = parsed.map(split_text).unwrap_or_default();
+    let text = [f.name.as_deref().unwrap_or_default(), &words.join(" ")].join(" ");
+
+    let mut text = text_match(db, &text, f.fuzzy.unwrap_or(false));
+    let mut filters = filters(db, f, &mut text)?;
+    for node in &rest {
+        let mut vals = vec![];
+        let sql = compile(db, node, &mut vals);
+        filters.conds.push(Cond::new(None, sql, vals));
+    }
+    Ok((text, filters))
+}
+
+// ─── Consulta estruturada ─────────────────────────────────────────────────────
+// Termos livres no nível de cima (AND) viram o texto da busca ranqueada (FTS,
+// trecho, similaridade); o resto vira condição do WHERE. Frases entram nos dois:
+// o texto ranqueia e a condição garante a frase exata.
+
+fn split_text(node: Node) -> (Vec<String>, Vec<Node>) {
+    let nodes = match node {
+        Node::And(nodes) => nodes,
+        other => vec![other],
+    };
+    let mut words = vec![];
+    let mut rest = vec![];
+    for node in nodes {
+        match node {
+            Node::Term(t) if t.field.is_none() => {
+                words.push(t.value.clone());
+                if t.phrase {
+                    rest.push(Node::Term(t));
+                }
+            }
+            other => rest.push(other),
+        }
+    }
+    (words, rest)
+}
+
+fn compile(db: &dyn Backend, node: &Node, vals: &mut Vec<Value>) -> String {
+    let join = |nodes: &[Node], op: &str, vals: &mut Vec<Value>| {
+        let parts: Vec<String> = nodes.iter().map(|n| compile(db, n, vals)).collect();
+        format!("({})", parts.join(op))
+    };
+    match node {
+        Node::And(nodes) => join(nodes, " AND ", vals),
+        Node::Or(nodes)  => join(nodes, " OR ", vals),
+        // CASE: NULL (coluna ausente/vazia) conta como falso antes da negação
+        Node::Not(inner) => format!("(CASE WHEN {} THEN 1 ELSE 0 END) = 0", compile(db, inner, vals)),
+        Node::Term(t)    => term_cond(db, t, vals),
+    }
+}
+
+fn birth_year_expr(db: &dyn Backend) -> String {
+    let birth_date = db.schema().text(INDIVIDUALS, "i", "birth_date");
+    match db.dialect() {
+        Dialect::Sqlite => format!("birth_year({birth_date})"),
+        Dialect::Postgres => format!("CAST(substring({birth_date} from '((19|20)[0-9][0-9])') AS BIGINT)"),
+    }
+}
+
+// Data de nascimento comparável (AAAA-MM-DD, ver normalize::birth_date); no
+// Postgres só os formatos ISO e o ano solto
+fn birth_date_expr(db: &dyn Backend) -> String {
+    let birth_date = db.schema().text(INDIVIDUALS, "i", "birth_date");
+    match db.dialect() {
+        Dialect::Sqlite => format!("birth_date({birth_date})"),
+        Dialect::Postgres => format!(
+            "COALESCE(substring({birth_date} from '^[0-9]{{4}}-[0-9]{{2}}-[0-9]{{2}}'),
+                      substring({birth_date} from '^[0-9]{{4}}-[0-9]{{2}}') || '-01',
+                      substring({birth_date} from '((19|20)[0-9][0-9])') || '-01-01')"
+        ),
+    }
+}
+
+fn imperial_sources_sql() -> String {
+    let sources: Vec<String> =
+        normalize::IMPERIAL_SOURCES.iter().map(|s| format!("'{}'", s.to_uppercase())).collect();
+    format!("UPPER(i.source) IN ({})", sources.join(", "))
+}
+
+// Altura em cm (normalize::height_cm); no Postgres sem os limites de plausibilidade
+fn height_expr(db: &dyn Backend) -> String {
+    let h = db.schema().real(INDIVIDUALS, "i", "height_cm");
+    match db.dialect() {
+        Dialect::Sqlite => format!("height_cm({h}, i.source)"),
+        Dialect::Postgres => format!(
+            "(CASE WHEN {h} <= 0 THEN NULL
+                   WHEN {h} < 3 THEN {h} * 100
+                   WHEN {h} >= 300 AND {h} < 900 AND MOD(CAST({h} AS NUMERIC), 100) < 12
+                        THEN FLOOR({h} / 100) * 30.48 + CAST(MOD(CAST({h} AS NUMERIC), 100) AS DOUBLE PRECISION) * 2.54
+                   WHEN {imperial} AND {h} < 100 THEN {h} * 2.54
+                   ELSE {h} END)",
+            imperial = imperial_sources_sql()
+        ),
+    }
+}
+
+fn weight_expr(db: &dyn Backend) -> String {
+    let w = db.schema().real(INDIVIDUALS, "i", "weight_kg");
+    match db.dialect() {
+        Dialect::Sqlite => format!("weight_kg({w}, i.source)"),
+        Dialect::Postgres => format!("(CASE WHEN {} THEN {w} * 0.45359237 ELSE {w} END)", imperial_sources_sql()),
+    }
+}
+
+fn term_cond(db: &dyn Backend, t: &Term, vals: &mut Vec<Value>) -> String {
+    let s = db.schema();
+    let like = format!("%{}%", t.value);
+    let Some(field) = t.field else {
+        return free_text_cond(db, t, vals);
+    };
+    match field {
+        Field::Name => {
+            vals.extend([like.clone().into(), like.into()]);
+            format!("(i.name LIKE ? OR {} LIKE ?)", s.text(INDIVIDUALS, "i", "aliases"))
+        }
+        Field::Source => {
+            vals.push(like.into());
+            "i.source LIKE ?".into()
+        }
+        Field::Category => {
+            vals.push(t.value.to_lowercase().into());
+            "i.category = ?".into()
+        }
+        Field::Nationality => {
+            vals.push(like.into());
+            format!("{} LIKE ?", s.text(INDIVIDUALS, "i", "nationalities"))
+        }
+        Field::Crime if s.has_crimes() => {
+            vals.push(like.into());
+            "EXISTS (SELECT 1 FROM crimes c WHERE c.individual_id = i.id AND c.crime LIKE ?)".into()
+        }
+        Field::Location if s.has_locations() => {
+            vals.extend([like.clone().into(), like.clone().into(), like.into()]);
+            let l = |c: &str| s.text(LOCATIONS, "l", c);
+            format!(
+                "EXISTS (SELECT 1 FROM locations l WHERE l.individual_id = i.id AND ({} LIKE ? OR {} LIKE ? OR {} LIKE ?))",
+                l("country"), l("state"), l("city")
+            )
+        }
+        Field::Crime | Field::Location => "1=0".into(),
+        Field::Born => {
+            vals.push(Value::Int(t.year().unwrap_or_default()));
+            format!("{} {} ?", birth_year_expr(db), t.op.sql())
+        }
+        Field::Has => match t.has() {
+            Some(Has::Images) => has_images_expr(db).into(),
+            Some(Has::Biometrics) => format!("COALESCE({}, 0) = 1", s.int(INDIVIDUALS, "i", "has_embedding")),
+            Some(Has::Crimes) if s.has_crimes() => "EXISTS (SELECT 1 FROM crimes c WHERE c.individual_id = i.id)".into(),
+            Some(Has::Locations) if s.has_locations() => {
+                "EXISTS (SELECT 1 FROM locations l WHERE l.individual_id = i.id)".into()
+            }
+            Some(Has::Reward) => format!("COALESCE({}, '') <> ''", s.text(INDIVIDUALS, "i", "reward")),
+            _ => "1=0".into(),
+        },
+    }
+}
+
+// Texto livre dentro de OR/NOT/parênteses: filtra pelo índice, sem ranquear
+fn free_text_cond(db: &dyn Backend, t: &Term, vals: &mut Vec<Value>) -> String {
+    if db.is_attached(INDEX_ALIAS) {
+        let expr = if t.phrase { search_index::phrase_expr(&t.value) } else { search_index::match_expr(&t.value) };
+        return match expr {
+            Some(expr) => {
+                vals.push(expr.into());
+                "i.id IN (SELECT id FROM individuals_fts WHERE individuals_fts MATCH ?)".into()
+            }
+            None => "1=1".into(),
+        };
+    }
+    let like = format!("%{}%", t.value);
+    vals.extend([like.clone().into(), like.into()]);
+    format!("(i.name LIKE ? OR {} LIKE ?)", db.schema().text(INDIVIDUALS, "i", "description"))
+}
+
+// ─── Ordenação ────────────────────────────────────────────────────────────────
+// Toda ordenação termina em `i.id` para ser total (o cursor precisa de um
+// desempate único). As expressões não têm NULL (COALESCE) para o keyset poder
+// comparar com `<`/`>`.
+
+#[derive(Clone, Copy)]
+enum KeyKind {
+    Text,
+    Int,
+    Real,
+}
+
+struct OrderKey {
+    sql:  String,
+    vals: Vec<Value>, // parâmetros da expressão (repetidos a cada uso)
+    kind: KeyKind,
+    desc: bool,
+}
+
+impl OrderKey {
+    fn new(sql: impl Into<String>, kind: KeyKind, desc: bool) -> Self {
+        OrderKey { sql: sql.into(), vals: vec![], kind, desc }
+    }
+
+    fn read(&self, r: &dyn Record, idx: usize) -> Result<Value> {
+        let value = match self.kind {
+            KeyKind::Text => r.opt_text(idx)?.map(Value::Text),
+            KeyKind::Int  => r.opt_int(idx)?.map(Value::Int),
+            KeyKind::Real => r.opt_real(idx)?.map(Value::Real),
+        };
+        Ok(value.unwrap_or(Value::Null))
+    }
+}
+
+fn reward_expr(db: &dyn Backend) -> String {
+    let reward = db.schema().text(INDIVIDUALS, "i", "reward");
+    match db.dialect() {
+        Dialect::Sqlite => format!("COALESCE(reward_amount({reward}), 0.0)"),
+        // Sem a função do SQLite: só os dígitos ("$5 million" vale 5)
+        Dialect::Postgres => format!(
+            "COALESCE(CAST(NULLIF(regexp_replace(COALESCE({reward}, ''), '[^0-9]', '', 'g'), '') AS DOUBLE PRECISION), 0.0)"
+        ),
+    }
+}
+
+fn order_keys(db: &dyn Backend, sort: SortKey, desc: bool, rank: Option<OrderKey>) -> Vec<OrderKey> {
+    let s = db.schema();
+    let text = |expr: String, desc| OrderKey::new(format!("COALESCE({expr}, '')"), KeyKind::Text, desc);
+    let ingested_at = s.timestamp(INDIVIDUALS, "i", "ingested_at");
+
+    let mut keys = match sort {
+        SortKey::Relevance => {
+            let mut keys: Vec<OrderKey> = rank.into_iter().collect();
+            keys.push(OrderKey::new(
+                format!("COALESCE({}, 0)", s.int(INDIVIDUALS, "i", "has_embedding")),
+                KeyKind::Int,
+                true,
+            ));
+            keys.push(text(ingested_at, true));
+            keys.push(OrderKey::new("i.name", KeyKind::Text, false));
+            keys
+        }
+        SortKey::Name       => vec![OrderKey::new("i.name", KeyKind::Text, desc)],
+        SortKey::IngestedAt => vec![text(ingested_at, desc)],
+        SortKey::Source     => vec![OrderKey::new("i.source", KeyKind::Text, desc)],
+        SortKey::Reward     => vec![OrderKey::new(reward_expr(db), KeyKind::Real, desc)],
+        SortKey::BirthDate  => vec![text(birth_date_expr(db), desc)],
+    };
+    keys.push(OrderKey::new("i.id", KeyKind::Text, false));
+    keys
+}
+
+// ─── Cursor ───────────────────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize)]
+struct Cursor {
+    sort:  SortKey,
+    desc:  bool,
+    after: Vec<Value>,
+}
+
+fn decode_cursor(raw: &str, sort: SortKey, desc: bool, keys: usize) -> Result<Vec<Value>> {
+    let invalid = |detail: &str| Error::InvalidInput("cursor".into(), detail.into());
+    let cursor: Cursor = serde_json::from_str(raw).map_err(|e| invalid(&e.to_string()))?;
+    if cursor.sort != sort || cursor.desc != desc || cursor.after.len() != keys {
+        return Err(invalid("cursor de outra ordenação; recomece da primeira página"));
+    }
+    Ok(cursor.after)
+}
+
+// (k1 > v1) OR (k1 = v1 AND k2 > v2) OR ... — `<` nas chaves descendentes
+fn keyset_cond(keys: &[OrderKey], after: &[Value], vals: &mut Vec<Value>) -> String {
+    let mut branches = Vec::with_capacity(keys.len());
+    for (n, key) in keys.iter().enumerate() {
+        let mut terms = Vec::with_capacity(n + 1);
+        for (prev, value) in keys[..n].iter().zip(after) {
+            terms.push(format!("{} = ?", prev.sql));
+            vals.extend(prev.vals.iter().cloned());
+            vals.push(value.clone());
+        }
+        terms.push(format!("{} {} ?", key.sql, if key.desc { "<" } else { ">" }));
+        vals.extend(key.vals.iter().cloned());
+        vals.push(after[n].clone());
+        branches.push(format!("({})", terms.join(" AND ")));
+    }
+    format!("({})", branches.join(" OR "))
+}
+
+// ─── Consulta ─────────────────────────────────────────────────────────────────
+
+pub fn search_individuals(db: &dyn Backend, f: &SearchFilter) -> Result<SearchPage> {
+    let s = db.schema();
+    let limit = f.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let sort = f.sort.unwrap_or_default();
+    let desc = f.descending.unwrap_or_else(|| sort.default_descending());
+
+    let (text, filters) = prepare(db, f)?;
+    let (where_clause, cond_vals) = filters.clause(None);
+
+    let total = db.count(
+        &format!("SELECT COUNT(*) FROM individuals i {} WHERE {where_clause}", filters.join),
+        &cond_vals,
+    )?;
+
+    // Parâmetros na ordem do SQL: colunas do SELECT, chaves, WHERE, cursor, LIMIT/OFFSET
+    let keys = order_keys(db, sort, desc, text.rank);
+    let mut vals: Vec<Value> = text.column_vals;
+    for key in &keys {
+        vals.extend(key.vals.iter().cloned());
+    }
+    vals.extend(cond_vals);
+
+    let mut conds = vec![where_clause];
+    let mut offset = None;
+    let cursor = f.cursor.as_deref().filter(|c| !c.is_empty());
+    match cursor {
+        Some(raw) => {
+            let after = decode_cursor(raw, sort, desc, keys.len())?;
+            conds.push(keyset_cond(&keys, &after, &mut vals));
+        }
+        None => offset = f.page.filter(|p| *p > 0).map(|p| p as i64 * limit as i64),
+    }
+
+    // ORDER BY por posição: as chaves já estão no SELECT (sem repetir parâmetros)
+    let order_by: Vec<String> = keys
+        .iter()
+        .enumerate()
+        .map(|(n, key)| format!("{} {}", KEY_COLUMN_OFFSET + n + 1, if key.desc { "DESC" } else { "ASC" }))
+        .collect();
+    let key_columns: Vec<&str> = keys.iter().map(|k| k.sql.as_str()).collect();
+
+    // Uma linha a mais indica que existe próxima página
+    let mut sql = format!(
+        "SELECT {columns}, {text_columns}, {key_columns}
+         FROM individuals i {join}
+         WHERE {where_clause}
+         ORDER BY {order_by} LIMIT ?",
+        columns      = individual_summary_columns(s),
+        text_columns = text.columns,
+        key_columns  = key_columns.join(", "),
+        join         = filters.join,
+        where_clause = conds.join(" AND "),
+        order_by     = order_by.join(", "),
+    );
+    vals.push(Value::Int(limit as i64 + 1));
+    if let Some(off) = offset {
+        sql.push_str(" OFFSET ?");
+        vals.push(Value::Int(off));
+    }
+
+    let mut rows = db.query(&sql, &vals, |r| {
+        let key_values = keys
+            .iter()
+            .enumerate()
+            .map(|(n, key)| key.read(r, KEY_COLUMN_OFFSET + n))
+            .collect::<Result<Vec<Value>>>()?;
+        Ok((map_individual(r)?, key_values))
+    })?;
+
+    let mut next_cursor = None;
+    if rows.len() > limit as usize {
+        rows.truncate(limit as usize);
+        if let Some((_, after)) = rows.last() {
+            let cursor = Cursor { sort, desc, after: after.clone() };
+            next_cursor = Some(serde_json::to_string(&cursor).map_err(|e| Error::InvalidInput("cursor".into(), e.to_string()))?);
+        }
+    }
+
+    let facets = match (f.facets.unwrap_or(false), cursor) {
+        (true, None) => Some(all_facets(db, &filters)?),
+        _ => None,
+    };
+
+    let mut items: Vec<Individual> = rows.into_iter().map(|(item, _)| item).collect();
+    let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+    let mut linked = linkage::linked_records(db, &ids)?;
+    for item in &mut items {
+        item.linked = linked.remove(&item.id).unwrap_or_default();
+    }
+
+    Ok(SearchPage {
+        items,
+        total,
+        next_cursor,
+        limit,
+        facets,
+    })
+}
+
+// ─── Facetas ──────────────────────────────────────────────────────────────────
+
+// Contagens de uma faceta para o filtro (ex.: `by_source` do get_stats)
+pub fn facet(db: &dyn Backend, f: &SearchFilter, facet: Facet) -> Result<Vec<FacetCount>> {
+    let (_, filters) = prepare(db, f)?;
+    facet_counts(db, &filters, facet)
+}
+
+fn all_facets(db: &dyn Backend, filters: &Filters) -> Result<Facets> {
+    Ok(Facets {
+        category:         facet_counts(db, filters, Facet::Category)?,
+        source:           facet_counts(db, filters, Facet::Source)?,
+        nationality:      facet_counts(db, filters, Facet::Nationality)?,
+        crime:            facet_counts(db, filters, Facet::Crime)?,
+        location_country: facet_counts(db, filters, Facet::LocationCountry)?,
+        has_images:       facet_counts(db, filters, Facet::HasImages)?,
+    })
+}
+
+fn facet_counts(db: &dyn Backend, filters: &Filters, facet: Facet) -> Result<Vec<FacetCount>> {
+    let s = db.schema();
+    let (where_clause, mut vals) = filters.clause(Some(facet));
+    let from = format!("individuals i {}", filters.join);
+
+    let (value, from) = match facet {
+        Facet::Category => ("i.category".to_string(), from),
+        Facet::Source   => ("i.source".to_string(), from),
+        Facet::Crime => {
+            if !s.has_crimes() {
+                return Ok(vec![]);
+            }
+            ("c.crime".to_string(), format!("{from} JOIN crimes c ON c.individual_id = i.id"))
+        }
+        Facet::LocationCountry => {
+            if !s.has_locations() {
+                return Ok(vec![]);
+            }
+            // Parâmetros do JOIN vêm antes dos do WHERE
+            let mut join = format!("{from} JOIN locations l ON l.individual_id = i.id");
+            for (n, (column, value)) in filters.location_scope.iter().enumerate() {
+                join.push_str(&format!(" AND LOWER({}) = LOWER(?)", s.text(LOCATIONS, "l", column)));
+                vals.insert(n, value.as_str().into());
+            }
+            (s.text(LOCATIONS, "l", "country"), join)
+        }
+        Facet::HasImages => (format!("CASE WHEN {} THEN 'true' ELSE 'false' END", has_images_expr(db)), from),
+        // Nacionalidades são uma lista JSON por registro: contadas no Rust (portável)
+        Facet::Nationality => return nationality_counts(db, &from, &where_clause, &vals),
+    };
+
+    let sql = format!(
+        "SELECT {value}, COUNT(DISTINCT i.id) FROM {from}
+         WHERE {where_clause} AND {value} IS NOT NULL
+         GROUP BY 1 ORDER BY 2 DESC, 1 LIMIT ?"
+    );
+    vals.push(Value::Int(FACET_LIMIT));
+    db.query(&sql, &vals, |r| Ok(FacetCount { value: r.text(0)?, count: r.int(1)? }))
+}
+
+fn nationality_counts(db: &dyn Backend, from: &str, where_clause: &str, vals: &[Value]) -> Result<Vec<FacetCount>> {
+    let nationalities = db.schema().text(INDIVIDUALS, "i", "nationalities");
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    db.for_each(
+        &format!("SELECT {nationalities} FROM {from} WHERE {where_clause} AND {nationalities} IS NOT NULL"),
+        vals,
+        &mut |r| {
+            let mut values = normalize::json_list(&r.text(0)?);
+            values.sort();
+            values.dedup();
+            for value in values {
+                *counts.entry(value).or_default() += 1;
+            }
+            Ok(())
+        },
+    )?;
+
+    let mut out: Vec<FacetCount> = counts.into_iter().map(|(value, count)| FacetCount { value, count }).collect();
+    out.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    out.truncate(FACET_LIMIT as usize);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, Fixture};
+
+    // Quatro registros: duas fontes, crimes, locais, uma imagem e uma biometria
+    const SEED: &str = "
+        INSERT INTO individuals (id, name, category, source, birth_date, nationalities, description, reward, has_embedding, ingested_at) VALUES
+            ('a', 'José Silva',    'wanted',  'fbi',      '1975-03-04', '[\"Brazil\"]',             'fraude bancária',  '$5,000',    1, '2026-01-04 10:00:00'),
+            ('b', 'Ivan Petrov',   'wanted',  'interpol', '1982',       '[\"Russia\", \"Brazil\"]', 'lavagem',          NULL,        0, '2026-01-03 10:00:00'),
+            ('c', 'Maria Souza',   'missing', 'fbi',      NULL,         '[\"Brazil\"]',             'vista pela última vez em Recife', '$20,000', 0, '2026-01-02 10:00:00'),
+            ('d', 'John Smith',    'wanted',  'interpol', '1990-07-01', '[\"USA\"]',                 NULL,               NULL,        0, '2026-01-01 10:00:00');
+        INSERT INTO crimes (individual_id, crime) VALUES ('a', 'fraud'), ('b', 'money laundering'), ('d', 'fraud');
+        INSERT INTO locations (individual_id, type, country, city) VALUES
+            ('a', 'last_seen', 'Brazil', 'São Paulo'), ('c', 'last_seen', 'Brazil', 'Recife'), ('d', 'residence', 'USA', 'Boston');
+        INSERT INTO individual_images (individual_id, img_path, is_primary) VALUES ('a', 'images/a.jpg', 1);";
+
+    fn ids(page: &SearchPage) -> Vec<&str> {
+        page.items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    fn search(f: &Fixture, filter: SearchFilter) -> SearchPage {
+        search_individuals(&f.db, &filter).unwrap()
+    }
+
+    fn by_name() -> SearchFilter {
+        SearchFilter { sort: Some(SortKey::Name), ..Default::default() }
+    }
+
+    fn counts(facet: &[FacetCount]) -> Vec<(&str, i64)> {
+        facet.iter().map(|c| (c.value.as_str(), c.count)).collect()
+    }
+
+    #[test]
+    fn filters_combine_and_facets_ignore_their_own() {
+        let f = Fixture::new("search_filters", SEED);
+        let page = search(&f, SearchFilter { source: Some("fbi".into()), facets: Some(true), ..by_name() });
+        assert_eq!(ids(&page), ["a", "c"]);
+        assert_eq!(page.total, 2);
+
+        // A faceta da fonte continua mostrando a alternativa; as outras seguem o filtro
+        let facets = page.facets.unwrap();
+        assert_eq!(counts(&facets.source), [("fbi", 2), ("interpol", 2)]);
+        assert_eq!(counts(&facets.category), [("missing", 1), ("wanted", 1)]);
+        assert_eq!(counts(&facets.nationality), [("Brazil", 2)]);
+        assert_eq!(counts(&facets.crime), [("fraud", 1)]);
+        assert_eq!(counts(&facets.has_images), [("false", 1), ("true", 1)]);
+
+        let page = search(&f, SearchFilter { crime: Some("fraud".into()), has_images: Some(false), ..by_name() });
+        assert_eq!(ids(&page), ["d"]);
+        let page = search(&f, SearchFilter { location_city: Some("recife".into()), ..by_name() });
+        assert_eq!(ids(&page), ["c"]);
+        let page = search(&f, SearchFilter { has_embedding: Some(true), ..by_name() });
+        assert_eq!(ids(&page), ["a"]);
+    }
+
+    #[test]
+    fn location_facet_counts_only_the_chosen_type() {
+        let f = Fixture::new("search_location_facet", SEED);
+        let (_, filters) = prepare(&f.db, &SearchFilter { location_type: Some("last_seen".into()), ..Default::default() }).unwrap();
+        let countries = facet_counts(&f.db, &filters, Facet::LocationCountry).unwrap();
+        assert_eq!(counts(&countries), [("Brazil", 2)]);
+    }
+
+    #[test]
+    fn descriptor_ranges_use_normalized_values() {
+        let f = Fixture::new("search_descriptors", SEED);
+        f.conn
+            .execute_batch(
+                "UPDATE individuals SET height_cm = 511, eye_color = 'castanhos' WHERE id = 'a';
+                 UPDATE individuals SET height_cm = 180, eye_color = 'blue' WHERE id = 'b';",
+            )
+            .unwrap();
+        // 5'11\" = 180,3 cm
+        let page = search(&f, SearchFilter { height_min: Some(178.0), height_max: Some(182.0), ..by_name() });
+        assert_eq!(ids(&page), ["b", "a"]);
+        let page = search(&f, SearchFilter { eye_color: Some("brown".into()), ..by_name() });
+        assert_eq!(ids(&page), ["a"]);
+        assert!(matches!(
+            search_individuals(&f.db, &SearchFilter { eye_color: Some("xadrez".into()), ..Default::default() }),
+            Err(Error::InvalidInput(..))
+        ));
+
+        let today = normalize::today();
+        let age = |born: &str| normalize::birth_date(born).unwrap().age_on(today).unwrap() as u32;
+        let page = search(&f, SearchFilter { age_min: Some(age("1982")), age_max: Some(age("1982")), ..by_name() });
+        assert_eq!(ids(&page), ["b"]);
+    }
+
+    #[test]
+    fn cursor_pages_cover_every_row_once() {
+        let f = Fixture::new("search_cursor", SEED);
+        for sort in [SortKey::Relevance, SortKey::Name, SortKey::IngestedAt, SortKey::Reward, SortKey::BirthDate] {
+            let all = ids(&search(&f, SearchFilter { sort: Some(sort), ..Default::default() }))
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            let mut seen = vec![];
+            let mut cursor = None;
+            loop {
+                let page = search(&f, SearchFilter { sort: Some(sort), limit: Some(3), cursor: cursor.take(), ..Default::default() });
+                seen.extend(ids(&page).into_iter().map(str::to_string));
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            assert_eq!(seen, all, "{sort:?}");
+        }
+
+        let first = search(&f, SearchFilter { limit: Some(2), ..by_name() });
+        assert_eq!(ids(&first), ["b", "d"]);
+        let offset = search(&f, SearchFilter { limit: Some(2), page: Some(1), ..by_name() });
+        assert_eq!(ids(&offset), ["a", "c"]);
+        let other_sort = SearchFilter { sort: Some(SortKey::Source), cursor: first.next_cursor, ..Default::default() };
+        assert!(matches!(search_individuals(&f.db, &other_sort), Err(Error::InvalidInput(..))));
+    }
+
+    #[test]
+    fn descending_and_reward_orders() {
+        let f = Fixture::new("search_order", SEED);
+        assert_eq!(ids(&search(&f, SearchFilter { sort: Some(SortKey::Reward), ..Default::default() })), ["c", "a", "b", "d"]);
+        assert_eq!(ids(&search(&f, SearchFilter { descending: Some(true), ..by_name() })), ["c", "a", "d", "b"]);
+        assert_eq!(ids(&search(&f, SearchFilter { sort: Some(SortKey::IngestedAt), ..Default::default() })), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn query_syntax_compiles_to_filters() {
+        let f = Fixture::new("search_query", SEED);
+        let query = |q: &str| ids(&search(&f, SearchFilter { query: Some(q.into()), ..by_name() })).join(",");
+        assert_eq!(query("source:interpol born:<1985"), "b");
+        assert_eq!(query("crime:fraud -source:fbi"), "d");
+        assert_eq!(query("(crime:laundering OR has:images) nationality:brazil"), "b,a");
+        assert_eq!(query("has:reward category:missing"), "c");
+        assert_eq!(query("location:boston OR location:recife"), "d,c");
+        // Sem crimes, o NOT conta como verdadeiro (NULL não exclui)
+        assert_eq!(query("-crime:fraud"), "b,c");
+        assert!(matches!(
+            search_individuals(&f.db, &SearchFilter { query: Some("source:".into()), ..Default::default() }),
+            Err(Error::QuerySyntax(_))
+        ));
+    }
+
+    #[test]
+    fn text_without_index_falls_back_to_like() {
+        let f = Fixture::new("search_like", SEED);
+        let page = search(&f, SearchFilter { name: Some("recife".into()), ..by_name() });
+        assert_eq!(ids(&page), ["c"]);
+        assert!(page.items[0].snippet.is_none());
+    }
+
+    #[test]
+    fn indexed_text_ignores_accents_and_ranks() {
+        let f = Fixture::new("search_fts", SEED);
+        search_index::refresh(&f.db, &temp_dir("search_fts").join(search_index::INDEX_FILE), false).unwrap();
+
+        let page = search(&f, SearchFilter { name: Some("jose".into()), ..Default::default() });
+        assert_eq!(ids(&page), ["a"]);
+        let snippet = page.items[0].snippet.clone().unwrap();
+        assert!(snippet.contains(&format!("{}José{}", search_index::HIGHLIGHT_START, search_index::HIGHLIGHT_END)));
+
+        // Prefixo, crimes no texto indexado e frase exata pela consulta
+        assert_eq!(ids(&search(&f, SearchFilter { name: Some("laund".into()), ..Default::default() })), ["b"]);
+        let phrase = |q: &str| ids(&search(&f, SearchFilter { query: Some(q.into()), ..by_name() })).join(",");
+        assert_eq!(phrase("\"maria souza\""), "c");
+        assert_eq!(phrase("\"souza maria\""), "");
+        assert_eq!(phrase("ivan OR john"), "b,d");
+    }
+
+    #[test]
+    fn fuzzy_search_scores_spelling_variants() {
+        let f = Fixture::new("search_fuzzy", SEED);
+        search_index::refresh(&f.db, &temp_dir("search_fuzzy").join(search_index::INDEX_FILE), false).unwrap();
+        let page = search(&f, SearchFilter { name: Some("Jose Sylva".into()), fuzzy: Some(true), ..Default::default() });
+        assert_eq!(ids(&page), ["a"]);
+        assert!(page.items[0].match_score.unwrap() >= FUZZY_THRESHOLD);
+        let exact = search(&f, SearchFilter { name: Some("Jose Sylva".into()), ..Default::default() });
+        assert!(exact.items.is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+// Índice de busca textual (FTS5) sobre individuals, num sidecar do app
+// (search_index.db), sem tocar no intelligence.db nem na ingestão Python.
+// É reconstruído quando a impressão digital do banco (contagens + último
+// ingested_at) muda, e anexado às conexões de leitura do SQLite como `search_index`.
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::backend::{Backend, Dialect};
+use crate::error::{Error, Result};
+use crate::normalize;
+use crate::schema::INDIVIDUALS;
+use crate::sidecar;
+
+pub const INDEX_FILE:  &str = "search_index.db";
+pub const INDEX_ALIAS: &str = "search_index";
+
+// Marcadores do trecho destacado (o frontend troca por <mark>)
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_END:   char = '\u{3}';
+
+const MIGRATIONS: &[&str] = &[
+    "CREATE VIRTUAL TABLE individuals_fts USING fts5(
+         id UNINDEXED, name, aliases, description, crimes, occupation,
+         tokenize = 'unicode61 remove_diacritics 2'
+     );
+     CREATE TABLE index_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);",
+    // 2 — nomes transliterados (normalize::fold) no FTS e chaves para similaridade
+    "DROP TABLE individuals_fts;
+     CREATE VIRTUAL TABLE individuals_fts USING fts5(
+         id UNINDEXED, name, aliases, description, crimes, occupation, name_folded,
+         tokenize = 'unicode61 remove_diacritics 2'
+     );
+     CREATE TABLE name_keys (id TEXT NOT NULL, folded TEXT NOT NULL);
+     CREATE INDEX name_keys_id ON name_keys (id);
+     DELETE FROM index_meta;",
+];
+
+// Função de `rank` com os pesos do bm25 por coluna do FTS
+// (id, name, aliases, description, crimes, occupation, name_folded)
+pub const RANK_FUNCTION: &str = "bm25(0.0, 10.0, 8.0, 1.0, 3.0, 2.0, 8.0)";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct IndexStatus {
+    pub path:      PathBuf,
+    pub documents: i64,
+    pub built_at:  Option<String>,
+    pub rebuilt:   bool,
+    pub attached:  bool,
+}
+
+fn fingerprint(db: &dyn Backend) -> Result<String> {
+    let s = db.schema();
+    let (count, last) = db
+        .query_opt(
+            &format!("SELECT COUNT(*), MAX({}) FROM individuals i", s.timestamp(INDIVIDUALS, "i", "ingested_at")),
+            &[],
+            |r| Ok((r.int(0)?, r.opt_text(1)?)),
+        )?
+        .unwrap_or((0, None));
+    let crimes = if s.has_crimes() { db.count("SELECT COUNT(*) FROM crimes", &[])? } else { 0 };
+    Ok(format!("{}|{}|{}|{}", db.describe(), count, last.unwrap_or_default(), crimes))
+}
+
+fn meta(conn: &Connection, key: &str) -> rusqlite::Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT value FROM index_meta WHERE key = ?1")?;
+    let mut rows = stmt.query([key])?;
+    match rows.next()? {
+        Some(row) => row.get(0),
+        None => Ok(None),
+    }
+}
+
+fn rebuild(conn: &mut Connection, db: &dyn Backend, fingerprint: &str) -> Result<()> {
+    let s = db.schema();
+    let mut crimes: HashMap<String, Vec<String>> = HashMap::new();
+    if s.has_crimes() {
+        db.for_each("SELECT individual_id, crime FROM crimes", &[], &mut |r| {
+            if let Some(id) = r.opt_text(0)? {
+                crimes.entry(id).or_default().push(r.text(1)?);
+            }
+            Ok(())
+        })?;
+    }
+
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM individuals_fts", [])?;
+    tx.execute("DELETE FROM name_keys", [])?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO individuals_fts (id, name, aliases, description, crimes, occupation, name_folded)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        let mut insert_key = tx.prepare("INSERT INTO name_keys (id, folded) VALUES (?1, ?2)")?;
+        let t = |c: &str| s.text(INDIVIDUALS, "i", c);
+        let sql = format!(
+            "SELECT i.id, i.name, {}, {}, {} FROM individuals i",
+            t("aliases"), t("description"), t("occupation")
+        );
+        db.for_each(&sql, &[], &mut |r| {
+            let id = r.text(0)?;
+            let name = r.text(1)?;
+            let aliases = r.opt_text(2)?;
+            let keys = normalize::name_keys(&name, aliases.as_deref());
+            let joined = crimes.get(&id).map(|c| c.join("; "));
+            insert.execute(params![id, name, aliases, r.opt_text(3)?, joined, r.opt_text(4)?, keys.join(" ")])?;
+            for key in &keys {
+                insert_key.execute(params![id, key])?;
+            }
+            Ok(())
+        })?;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO index_meta (key, value) VALUES ('fingerprint', ?1), ('built_at', datetime('now'))",
+        [fingerprint],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+// Reconstrói o índice se o banco mudou (ou se `force`) e o anexa ao backend.
+pub fn refresh(db: &dyn Backend, path: &Path, force: bool) -> Result<IndexStatus> {
+    if db.dialect() != Dialect::Sqlite {
+        return Err(Error::Unsupported("busca FTS5 (somente SQLite)".into()));
+    }
+    let mut conn = sidecar::open(path, MIGRATIONS)?;
+    let current = fingerprint(db)?;
+    let rebuilt = force || meta(&conn, "fingerprint")?.as_deref() != Some(current.as_str());
+    if rebuilt {
+        let started = Instant::now();
+        rebuild(&mut conn, db, &current)?;
+        println!("[INDEX] Índice de busca reconstruído em {:?}", started.elapsed());
+    }
+    let documents = conn.query_row("SELECT COUNT(*) FROM individuals_fts", [], |r| r.get(0))?;
+    let built_at = meta(&conn, "built_at")?;
+    drop(conn);
+
+    if rebuilt || !db.is_attached(INDEX_ALIAS) {
+        db.attach(INDEX_ALIAS, path)?;
+    }
+    Ok(IndexStatus { path: path.to_path_buf(), documents, built_at, rebuilt, attached: true })
+}
+
+fn terms(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .map(|w| format!("\"{}\"*", w.replace('"', "")))
+        .collect();
+    if terms.is_empty() { None } else { Some(terms.join(" ")) }
+}
+
+// Texto livre -> expressão MATCH segura: cada palavra vira um termo entre aspas
+// com prefixo (`"jose"* "silva"*`, AND implícito), mais a forma transliterada
+// quando difere (`Иван` também procura `"ivan"*`). Sem palavras úteis: None.
+pub fn match_expr(input: &str) -> Option<String> {
+    let raw = terms(input);
+    let folded = terms(&normalize::fold(input));
+    match (raw, folded) {
+        (Some(r), Some(f)) if r.to_lowercase() != f => Some(format!("({r}) OR ({f})")),
+        (raw, folded) => raw.or(folded),
+    }
+}
+
+// Frase exata (`"jose silva"`), também na forma transliterada quando difere
+pub fn phrase_expr(input: &str) -> Option<String> {
+    let phrase = |s: &str| {
+        let words: Vec<&str> = s.split_whitespace().filter(|w| w.chars().any(char::is_alphanumeric)).collect();
+        if words.is_empty() { None } else { Some(format!("\"{}\"", words.join(" ").replace('"', ""))) }
+    };
+    match (phrase(input), phrase(&normalize::fold(input))) {
+        (Some(r), Some(f)) if r.to_lowercase() != f => Some(format!("{r} OR {f}")),
+        (raw, folded) => raw.or(folded),
+    }
+}
+
+// Coluna do SELECT com o trecho destacado do melhor campo
+pub fn snippet_expr() -> String {
+    format!("snippet(individuals_fts, -1, '{HIGHLIGHT_START}', '{HIGHLIGHT_END}', '…', 12)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, Fixture};
+
+    #[test]
+    fn match_expr_quotes_words_and_adds_the_folded_form() {
+        assert_eq!(match_expr("jose silva").as_deref(), Some("\"jose\"* \"silva\"*"));
+        assert_eq!(match_expr("José").as_deref(), Some("(\"José\"*) OR (\"jose\"*)"));
+        // Aspas e operadores do FTS5 não escapam do termo
+        assert_eq!(match_expr("a\"b OR -").as_deref(), Some("(\"ab\"* \"OR\"*) OR (\"a\"* \"b\"* \"or\"*)"));
+        assert_eq!(match_expr(" -- ! "), None);
+    }
+
+    #[test]
+    fn phrase_expr_keeps_word_order() {
+        assert_eq!(phrase_expr("jose  silva").as_deref(), Some("\"jose silva\""));
+        assert_eq!(phrase_expr("José Silva").as_deref(), Some("\"José Silva\" OR \"jose silva\""));
+        assert_eq!(phrase_expr("?"), None);
+    }
+
+    #[test]
+    fn refresh_rebuilds_only_when_the_database_changes() {
+        let f = Fixture::new(
+            "search_index_refresh",
+            "INSERT INTO individuals (id, name, aliases, category, source, ingested_at) VALUES
+                 ('a', 'José Silva', '[\"Zé\"]', 'wanted', 'fbi', '2026-01-01 10:00:00');
+             INSERT INTO crimes (individual_id, crime) VALUES ('a', 'fraud');",
+        );
+        let path = temp_dir("search_index_refresh").join(INDEX_FILE);
+        let first = refresh(&f.db, &path, false).unwrap();
+        assert!(first.rebuilt && first.attached);
+        assert_eq!(first.documents, 1);
+        assert!(f.db.is_attached(INDEX_ALIAS));
+        assert!(!refresh(&f.db, &path, false).unwrap().rebuilt);
+        assert!(refresh(&f.db, &path, true).unwrap().rebuilt);
+
+        // Crime novo muda a impressão digital e entra no texto indexado
+        f.conn.execute("INSERT INTO crimes (individual_id, crime) VALUES ('a', 'smuggling')", []).unwrap();
+        assert!(refresh(&f.db, &path, false).unwrap().rebuilt);
+        let conn = Connection::open(&path).unwrap();
+        let hit: String = conn
+            .query_row("SELECT id FROM individuals_fts WHERE individuals_fts MATCH ?1", [match_expr("smuggl")], |r| r.get(0))
+            .unwrap();
+        assert_eq!(hit, "a");
+
+        // Apelidos viram chaves de similaridade
+        let mut stmt = conn.prepare("SELECT folded FROM name_keys ORDER BY folded").unwrap();
+        let keys: Vec<String> = stmt.query_map([], |r| r.get(0)).unwrap().collect::<rusqlite::Result<_>>().unwrap();
+        assert_eq!(keys, ["jose silva", "ze"]);
+    }
+}
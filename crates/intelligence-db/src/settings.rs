@@ -0,0 +1,288 @@
+// Configuração persistente dos apps. O settings.json fica no diretório de config de
+// cada app (Tauri) e pode ser sobrescrito por variáveis de ambiente — as mesmas
+// usadas pela ingestão Python. Pasta de dados, banco e bloqueio por inatividade são
+// comuns ao catálogo e ao dashboard; os campos só de um app ficam em `Settings::app`
+// (no mesmo nível do JSON) e os comandos comuns saem de `settings_commands!`.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::accounts::{ACCOUNTS_FILE, DEFAULT_IDLE_LOCK_MINUTES};
+use crate::app_error::{AppError, AppResult};
+use crate::audit_log::AUDIT_LOG_FILE;
+use crate::backend::{BackendConfig, BackendSlot};
+use crate::config::DatabaseSettings;
+use crate::layout::{check_data_root, discover_data_root, CAMS_FILE_CANDIDATES};
+use crate::operator::OperatorStore;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+// Variáveis de ambiente (têm prioridade sobre o settings.json)
+pub const ENV_DATA_ROOT:   &str = "OSS_DATA_ROOT";
+pub const ENV_DB_FILE:     &str = "DB_FILE";
+pub const ENV_CAMS_FILE:   &str = "OSS_CAMS_FILE";
+pub const ENV_AUDIT_DB:    &str = "OSS_AUDIT_DB";
+pub const ENV_ACCOUNTS_DB: &str = "OSS_ACCOUNTS_DB";
+
+// Campos do settings.json só de um app
+pub trait AppSettings: Serialize + DeserializeOwned + Clone + Default + Send + Sync {
+    // Variáveis de ambiente próprias, listadas em `SettingsView::env_overrides`
+    const ENV_VARS: &'static [&'static str];
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, bound = "A: AppSettings")]
+pub struct Settings<A> {
+    pub data_root:         Option<PathBuf>,
+    pub database:          DatabaseSettings,
+    // Minutos sem atividade do operador até trancar a sessão
+    pub idle_lock_minutes: Option<u32>,
+    #[serde(flatten)]
+    pub app:               A,
+}
+
+// Caminhos efetivos depois de aplicar settings + env
+#[derive(Serialize, Clone)]
+pub struct DataPaths {
+    pub db_file:   PathBuf,
+    pub cams_file: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(bound = "A: AppSettings")]
+pub struct SettingsView<A> {
+    pub config_file:   PathBuf,
+    pub settings:      Settings<A>,
+    pub paths:         Option<DataPaths>,
+    pub configured:    bool,
+    pub env_overrides: Vec<String>,
+}
+
+pub struct SettingsStore<A> {
+    file:     PathBuf,
+    data_dir: PathBuf,
+    settings: RwLock<Settings<A>>,
+}
+
+pub fn env_path(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+impl<A: AppSettings> SettingsStore<A> {
+    // `data_dir`: sidecars do app (índice de busca, integridade etc.), nunca dentro
+    // da pasta de dados
+    pub fn load(config_dir: &Path, data_dir: PathBuf) -> Self {
+        let file = config_dir.join(SETTINGS_FILE);
+        let settings = match std::fs::read_to_string(&file) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+                println!("[SETTINGS] {:?} inválido ({}), usando padrão", file, e);
+                Settings::default()
+            }),
+            Err(_) => Settings::default(),
+        };
+        SettingsStore { file, data_dir, settings: RwLock::new(settings) }
+    }
+
+    pub fn get(&self) -> Settings<A> {
+        self.settings.read().unwrap().clone()
+    }
+
+    pub fn save(&self, settings: Settings<A>) -> AppResult<()> {
+        if let Some(dir) = self.file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| AppError::io(dir, e))?;
+        }
+        let raw = serde_json::to_string_pretty(&settings).map_err(|e| AppError::Settings(e.to_string()))?;
+        std::fs::write(&self.file, raw).map_err(|e| AppError::io(&self.file, e))?;
+        *self.settings.write().unwrap() = settings;
+        Ok(())
+    }
+
+    pub fn update(&self, change: impl FnOnce(&mut Settings<A>)) -> AppResult<()> {
+        let mut settings = self.get();
+        change(&mut settings);
+        self.save(settings)
+    }
+
+    pub fn app_data_file(&self, name: &str) -> PathBuf {
+        self.data_dir.join(name)
+    }
+
+    pub fn data_root(&self) -> Option<PathBuf> {
+        env_path(ENV_DATA_ROOT)
+            .or_else(|| self.get().data_root)
+            .or_else(discover_data_root)
+    }
+
+    pub fn paths(&self) -> Option<DataPaths> {
+        let check = self.data_root().as_deref().map(check_data_root);
+        let db_file = env_path(ENV_DB_FILE).or_else(|| check.as_ref()?.db_file.clone())?;
+        let cams_file = env_path(ENV_CAMS_FILE)
+            .or_else(|| check.as_ref()?.cams_file.clone())
+            .unwrap_or_else(|| PathBuf::from(CAMS_FILE_CANDIDATES[0]));
+        Some(DataPaths { db_file, cams_file })
+    }
+
+    pub fn require_paths(&self) -> AppResult<DataPaths> {
+        self.paths().ok_or(AppError::DataRootNotConfigured)
+    }
+
+    pub fn backend_config(&self) -> AppResult<BackendConfig> {
+        let database = self.get().database.with_env();
+        database
+            .backend_config(self.paths().map(|p| p.db_file))
+            .ok_or(AppError::DataRootNotConfigured)
+    }
+
+    pub fn view(&self) -> SettingsView<A> {
+        let paths = self.paths();
+        let env_overrides = [ENV_DATA_ROOT, ENV_DB_FILE, ENV_CAMS_FILE, ENV_AUDIT_DB, ENV_ACCOUNTS_DB]
+            .iter()
+            .chain(A::ENV_VARS)
+            .filter(|k| env_path(k).is_some())
+            .map(|k| k.to_string())
+            .chain(DatabaseSettings::env_overrides())
+            .collect();
+        SettingsView {
+            config_file: self.file.clone(),
+            settings: self.get(),
+            configured: paths.as_ref().is_some_and(|p| p.db_file.is_file()),
+            paths,
+            env_overrides,
+        }
+    }
+
+    pub fn apply_data_root(&self, slot: &BackendSlot, root: PathBuf) -> AppResult<SettingsView<A>> {
+        let check = check_data_root(&root);
+        if !check.valid {
+            return Err(AppError::InvalidDataRoot(root));
+        }
+        self.update(|s| s.data_root = Some(root))?;
+        slot.reset();
+        Ok(self.view())
+    }
+
+    // Campos comuns de `update_settings`; cada app grava os seus antes de chamar
+    pub fn update_common(
+        &self,
+        slot:              &BackendSlot,
+        data_root:         Option<String>,
+        database:          Option<DatabaseSettings>,
+        idle_lock_minutes: Option<u32>,
+    ) -> AppResult<SettingsView<A>> {
+        if let Some(minutes) = idle_lock_minutes {
+            self.update(|s| s.idle_lock_minutes = (minutes > 0).then_some(minutes))?;
+        }
+        if let Some(database) = database {
+            self.update(|s| s.database = database)?;
+            slot.reset();
+        }
+        match data_root {
+            Some(root) => self.apply_data_root(slot, PathBuf::from(root)),
+            None => Ok(self.view()),
+        }
+    }
+}
+
+impl<A: AppSettings> OperatorStore for SettingsStore<A> {
+    fn accounts_file(&self) -> PathBuf {
+        env_path(ENV_ACCOUNTS_DB).unwrap_or_else(|| self.app_data_file(ACCOUNTS_FILE))
+    }
+
+    fn audit_file(&self) -> PathBuf {
+        env_path(ENV_AUDIT_DB).unwrap_or_else(|| self.app_data_file(AUDIT_LOG_FILE))
+    }
+
+    fn idle_lock(&self) -> Duration {
+        let minutes = self.get().idle_lock_minutes.unwrap_or(DEFAULT_IDLE_LOCK_MINUTES);
+        Duration::from_secs(u64::from(minutes) * 60)
+    }
+}
+
+// Comandos Tauri de configuração iguais nos dois apps (`get_settings`,
+// `validate_data_root`, `pick_data_root`), gerados no módulo settings de cada app
+// para o seu tipo de campos próprios. O app precisa depender de tauri e
+// tauri-plugin-dialog; `update_settings` fica no app (argumentos próprios).
+#[macro_export]
+macro_rules! settings_commands {
+    ($app:ty) => {
+        #[tauri::command]
+        pub fn get_settings(
+            store:    ::tauri::State<'_, $crate::settings::SettingsStore<$app>>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::settings::SettingsView<$app>> {
+            $crate::operator::OperatorStore::require(&*store, &sessions, $crate::accounts::Role::Viewer)?;
+            Ok(store.view())
+        }
+
+        #[tauri::command]
+        pub fn validate_data_root(
+            path:     String,
+            store:    ::tauri::State<'_, $crate::settings::SettingsStore<$app>>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<$crate::layout::DataRootCheck> {
+            $crate::operator::OperatorStore::require(&*store, &sessions, $crate::accounts::Role::Admin)?;
+            Ok($crate::layout::check_data_root(::std::path::Path::new(&path)))
+        }
+
+        // Primeiro uso: abre o seletor nativo de pasta e valida antes de salvar.
+        #[tauri::command]
+        pub async fn pick_data_root(
+            app:      ::tauri::AppHandle,
+            store:    ::tauri::State<'_, $crate::settings::SettingsStore<$app>>,
+            slot:     ::tauri::State<'_, $crate::BackendSlot>,
+            sessions: ::tauri::State<'_, $crate::accounts::Sessions>,
+        ) -> $crate::AppResult<Option<$crate::settings::SettingsView<$app>>> {
+            use ::tauri_plugin_dialog::DialogExt;
+            $crate::operator::OperatorStore::require(&*store, &sessions, $crate::accounts::Role::Admin)?;
+            let Some(picked) = app.dialog().file().blocking_pick_folder() else {
+                return Ok(None);
+            };
+            let root = picked.into_path().map_err(|e| $crate::AppError::invalid_input("path", e))?;
+            store.apply_data_root(&slot, root).map(Some)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+    #[serde(default)]
+    struct Extra {
+        images_dir: Option<PathBuf>,
+    }
+
+    impl AppSettings for Extra {
+        const ENV_VARS: &'static [&'static str] = &[];
+    }
+
+    #[test]
+    fn app_fields_share_the_settings_json() {
+        let raw = r#"{"data_root": "/dados", "images_dir": "/imagens", "idle_lock_minutes": 5}"#;
+        let settings: Settings<Extra> = serde_json::from_str(raw).unwrap();
+        assert_eq!(settings.data_root, Some(PathBuf::from("/dados")));
+        assert_eq!(settings.app.images_dir, Some(PathBuf::from("/imagens")));
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["images_dir"], "/imagens");
+        assert!(json.get("app").is_none());
+    }
+
+    #[test]
+    fn update_persists_to_the_settings_file() {
+        let dir = std::env::temp_dir().join(format!("settings_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = SettingsStore::<Extra>::load(&dir, dir.join("data"));
+        store.update(|s| {
+            s.idle_lock_minutes = Some(3);
+            s.app.images_dir = Some(PathBuf::from("/imagens"));
+        }).unwrap();
+        let reloaded = SettingsStore::<Extra>::load(&dir, dir.join("data"));
+        assert_eq!(reloaded.idle_lock(), Duration::from_secs(180));
+        assert_eq!(reloaded.get().app, store.get().app);
+        assert!(matches!(reloaded.apply_data_root(&BackendSlot::default(), dir.clone()), Err(AppError::InvalidDataRoot(_))));
+    }
+}
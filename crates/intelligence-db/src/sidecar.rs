@@ -0,0 +1,29 @@
+// Bancos SQLite auxiliares do app (índices, dados próprios), separados do
+// intelligence.db: a ingestão Python continua dona do banco principal e o app
+// só escreve nele para expurgar evidências vencidas (retention.rs). Cada sidecar
+// tem migrações numeradas por `user_version`.
+
+use rusqlite::{Connection, Result};
+use std::path::Path;
+use std::time::Duration;
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// `migrations[n]` leva o banco da versão n para n+1
+pub fn open(path: &Path, migrations: &[&str]) -> Result<Connection> {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let mut conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON")?;
+
+    let version: usize = conn.query_row("PRAGMA user_version", [], |r| r.get(0))?;
+    for (n, sql) in migrations.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.execute_batch(&format!("PRAGMA user_version = {}", n + 1))?;
+        tx.commit()?;
+    }
+    Ok(conn)
+}
@@ -0,0 +1,97 @@
+// Backend SQLite: pool somente-leitura sobre o intelligence.db local.
+
+use rusqlite::types::{ToSqlOutput, ValueRef};
+use rusqlite::{params_from_iter, Row, ToSql};
+use std::path::Path;
+
+use crate::backend::{Backend, Dialect, Record, Value};
+use crate::error::Result;
+use crate::introspect::SchemaInfo;
+use crate::pool::{ReadPool, DEFAULT_POOL_SIZE};
+
+pub struct SqliteBackend {
+    pool:   ReadPool,
+    schema: SchemaInfo,
+}
+
+impl SqliteBackend {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut backend = SqliteBackend {
+            pool:   ReadPool::open(path, DEFAULT_POOL_SIZE)?,
+            schema: SchemaInfo::default(),
+        };
+        backend.schema = SchemaInfo::detect(&backend)?;
+        Ok(backend)
+    }
+
+    pub fn pool(&self) -> &ReadPool {
+        &self.pool
+    }
+}
+
+impl ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self {
+            Value::Null    => ToSqlOutput::Borrowed(ValueRef::Null),
+            Value::Int(v)  => ToSqlOutput::Borrowed(ValueRef::Integer(*v)),
+            Value::Real(v) => ToSqlOutput::Borrowed(ValueRef::Real(*v)),
+            Value::Text(v) => ToSqlOutput::Borrowed(ValueRef::Text(v.as_bytes())),
+        })
+    }
+}
+
+impl Record for Row<'_> {
+    fn opt_text(&self, idx: usize) -> Result<Option<String>> {
+        Ok(self.get(idx)?)
+    }
+
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>> {
+        Ok(self.get(idx)?)
+    }
+
+    fn opt_real(&self, idx: usize) -> Result<Option<f64>> {
+        Ok(self.get(idx)?)
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn dialect(&self) -> Dialect {
+        Dialect::Sqlite
+    }
+
+    fn describe(&self) -> String {
+        format!("sqlite:{} (journal_mode={})", self.pool.path().display(), self.pool.journal_mode())
+    }
+
+    fn schema(&self) -> &SchemaInfo {
+        &self.schema
+    }
+
+    fn attach(&self, alias: &str, path: &Path) -> Result<()> {
+        Ok(self.pool.attach(alias, path)?)
+    }
+
+    fn is_attached(&self, alias: &str) -> bool {
+        self.pool.is_attached(alias)
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize> {
+        let conn = self.pool.open_writer()?;
+        Ok(conn.execute(sql, params_from_iter(params))?)
+    }
+
+    fn for_each(
+        &self,
+        sql:    &str,
+        params: &[Value],
+        f:      &mut dyn FnMut(&dyn Record) -> Result<()>,
+    ) -> Result<()> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare_cached(sql)?;
+        let mut rows = stmt.query(params_from_iter(params))?;
+        while let Some(row) = rows.next()? {
+            f(row)?;
+        }
+        Ok(())
+    }
+}
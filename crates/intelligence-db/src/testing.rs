@@ -0,0 +1,136 @@
+// Banco de teste em memória com o schema da ingestão (intelligence/intelligence_db.py,
+// versão SQLite). A conexão de escrita mantém o banco vivo e é onde cada teste
+// insere seus dados; o backend lê pelo pool, como no app.
+
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+use crate::backend::{Backend, Dialect, Record, Value};
+use crate::error::Result;
+use crate::introspect::SchemaInfo;
+use crate::sqlite::SqliteBackend;
+
+pub const SCHEMA: &str = "
+CREATE TABLE individuals (
+    id              TEXT PRIMARY KEY,
+    name            TEXT NOT NULL,
+    aliases         TEXT,
+    category        TEXT NOT NULL,
+    source          TEXT NOT NULL,
+    birth_date      TEXT,
+    sex             TEXT,
+    height_cm       REAL,
+    weight_kg       REAL,
+    eye_color       TEXT,
+    hair_color      TEXT,
+    nationalities   TEXT,
+    languages       TEXT,
+    occupation      TEXT,
+    description     TEXT,
+    reward          TEXT,
+    url             TEXT,
+    img_url         TEXT,
+    img_path        TEXT,
+    has_embedding   INTEGER DEFAULT 0,
+    first_seen      TEXT,
+    last_seen       TEXT,
+    ingested_at     TEXT DEFAULT (datetime('now'))
+);
+CREATE TABLE crimes (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    individual_id   TEXT REFERENCES individuals(id),
+    crime           TEXT NOT NULL,
+    severity        TEXT
+);
+CREATE TABLE locations (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    individual_id   TEXT REFERENCES individuals(id),
+    type            TEXT NOT NULL,
+    country         TEXT,
+    state           TEXT,
+    city            TEXT,
+    details         TEXT
+);
+CREATE TABLE face_embeddings (
+    individual_id   TEXT PRIMARY KEY REFERENCES individuals(id),
+    embedding       BLOB,
+    embedding_blob  BLOB,
+    model           TEXT DEFAULT 'ArcFace',
+    created_at      TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE individual_images (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    individual_id   TEXT REFERENCES individuals(id),
+    img_url         TEXT,
+    img_path        TEXT,
+    caption         TEXT,
+    is_primary      INTEGER DEFAULT 0
+);
+CREATE TABLE evidence (
+    id              TEXT PRIMARY KEY,
+    individual_id   TEXT NOT NULL REFERENCES individuals(id),
+    camera_id       TEXT,
+    file_hash       TEXT NOT NULL,
+    file_path       TEXT NOT NULL,
+    captured_at     TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    match_distance    REAL,
+    match_probability REAL,
+    match_confidence  TEXT,
+    model             TEXT,
+    model_version     TEXT
+);
+CREATE TABLE threat_scores (
+    individual_id   TEXT PRIMARY KEY REFERENCES individuals(id),
+    score           FLOAT DEFAULT 1.0,
+    factors_json    TEXT,
+    updated_at      TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+";
+
+pub struct Fixture {
+    pub conn: Connection,
+    pub db:   SqliteBackend,
+}
+
+impl Fixture {
+    // `name` distingue o banco de cada teste (rodam em paralelo no mesmo processo).
+    // `seed` roda antes de abrir o backend, que detecta o schema na abertura.
+    pub fn new(name: &str, seed: &str) -> Fixture {
+        let uri = format!("file:{name}?mode=memory&cache=shared");
+        let conn = Connection::open(&uri).unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn.execute_batch(seed).unwrap();
+        let db = SqliteBackend::open(Path::new(&uri)).unwrap();
+        Fixture { conn, db }
+    }
+}
+
+// O mesmo banco visto como um backend sem ATTACH (o Postgres): exercita os
+// caminhos que leem os sidecars à parte
+pub struct Detached<'a>(pub &'a SqliteBackend);
+
+impl Backend for Detached<'_> {
+    fn dialect(&self) -> Dialect {
+        Dialect::Postgres
+    }
+
+    fn describe(&self) -> String {
+        self.0.describe()
+    }
+
+    fn schema(&self) -> &SchemaInfo {
+        self.0.schema()
+    }
+
+    fn for_each(&self, sql: &str, params: &[Value], f: &mut dyn FnMut(&dyn Record) -> Result<()>) -> Result<()> {
+        self.0.for_each(sql, params, f)
+    }
+}
+
+// Pasta temporária vazia, só deste teste
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("intelligence_db_test_{}_{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -27,6 +27,10 @@ PG_USER = os.getenv("DB_USER", "ghost")
 PG_PASS = os.getenv("DB_PASS", "protocol")
 PG_PORT = os.getenv("DB_PORT", "5432")
 
+# Revisões dos avistamentos feitas no dashboard (sidecar review.db, fora deste banco):
+# por padrão ao lado do intelligence.db, o mesmo lugar em que o dashboard o grava
+REVIEW_DB = os.getenv("OSS_REVIEW_DB") or os.path.join(os.path.dirname(DB_FILE), "review.db")
+
 # ─────────────────────────────────────────────────────────────────
 # CLASSE DE ABSTRAÇÃO DB
 # ─────────────────────────────────────────────────────────────────
@@ -151,7 +155,13 @@ CREATE TABLE IF NOT EXISTS evidence (
     camera_id       TEXT, -- ID da câmera de origem (Fase 13)
     file_hash       TEXT NOT NULL,
     file_path       TEXT NOT NULL,
-    captured_at     TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    captured_at     TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    -- Proveniência do match (NULL = desconhecido; nunca preencher com padrão)
+    match_distance    REAL,
+    match_probability REAL,
+    match_confidence  TEXT, -- HIGH / MEDIUM / LOW
+    model             TEXT,
+    model_version     TEXT
 );
 
 CREATE TABLE IF NOT EXISTS threat_scores (
@@ -163,6 +173,25 @@ CREATE TABLE IF NOT EXISTS threat_scores (
 
 """
 
+# Colunas novas de `evidence` para bancos criados antes delas (CREATE IF NOT EXISTS não altera)
+EVIDENCE_PROVENANCE_COLUMNS = [
+    ("match_distance", "REAL"),
+    ("match_probability", "REAL"),
+    ("match_confidence", "TEXT"),
+    ("model", "TEXT"),
+    ("model_version", "TEXT"),
+]
+
+def _migrate_evidence_provenance(db: DB):
+    if db.type == "sqlite":
+        existing = {r[1] for r in db.conn.execute("PRAGMA table_info(evidence)").fetchall()}
+        for col, typ in EVIDENCE_PROVENANCE_COLUMNS:
+            if col not in existing:
+                db.conn.execute(f"ALTER TABLE evidence ADD COLUMN {col} {typ}")
+    else:
+        for col, typ in EVIDENCE_PROVENANCE_COLUMNS:
+            db.execute(f"ALTER TABLE evidence ADD COLUMN IF NOT EXISTS {col} {typ}")
+
 def init_db():
     db = DB()
     if db.type == "sqlite":
@@ -176,6 +205,7 @@ def init_db():
         # Habilitar pgvector no Postgres
         db.execute("CREATE EXTENSION IF NOT EXISTS vector;")
         db.execute(SCHEMA_SQL)
+    _migrate_evidence_provenance(db)
     db.commit()
     db.close()
     print(f"[db] Banco inicializado ({db.type}) com suporte vetorial.")
@@ -353,28 +383,63 @@ def get_all_embeddings_for_index(db: DB) -> List[Dict]:
 # FASE 16 — CADEIA DE CUSTÓDIA (SHA-256)
 # ─────────────────────────────────────────────────────────────────
 
-def register_evidence(db: DB, evidence_id: str, individual_id: str, file_hash: str, file_path: str, camera_id: str = None):
+def register_evidence(db: DB, evidence_id: str, individual_id: str, file_hash: str, file_path: str, camera_id: str = None,
+                      match: Optional[Dict] = None):
     """
     Registra uma evidência (foto/frame) na Cadeia de Custódia.
     Design Append-Only: Rejeita se o ID já existir.
+    `match` (do BiometricProcessor): score (distância), match_probability,
+    identity_confidence, model, model_version — o que faltar fica NULL.
     """
     # Verificar se já existe (Proteção de Imutabilidade)
     cur = db.execute("SELECT 1 FROM evidence WHERE id = ?", (evidence_id,))
     if cur.fetchone():
         raise PermissionError(f"Violação de Imutabilidade: Evidência {evidence_id} já existe.")
 
-    q = "INSERT INTO evidence (id, individual_id, camera_id, file_hash, file_path) VALUES (?, ?, ?, ?, ?)"
-    db.execute(q, (evidence_id, individual_id, camera_id, file_hash, file_path))
+    if match is None:
+        # Evidências da ingestão (sem match): não dependem das colunas de proveniência
+        q = "INSERT INTO evidence (id, individual_id, camera_id, file_hash, file_path) VALUES (?, ?, ?, ?, ?)"
+        db.execute(q, (evidence_id, individual_id, camera_id, file_hash, file_path))
+    else:
+        q = """
+            INSERT INTO evidence (
+                id, individual_id, camera_id, file_hash, file_path,
+                match_distance, match_probability, match_confidence, model, model_version
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        """
+        db.execute(q, (
+            evidence_id, individual_id, camera_id, file_hash, file_path,
+            match.get("score"), match.get("match_probability"), match.get("identity_confidence"),
+            match.get("model"), match.get("model_version"),
+        ))
     db.commit()
 
 
+def get_rejected_evidence_ids() -> set:
+    """IDs de evidências rejeitadas (falso positivo) na revisão do dashboard."""
+    if not os.path.isfile(REVIEW_DB):
+        # Nenhuma revisão feita ainda (o dashboard cria o arquivo na primeira)
+        return set()
+    try:
+        conn = sqlite3.connect(f"file:{REVIEW_DB}?mode=ro", uri=True)
+        try:
+            cur = conn.execute("SELECT evidence_id FROM sighting_reviews WHERE status = 'rejected'")
+            return {r[0] for r in cur.fetchall()}
+        finally:
+            conn.close()
+    except sqlite3.Error as e:
+        print(f"[db] Erro ao ler revisões ({REVIEW_DB}): {e}")
+        return set()
+
+
 def get_evidence(db: DB, individual_id: str) -> List[Dict]:
-    """Retorna todas as evidências de um indivíduo."""
+    """Retorna as evidências de um indivíduo, sem as rejeitadas na revisão."""
     cur = db.execute(
         "SELECT * FROM evidence WHERE individual_id = ? ORDER BY captured_at DESC",
         (individual_id,)
     )
-    return [dict(r) for r in cur.fetchall()]
+    rejected = get_rejected_evidence_ids()
+    return [dict(r) for r in cur.fetchall() if r["id"] not in rejected]
 
 
 def get_all_evidence_hashes(db: DB) -> List[Dict]:
@@ -22,6 +22,13 @@ os.environ["TF_CPP_MIN_LOG_LEVEL"] = "3"
 # Distância L2 → probabilidade; thresholds para HIGH/MEDIUM/LOW (calibrar com match_logs depois)
 # k menor = probabilidade decai mais devagar (d=0.25 pode virar MEDIUM/HIGH)
 PROB_K = 1.2  # probability = exp(-distance * k)
+# Proveniência gravada com cada evidência (evidence.model / model_version)
+MODEL_NAME = "ArcFace"
+try:
+    from importlib.metadata import version as _pkg_version
+    MODEL_VERSION = f"deepface-{_pkg_version('deepface')}"
+except Exception:
+    MODEL_VERSION = None
 CONFIDENCE_HIGH_PROB = 0.85
 CONFIDENCE_MEDIUM_PROB = 0.60
 
@@ -314,7 +321,7 @@ class BiometricProcessor:
         try:
             objs = DeepFace.represent(
                 img_path=face_img,
-                model_name="ArcFace",
+                model_name=MODEL_NAME,
                 enforce_detection=False,
                 detector_backend="skip"
             )
@@ -357,6 +364,8 @@ class BiometricProcessor:
                     "score": match_data["score"],
                     "match_probability": match_data.get("match_probability"),
                     "identity_confidence": match_data.get("identity_confidence"),
+                    "model": MODEL_NAME,
+                    "model_version": MODEL_VERSION,
                 }
                 return embedding, match
             else:
@@ -287,7 +287,7 @@ class LivePipeline:
             file_hash = hashlib.sha256(f.read()).hexdigest()
         ev_id = str(uuid_pkg.uuid4())
         try:
-            register_evidence(db, ev_id, uid, file_hash, str(file_path), camera_id=self.camera_id)
+            register_evidence(db, ev_id, uid, file_hash, str(file_path), camera_id=self.camera_id, match=match)
         except Exception as e:
             log.error(f"Falha ao registrar evidência pericial: {e}")
 